@@ -0,0 +1,187 @@
+//! `dyson-macros` provide procedural macros for [dyson](https://hayas1.github.io/dyson-rs/dyson).
+//!
+//! # usage
+//! in `Cargo.toml`
+//! ```toml
+//! [dependencies]
+//!     dyson = { git = "https://github.com/hayas1/dyson-rs" }
+//!     dyson-macros = { git = "https://github.com/hayas1/dyson-rs" }
+//! ```
+
+use dyson::{JsonIndexer, JsonPath, Value};
+use proc_macro::{Delimiter, Literal, Spacing, TokenStream, TokenTree};
+use std::path::PathBuf;
+
+/// parse json file at compile time, and expand into `dyson::ValueBuilder` that can be used in `const` context.
+/// the path is relative to `CARGO_MANIFEST_DIR` of the crate that invoke this macro.
+/// invalid json cause compile error, so embedded default config is always valid.
+/// # examples
+/// ```ignore
+/// // `defaults.json`
+/// // {
+/// //     "language": "rust",
+/// //     "version": 0.1
+/// // }
+/// use dyson::{Value, ValueBuilder};
+/// use dyson_macros::include_json;
+/// const DEFAULTS: ValueBuilder = include_json!("defaults.json");
+///
+/// let defaults: Value = DEFAULTS.build();
+/// assert_eq!(defaults["language"], Value::String("rust".to_string()));
+/// ```
+#[proc_macro]
+pub fn include_json(input: TokenStream) -> TokenStream {
+    match expand_include_json(input) {
+        Ok(expanded) => expanded,
+        Err(message) => format!("::std::compile_error!({:?})", message).parse().expect("compile_error is valid tokens"),
+    }
+}
+
+fn expand_include_json(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = input.into_iter();
+    let path = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => unquote(&literal)?,
+        _ => return Err("include_json! expect exactly one string literal".to_string()),
+    };
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| e.to_string())?;
+    let full_path = PathBuf::from(manifest_dir).join(path);
+    let json = Value::load(&full_path).map_err(|e| format!("{}: {}", full_path.display(), e))?;
+    // `include_str!` make cargo to track changes of the json file
+    let tracked = format!("const _: &str = ::std::include_str!({:?});", full_path.display().to_string());
+    format!("{{ {} {} }}", tracked, builder_expression(&json)).parse().map_err(|e| format!("{:?}", e))
+}
+
+/// define `const` of `dyson::StaticPath` from dotted path strings such as `"keyword[0]"`. the paths are parsed at
+/// compile time by `dyson::JsonPath::from_dotted`, so invalid path cause compile error.
+/// # examples
+/// ```ignore
+/// use dyson::Value;
+/// use dyson_macros::json_paths;
+/// json_paths! {
+///     VERSION = "version";
+///     pub FIRST_KEYWORD = "keyword[0]";
+/// }
+///
+/// let json = Value::parse(r#"{"version": 0.1, "keyword": ["rust", "json"]}"#).unwrap();
+/// assert_eq!(json[VERSION], Value::Float(0.1));
+/// assert_eq!(json[FIRST_KEYWORD], Value::String("rust".to_string()));
+/// ```
+#[proc_macro]
+pub fn json_paths(input: TokenStream) -> TokenStream {
+    match expand_json_paths(input) {
+        Ok(expanded) => expanded,
+        Err(message) => {
+            format!("::std::compile_error!({:?});", message).parse().expect("compile_error is valid tokens")
+        }
+    }
+}
+
+fn expand_json_paths(input: TokenStream) -> Result<TokenStream, String> {
+    let tokens: Vec<_> = input.into_iter().collect();
+    let mut consts = Vec::new();
+    for entry in tokens.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';')).filter(|e| !e.is_empty()) {
+        let eq = entry
+            .iter()
+            .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '=' && p.spacing() == Spacing::Alone))
+            .ok_or("json_paths! expect entries like `NAME = \"path\";`")?;
+        let (name, prefix) = match entry[..eq].split_last() {
+            Some((TokenTree::Ident(name), prefix)) => (name.to_string(), prefix),
+            _ => return Err("json_paths! expect identifier before `=`".to_string()),
+        };
+        let dotted = match &entry[eq + 1..] {
+            [TokenTree::Literal(literal)] => unquote(literal)?,
+            [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => {
+                match &group.stream().into_iter().collect::<Vec<_>>()[..] {
+                    [TokenTree::Literal(literal)] => unquote(literal)?,
+                    _ => return Err(format!("json_paths! expect one string literal for {}", name)),
+                }
+            }
+            _ => return Err(format!("json_paths! expect one string literal for {}", name)),
+        };
+        let path = JsonPath::from_dotted(&dotted).map_err(|e| format!("{}: {}", name, e))?;
+        consts.push(format!(
+            "{} const {}: ::dyson::StaticPath = {};",
+            prefix.iter().cloned().collect::<TokenStream>(),
+            name,
+            static_path_expression(&path)
+        ));
+    }
+    consts.concat().parse().map_err(|e| format!("{:?}", e))
+}
+
+/// get rust expression of `dyson::StaticPath` that is same as `path`.
+fn static_path_expression(path: &JsonPath) -> String {
+    format!(
+        "::dyson::StaticPath(&[{}])",
+        path.iter()
+            .map(|i| match i {
+                JsonIndexer::ObjInd(k) => format!("::dyson::StaticIndexer::ObjInd({:?})", k),
+                JsonIndexer::ArrInd(i) => format!("::dyson::StaticIndexer::ArrInd({}usize)", i),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// get content of string literal such as `"path/to/file.json"` or `r#"path/to/file.json"#`.
+fn unquote(literal: &Literal) -> Result<String, String> {
+    let quoted = literal.to_string();
+    if let Some(raw) = quoted.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let delimiter = "#".repeat(hashes);
+        return raw
+            .strip_prefix(&delimiter)
+            .and_then(|s| s.strip_suffix(&delimiter))
+            .and_then(|s| s.strip_prefix('"'))
+            .and_then(|s| s.strip_suffix('"'))
+            .map(|s| s.to_string())
+            .ok_or(format!("expect string literal, but {}", quoted));
+    }
+    let content = quoted
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or(format!("expect string literal, but {}", quoted))?;
+    let (mut unquoted, mut chars) = (String::new(), content.chars());
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, false) => unquoted.push(c),
+            (_, true) => match chars.next() {
+                Some('"') => unquoted.push('"'),
+                Some('\'') => unquoted.push('\''),
+                Some('\\') => unquoted.push('\\'),
+                Some('n') => unquoted.push('\n'),
+                Some('r') => unquoted.push('\r'),
+                Some('t') => unquoted.push('\t'),
+                Some('0') => unquoted.push('\0'),
+                _ => return Err(format!("unsupported escape sequence in {}", quoted)),
+            },
+        }
+    }
+    Ok(unquoted)
+}
+
+/// get rust expression of `dyson::ValueBuilder` that build `value`.
+fn builder_expression(value: &Value) -> String {
+    match value {
+        Value::Object(m) => format!(
+            "::dyson::ValueBuilder::Object(&[{}])",
+            m.iter().map(|(k, v)| format!("({:?}, {})", k, builder_expression(v))).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Array(v) => format!(
+            "::dyson::ValueBuilder::Array(&[{}])",
+            v.iter().map(builder_expression).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Bool(b) => format!("::dyson::ValueBuilder::Bool({})", b),
+        Value::Null => "::dyson::ValueBuilder::Null".to_string(),
+        Value::String(s) => format!("::dyson::ValueBuilder::String({:?})", s),
+        Value::Integer(i) => format!("::dyson::ValueBuilder::Integer({}i64)", i),
+        Value::Float(f) if f.is_finite() => format!("::dyson::ValueBuilder::Float({:?}f64)", f),
+        Value::Float(f) if f.is_sign_positive() => "::dyson::ValueBuilder::Float(f64::INFINITY)".to_string(),
+        Value::Float(_) => "::dyson::ValueBuilder::Float(f64::NEG_INFINITY)".to_string(),
+        Value::RawNumber(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => builder_expression(&Value::Integer(i)),
+            (None, Some(f)) => builder_expression(&Value::Float(f)),
+            (None, None) => unreachable!("raw number is valid json number"),
+        },
+    }
+}
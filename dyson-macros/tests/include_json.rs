@@ -0,0 +1,12 @@
+use dyson::{Value, ValueBuilder};
+use dyson_macros::include_json;
+
+const DEFAULTS: ValueBuilder = include_json!("tests/fixtures/defaults.json");
+
+#[test]
+fn test_include_json() {
+    let defaults = DEFAULTS.build();
+    assert_eq!(defaults, Value::load(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/defaults.json")).unwrap());
+    assert_eq!(defaults["escaped"], Value::String("quote \" backslash \\ unicode ù".to_string()));
+    assert_eq!(defaults["keyword"][4], Value::Float(1e300));
+}
@@ -0,0 +1,21 @@
+use dyson::{JsonPath, StaticPath, Value};
+use dyson_macros::json_paths;
+
+json_paths! {
+    VERSION = "version";
+    pub FIRST_KEYWORD = "keyword[0]";
+    pub(crate) ESCAPED = r#"nested["a.b"]["q\""]"#;
+    ROOT = "";
+}
+
+#[test]
+fn test_json_paths() {
+    let json =
+        Value::parse(r#"{"version": 0.1, "keyword": ["rust", "json"], "nested": {"a.b": {"q\"": null}}}"#).unwrap();
+    assert_eq!(json[VERSION], Value::Float(0.1));
+    assert_eq!(json[FIRST_KEYWORD], Value::String("rust".to_string()));
+    assert_eq!(json[ESCAPED], Value::Null);
+    assert_eq!(json[ROOT], json);
+    assert_eq!(FIRST_KEYWORD, StaticPath(&[dyson::StaticIndexer::ObjInd("keyword"), dyson::StaticIndexer::ArrInd(0)]));
+    assert_eq!(JsonPath::from(ESCAPED), JsonPath::from_dotted("nested[\"a.b\"][\"q\\\"\"]").unwrap());
+}
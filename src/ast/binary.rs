@@ -0,0 +1,49 @@
+//! binary data in json, with `base64` feature. json has no binary type, so bytes are stored as string of standard
+//! base64 with padding, such as `"AQID"` for `[1, 2, 3]`.
+
+use super::Value;
+use ::base64::{engine::general_purpose::STANDARD, Engine};
+
+impl Value {
+    /// get [`Value::String`] of base64 that encode `bytes`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::from_bytes(b"dyson");
+    /// assert_eq!(json, Value::from("ZHlzb24="));
+    /// assert_eq!(json.as_base64_bytes(), Some(b"dyson".to_vec()));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Value {
+        Value::String(STANDARD.encode(bytes))
+    }
+
+    /// decode [`Value::String`] of base64 into bytes. if value is not `String` or it is not valid base64, return `None`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"blob": "AQID", "text": "not base64!"}"#).unwrap();
+    /// assert_eq!(json["blob"].as_base64_bytes(), Some(vec![1, 2, 3]));
+    /// assert_eq!(json["text"].as_base64_bytes(), None);
+    /// assert_eq!(json.as_base64_bytes(), None);
+    /// ```
+    pub fn as_base64_bytes(&self) -> Option<Vec<u8>> {
+        self.get_string().and_then(|s| STANDARD.decode(s).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_round_trip() {
+        for bytes in [&b""[..], &[0], &[0xff, 0xfe], &[1, 2, 3, 4], &(0..=255).collect::<Vec<_>>()] {
+            let json = Value::from_bytes(bytes);
+            assert!(json.is_string());
+            assert_eq!(json.as_base64_bytes().as_deref(), Some(bytes));
+            assert_eq!(Value::parse(json.to_string()).unwrap().as_base64_bytes().as_deref(), Some(bytes));
+        }
+        assert_eq!(Value::from("AQ").as_base64_bytes(), None);
+        assert_eq!(Value::Integer(1).as_base64_bytes(), None);
+    }
+}
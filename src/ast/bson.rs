@@ -0,0 +1,142 @@
+//! conversion between json and bson, with `bson` feature.
+//! types only in bson cannot be represented in json, so they are converted with loss.
+//! - `ObjectId` is converted into string of its hex, such as `"65f1c0ffee0123456789abcd"`.
+//! - `DateTime` is converted into string of RFC 3339, such as `"2024-03-13T12:00:00Z"`.
+//!   if it is out of range of RFC 3339, it is converted into integer of milliseconds since unix epoch.
+//! - `Int32` and `Int64` are converted into integer, and `Double` is converted into float.
+//! - `Undefined` is converted into null.
+//! - other types such as `Binary`, `Decimal128`, and `Timestamp` are converted into relaxed extended json,
+//!   such as `{"$numberDecimal": "1.5"}`.
+//!
+//! json integer is converted into `Int64`, so conversion from json into bson is lossless.
+
+use super::{into::ConvertError, number::raw_number, Value};
+use ::bson::{Bson, Document};
+
+impl From<&Value> for Bson {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Object(object) => Bson::Document(object.iter().map(|(k, v)| (k.to_string(), v.into())).collect()),
+            Value::Array(array) => Bson::Array(array.iter().map(Bson::from).collect()),
+            Value::Bool(bool) => Bson::Boolean(*bool),
+            Value::Null => Bson::Null,
+            Value::String(string) => Bson::String(string.clone()),
+            Value::Integer(integer) => Bson::Int64(*integer),
+            Value::Float(float) => Bson::Double(*float),
+            Value::RawNumber(raw) => {
+                let raw = raw.to_string();
+                raw.parse().map(Bson::Int64).unwrap_or_else(|_| Bson::Double(raw.parse().unwrap_or(f64::NAN)))
+            }
+        }
+    }
+}
+impl From<Bson> for Value {
+    fn from(bson: Bson) -> Self {
+        match bson {
+            Bson::Document(document) => document.into(),
+            Bson::Array(array) => Value::Array(array.into_iter().map(Value::from).collect()),
+            Bson::Boolean(bool) => Value::Bool(bool),
+            Bson::Null | Bson::Undefined => Value::Null,
+            Bson::String(string) => Value::String(string),
+            Bson::Int32(integer) => Value::Integer(integer.into()),
+            Bson::Int64(integer) => Value::Integer(integer),
+            Bson::Double(float) => Value::Float(float),
+            Bson::ObjectId(oid) => Value::String(oid.to_hex()),
+            Bson::DateTime(datetime) => match datetime.try_to_rfc3339_string() {
+                Ok(rfc3339) => Value::String(rfc3339),
+                Err(_) => Value::Integer(datetime.timestamp_millis()),
+            },
+            other => from_extjson(other.into_relaxed_extjson()),
+        }
+    }
+}
+fn from_extjson(extjson: serde_json::Value) -> Value {
+    match extjson {
+        serde_json::Value::Object(object) => object.into_iter().map(|(k, v)| (k, from_extjson(v))).collect(),
+        serde_json::Value::Array(array) => Value::Array(array.into_iter().map(from_extjson).collect()),
+        serde_json::Value::Bool(bool) => Value::Bool(bool),
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::String(string) => Value::String(string),
+        serde_json::Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+            (Some(integer), _) => Value::Integer(integer),
+            (None, _) if number.is_u64() => Value::RawNumber(raw_number(number.to_string())),
+            (None, float) => Value::Float(float.unwrap_or(f64::NAN)),
+        },
+    }
+}
+
+/// bson document is object, so only [`Value::Object`] can be converted into it.
+/// # examples
+/// ```
+/// use bson::{doc, Document};
+/// use dyson::Value;
+/// let json = Value::parse(r#"{"name": "dyson", "tags": ["json", 1]}"#).unwrap();
+/// assert_eq!(Document::try_from(&json).unwrap(), doc! { "name": "dyson", "tags": ["json", 1_i64] });
+/// assert!(Document::try_from(&Value::Null).is_err());
+/// ```
+impl TryFrom<&Value> for Document {
+    type Error = ConvertError;
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match Bson::from(value) {
+            Bson::Document(document) => Ok(document),
+            _ => Err(ConvertError::UnexpectedType { expected: "Object", found: value.node_type() }),
+        }
+    }
+}
+/// convert bson document into [`Value::Object`]. see [module document](self) for types only in bson.
+/// # examples
+/// ```
+/// use bson::{doc, oid::ObjectId};
+/// use dyson::Value;
+/// let oid = ObjectId::parse_str("65f1c0ffee0123456789abcd").unwrap();
+/// let json = Value::from(doc! { "_id": oid, "count": 3 });
+/// assert_eq!(json, Value::parse(r#"{"_id": "65f1c0ffee0123456789abcd", "count": 3}"#).unwrap());
+/// ```
+impl From<Document> for Value {
+    fn from(document: Document) -> Self {
+        document.into_iter().map(|(k, v)| (k, Value::from(v))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::bson::{doc, spec::BinarySubtype, Binary, DateTime, Decimal128};
+
+    #[test]
+    fn test_bson_round_trip() {
+        let json = Value::parse(r#"{"a": [1, 2.5, "three", null, true], "b": {"c": {}}, "d": -9007199254740993}"#);
+        let json = json.unwrap();
+        let document = Document::try_from(&json).unwrap();
+        assert_eq!(document.get_i64("d").unwrap(), -9007199254740993);
+        assert_eq!(Value::from(document), json);
+    }
+
+    #[test]
+    fn test_bson_lossy() {
+        let document = doc! {
+            "date": DateTime::from_millis(0),
+            "int32": 1_i32,
+            "binary": Binary { subtype: BinarySubtype::Generic, bytes: vec![1, 2, 3] },
+            "decimal": "1.5".parse::<Decimal128>().unwrap(),
+            "undefined": Bson::Undefined,
+        };
+        let json = Value::from(document);
+        assert_eq!(json["date"], Value::from("1970-01-01T00:00:00Z"));
+        assert_eq!(json["int32"], Value::Integer(1));
+        assert_eq!(json["binary"], Value::parse(r#"{"$binary": {"base64": "AQID", "subType": "00"}}"#).unwrap());
+        assert_eq!(json["decimal"], Value::parse(r#"{"$numberDecimal": "1.5"}"#).unwrap());
+        assert_eq!(json["undefined"], Value::Null);
+    }
+
+    #[test]
+    fn test_bson_extjson_escape() {
+        let document = doc! {
+            "symbol": Bson::Symbol("a\u{8}b\u{c}c".to_string()),
+            "code": Bson::JavaScriptCode("f(\"\u{8}\")".to_string()),
+        };
+        let json = Value::from(document);
+        assert_eq!(json["symbol"], [("$symbol", "a\u{8}b\u{c}c")].into_iter().collect());
+        assert_eq!(json["code"], [("$code", "f(\"\u{8}\")")].into_iter().collect());
+    }
+}
@@ -0,0 +1,101 @@
+use super::Value;
+
+/// [`ValueBuilder`] is allocation free representation of [`Value`], so it can be constructed in `const` or `static`.
+/// allocation is deferred until [`ValueBuilder::build`] is called.
+/// # examples
+/// ```
+/// use dyson::{Value, ValueBuilder};
+/// const DEFAULT_CONFIG: ValueBuilder = ValueBuilder::Object(&[
+///     ("language", Value::static_str("rust")),
+///     ("version", Value::static_int(1)),
+///     ("keyword", ValueBuilder::Array(&[Value::static_str("json"), Value::static_str("parser")])),
+/// ]);
+///
+/// let config = DEFAULT_CONFIG.build();
+/// assert_eq!(config, Value::parse(r#"{"language": "rust", "version": 1, "keyword": ["json", "parser"]}"#).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValueBuilder {
+    /// correspond to [`Value::Object`]. key order is preserved.
+    Object(&'static [(&'static str, ValueBuilder)]),
+
+    /// correspond to [`Value::Array`].
+    Array(&'static [ValueBuilder]),
+
+    /// correspond to [`Value::Bool`].
+    Bool(bool),
+
+    /// correspond to [`Value::Null`].
+    Null,
+
+    /// correspond to [`Value::String`].
+    String(&'static str),
+
+    /// correspond to [`Value::Integer`].
+    Integer(i64),
+
+    /// correspond to [`Value::Float`].
+    Float(f64),
+}
+
+impl ValueBuilder {
+    /// allocate and build [`Value`]. this method's complexity is **O(|value|)**.
+    pub fn build(&self) -> Value {
+        match *self {
+            ValueBuilder::Object(entries) => entries.iter().map(|(k, v)| (k.to_string(), v.build())).collect(),
+            ValueBuilder::Array(elements) => elements.iter().map(ValueBuilder::build).collect(),
+            ValueBuilder::Bool(b) => Value::Bool(b),
+            ValueBuilder::Null => Value::Null,
+            ValueBuilder::String(s) => Value::String(s.to_string()),
+            ValueBuilder::Integer(i) => Value::Integer(i),
+            ValueBuilder::Float(f) => Value::Float(f),
+        }
+    }
+}
+
+impl From<ValueBuilder> for Value {
+    fn from(builder: ValueBuilder) -> Self {
+        builder.build()
+    }
+}
+impl From<&ValueBuilder> for Value {
+    fn from(builder: &ValueBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// constructors that can be evaluated in `const` context. see [`ValueBuilder`] also.
+impl Value {
+    /// get [`ValueBuilder`] of string, it can be used in `const` context.
+    pub const fn static_str(s: &'static str) -> ValueBuilder {
+        ValueBuilder::String(s)
+    }
+
+    /// get [`ValueBuilder`] of integer, it can be used in `const` context.
+    pub const fn static_int(i: i64) -> ValueBuilder {
+        ValueBuilder::Integer(i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static NESTED: ValueBuilder = ValueBuilder::Object(&[
+        ("null", ValueBuilder::Null),
+        ("nested", ValueBuilder::Object(&[("bool", ValueBuilder::Bool(true)), ("float", ValueBuilder::Float(0.5))])),
+        ("array", ValueBuilder::Array(&[Value::static_int(1), ValueBuilder::Array(&[]), Value::static_str("two")])),
+    ]);
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_build_static_value() {
+        let value = NESTED.build();
+        assert_eq!(value["null"], Value::Null);
+        assert_eq!(value["nested"]["bool"], Value::Bool(true));
+        assert_eq!(value["nested"]["float"], Value::Float(0.5));
+        assert_eq!(value["array"], Value::parse(r#"[1, [], "two"]"#).unwrap());
+        assert_eq!(value.to_string(), r#"{"null":null,"nested":{"bool":true,"float":0.5},"array":[1,[],"two"]}"#);
+        assert_eq!(Value::from(&NESTED), value);
+    }
+}
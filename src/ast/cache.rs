@@ -0,0 +1,143 @@
+use super::Value;
+use crate::syntax::error::Error;
+use linked_hash_map::LinkedHashMap;
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// [`DocumentCache`] cache json documents loaded from files, with LRU eviction bounded by total byte size of the
+/// source files. cached document is reloaded if modified time of its file is changed.
+/// # examples
+/// ```no_run
+/// use dyson::DocumentCache;
+/// let mut cache = DocumentCache::new(64 * 1024 * 1024);
+/// let json = cache.get_or_load("path/to/read.json").unwrap();
+/// println!("{json}");
+///
+/// // second call do not read file unless it is modified
+/// let json = cache.get_or_load("path/to/read.json").unwrap();
+/// println!("{json}");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DocumentCache {
+    capacity: usize,
+    size: usize,
+    entries: LinkedHashMap<PathBuf, CacheEntry>,
+}
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    value: Value,
+    modified: SystemTime,
+    size: usize,
+}
+
+impl DocumentCache {
+    /// get new cache that hold documents up to `capacity` bytes of source files in total.
+    /// a document larger than `capacity` is cached until next load.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, size: 0, entries: LinkedHashMap::new() }
+    }
+
+    /// get cached document of path, or load it if it is not cached or its file is modified after cached.
+    pub fn get_or_load<P: AsRef<Path>>(&mut self, p: P) -> Result<&Value, Error> {
+        let path = p.as_ref().canonicalize()?;
+        let metadata = std::fs::metadata(&path)?;
+        let modified = metadata.modified()?;
+        if !matches!(self.entries.get_refresh(&path), Some(entry) if entry.modified == modified) {
+            self.invalidate(&path);
+            let value = Value::load(&path)?;
+            let size = metadata.len() as usize;
+            self.entries.insert(path.clone(), CacheEntry { value, modified, size });
+            self.size += size;
+            while self.size > self.capacity && self.entries.len() > 1 {
+                if let Some((_, evicted)) = self.entries.pop_front() {
+                    self.size -= evicted.size;
+                }
+            }
+        }
+        Ok(&self.entries[&path].value)
+    }
+
+    /// remove cached document of path. if it is cached, return true.
+    pub fn invalidate<P: AsRef<Path>>(&mut self, p: P) -> bool {
+        let path = p.as_ref().canonicalize().unwrap_or_else(|_| p.as_ref().to_path_buf());
+        match self.entries.remove(&path) {
+            Some(entry) => {
+                self.size -= entry.size;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// remove all cached documents.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.size = 0;
+    }
+
+    /// get number of cached documents.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// check no document is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// get total byte size of source files of cached documents.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// check document of path is cached. this method do not check modified time of its file.
+    pub fn contains<P: AsRef<Path>>(&self, p: P) -> bool {
+        p.as_ref().canonicalize().map_or(false, |path| self.entries.contains_key(&path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_document_cache() {
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            let (a, b, c) = (dir.path().join("a.json"), dir.path().join("b.json"), dir.path().join("c.json"));
+            std::fs::write(&a, r#"{"name": "a"}"#)?;
+            std::fs::write(&b, r#"{"name": "b"}"#)?;
+            std::fs::write(&c, r#"{"name": "c"}"#)?;
+
+            let mut cache = DocumentCache::new(30);
+            assert_eq!(cache.get_or_load(&a)?["name"], Value::from("a"));
+            assert_eq!(cache.get_or_load(&b)?["name"], Value::from("b"));
+            assert_eq!((cache.len(), cache.size()), (2, 26));
+
+            // `a` is recently used, so `b` is evicted
+            cache.get_or_load(&a)?;
+            cache.get_or_load(&c)?;
+            assert!(cache.contains(&a) && !cache.contains(&b) && cache.contains(&c));
+            assert_eq!((cache.len(), cache.size()), (2, 26));
+
+            // modified file is reloaded
+            let cached = std::fs::metadata(&a)?.modified()?;
+            while std::fs::metadata(&a)?.modified()? == cached {
+                std::thread::sleep(Duration::from_millis(10));
+                std::fs::write(&a, r#"{"name": "modified"}"#)?;
+            }
+            assert_eq!(cache.get_or_load(&a)?["name"], Value::from("modified"));
+            assert_eq!((cache.len(), cache.size()), (1, 20));
+
+            assert!(cache.invalidate(&a));
+            assert!(!cache.invalidate(&a));
+            assert!(cache.is_empty());
+            assert!(cache.get_or_load(dir.path().join("not_exist.json")).is_err());
+            Ok(())
+        };
+        result().unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+//! canonical json serialization of [RFC 8785](https://www.rfc-editor.org/rfc/rfc8785) (JSON Canonicalization Scheme).
+//! object keys are sorted by UTF-16 code units, numbers are written like ECMAScript, and no whitespace is included.
+
+use super::Value;
+
+impl Value {
+    /// serialize ast into canonical json bytes of RFC 8785, for hashing or signing deterministically.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"b": [1.50, 1e30], "a": "é"}"#).unwrap();
+    /// assert_eq!(json.canonicalize(), r#"{"a":"é","b":[1.5,1e+30]}"#.as_bytes());
+    /// ```
+    pub fn canonicalize(&self) -> Vec<u8> {
+        self.to_canonical_string().into_bytes()
+    }
+
+    /// serialize ast into canonical json string of RFC 8785. see [`Value::canonicalize`] also.
+    /// numbers are written as IEEE 754 double, so integer larger than 2^53 may lose precision.
+    /// NaN and infinity, which cannot be written in json, are written as `null`.
+    pub fn to_canonical_string(&self) -> String {
+        let mut canonical = String::new();
+        write_canonical(&mut canonical, self);
+        canonical
+    }
+}
+
+fn write_canonical(buf: &mut String, value: &Value) {
+    match value {
+        Value::Object(object) => {
+            let mut entries: Vec<_> = object.iter().collect();
+            entries.sort_by(|(k1, _), (k2, _)| k1.encode_utf16().cmp(k2.encode_utf16()));
+            buf.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    buf.push(',');
+                }
+                write_string(buf, k);
+                buf.push(':');
+                write_canonical(buf, v);
+            }
+            buf.push('}');
+        }
+        Value::Array(array) => {
+            buf.push('[');
+            for (i, v) in array.iter().enumerate() {
+                if i > 0 {
+                    buf.push(',');
+                }
+                write_canonical(buf, v);
+            }
+            buf.push(']');
+        }
+        Value::Bool(bool) => buf.push_str(&bool.to_string()),
+        Value::Null => buf.push_str("null"),
+        Value::String(string) => write_string(buf, string),
+        Value::Integer(integer) => buf.push_str(&number(*integer as f64)),
+        Value::Float(float) => buf.push_str(&number(*float)),
+        Value::RawNumber(raw) => buf.push_str(&number(raw.to_string().parse().unwrap_or(f64::NAN))),
+    }
+}
+
+/// write string with minimal escape of RFC 8785.
+fn write_string(buf: &mut String, s: &str) {
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\u{8}' => buf.push_str("\\b"),
+            '\t' => buf.push_str("\\t"),
+            '\n' => buf.push_str("\\n"),
+            '\u{c}' => buf.push_str("\\f"),
+            '\r' => buf.push_str("\\r"),
+            c if c < ' ' => buf.push_str(&format!("\\u{:04x}", c as u32)),
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
+/// format number like `Number.prototype.toString` of ECMAScript.
+fn number(float: f64) -> String {
+    let abs = float.abs();
+    if !float.is_finite() {
+        "null".to_string()
+    } else if abs == 0.0 {
+        "0".to_string()
+    } else if (1e-6..1e21).contains(&abs) {
+        float.to_string()
+    } else {
+        let exponential = format!("{float:e}");
+        match exponential.split_once('e') {
+            Some((mantissa, exp)) if !exp.starts_with('-') => format!("{mantissa}e+{exp}"),
+            _ => exponential,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::excessive_precision)] // number of RFC 8785 example
+    fn test_canonical_number() {
+        for (float, expected) in [
+            (333333333.33333329, "333333333.3333333"),
+            (1e30, "1e+30"),
+            (4.50, "4.5"),
+            (2e-3, "0.002"),
+            (0.000000000000000000000000001, "1e-27"),
+            (-0.0, "0"),
+            (1e21, "1e+21"),
+            (1e-7, "1e-7"),
+            (999999999999999900000.0, "999999999999999900000"),
+            (-5e-324, "-5e-324"),
+            (f64::NAN, "null"),
+        ] {
+            assert_eq!(number(float), expected);
+        }
+    }
+
+    #[test]
+    fn test_canonical_string() {
+        let json = Value::parse(r#"["\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/"]"#).unwrap();
+        assert_eq!(json.to_canonical_string(), r#"["€$\u000f\nA'B\"\\\\\"/"]"#);
+    }
+
+    #[test]
+    fn test_canonical_sort() {
+        let raw = r#"{"\u20ac": "Euro Sign", "\r": "Carriage Return", "\ufb33": "Hebrew Letter Dalet With Dagesh",
+            "1": "One", "😀": "Emoji: Grinning Face", "\u0080": "Control", "\u00f6": "Latin Small Letter O With Diaeresis"}"#;
+        let canonical = Value::parse(raw).unwrap().to_canonical_string();
+        let keys: Vec<_> =
+            canonical.split(',').map(|entry| entry.trim_start_matches('{').split(':').next().unwrap()).collect();
+        assert_eq!(
+            keys,
+            ["\"\\r\"", "\"1\"", "\"\u{80}\"", "\"\u{f6}\"", "\"\u{20ac}\"", "\"\u{1f600}\"", "\"\u{fb33}\""]
+        );
+
+        let json = Value::parse(r#"{"b": [{"z": 1, "y": 2}], "a": 1}"#).unwrap();
+        assert_eq!(json.to_canonical_string(), r#"{"a":1,"b":[{"y":2,"z":1}]}"#);
+    }
+}
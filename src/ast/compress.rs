@@ -0,0 +1,125 @@
+//! compression of json files. gzip is supported with `gzip` feature, and zstd is supported with `zstd` feature.
+//! [`super::Value::load`] detect compression by magic bytes, and [`super::Value::dump`] by extension of path.
+
+use std::{
+    io::{self, BufRead, Read, Write},
+    path::Path,
+};
+
+/// [`Compression`] is compression format of json file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Compression {
+    Gzip,
+    Zstd,
+}
+impl Compression {
+    /// detect compression by first bytes of input.
+    pub(crate) fn detect(magic: &[u8]) -> Option<Self> {
+        if magic.starts_with(&[0x1f, 0x8b]) {
+            Some(Compression::Gzip)
+        } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// detect compression by extension of path, such as `data.json.gz` or `data.json.zst`.
+    pub(crate) fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "gz" => Some(Compression::Gzip),
+            "zst" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// get reader that decompress `r`.
+    pub(crate) fn decoder<'a, R: 'a + BufRead>(self, r: R) -> io::Result<Box<dyn 'a + Read>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Box::new(flate2::bufread::MultiGzDecoder::new(r))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::with_buffer(r)?)),
+            #[allow(unreachable_patterns)]
+            _ => {
+                drop(r);
+                Err(self.disabled())
+            }
+        }
+    }
+
+    /// write into `w` through compressor. `write` get compressor, and return the number of written bytes.
+    pub(crate) fn compress<W, F, E>(self, w: W, write: F) -> Result<usize, E>
+    where
+        W: Write,
+        F: FnOnce(&mut dyn Write) -> Result<usize, E>,
+        E: From<io::Error>,
+    {
+        match self {
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(w, flate2::Compression::default());
+                let written = write(&mut encoder)?;
+                encoder.finish()?;
+                Ok(written)
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let mut encoder = zstd::stream::write::Encoder::new(w, 0)?;
+                let written = write(&mut encoder)?;
+                encoder.finish()?;
+                Ok(written)
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                drop((w, write));
+                Err(self.disabled().into())
+            }
+        }
+    }
+
+    fn disabled(self) -> io::Error {
+        let feature = match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+        };
+        io::Error::new(io::ErrorKind::Unsupported, format!("{feature} compressed json require `{feature}` feature"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Compression::detect(&[0x1f, 0x8b, 0x08]), Some(Compression::Gzip));
+        assert_eq!(Compression::detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00]), Some(Compression::Zstd));
+        assert_eq!(Compression::detect(b"{\"key\": 1}"), None);
+        assert_eq!(Compression::detect(&[0x1f]), None);
+        assert_eq!(Compression::from_extension(Path::new("data.json.gz")), Some(Compression::Gzip));
+        assert_eq!(Compression::from_extension(Path::new("data.json.zst")), Some(Compression::Zstd));
+        assert_eq!(Compression::from_extension(Path::new("data.json")), None);
+    }
+
+    #[test]
+    fn test_compress_round_trip() {
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            let enabled = match compression {
+                Compression::Gzip => cfg!(feature = "gzip"),
+                Compression::Zstd => cfg!(feature = "zstd"),
+            };
+            let mut compressed = Vec::new();
+            let written = compression.compress(&mut compressed, |w| w.write(b"[1, 2, 3]"));
+            if !enabled {
+                assert_eq!(written.unwrap_err().kind(), io::ErrorKind::Unsupported);
+                continue;
+            }
+            assert_eq!(written.unwrap(), 9);
+            assert_eq!(Compression::detect(&compressed), Some(compression));
+            let mut decompressed = String::new();
+            compression.decoder(&compressed[..]).unwrap().read_to_string(&mut decompressed).unwrap();
+            assert_eq!(decompressed, "[1, 2, 3]");
+        }
+    }
+}
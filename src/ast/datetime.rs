@@ -0,0 +1,67 @@
+//! datetime in json, with `chrono` feature. json has no datetime type, so datetime is stored as string of
+//! RFC 3339, such as `"2024-03-13T12:00:00Z"` or `"2024-03-13T21:00:00+09:00"`.
+
+use super::Value;
+use ::chrono::{DateTime, FixedOffset, SecondsFormat, TimeZone};
+use std::fmt::Display;
+
+impl Value {
+    /// parse [`Value::String`] of RFC 3339 into datetime with its offset. if value is not `String` or it is not
+    /// valid RFC 3339, return `None`.
+    /// # examples
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"created": "2024-03-13T21:00:00+09:00", "name": "dyson"}"#).unwrap();
+    ///
+    /// let created = json["created"].as_datetime().unwrap();
+    /// assert_eq!(created, Utc.with_ymd_and_hms(2024, 3, 13, 12, 0, 0).unwrap());
+    /// assert_eq!(created.offset().local_minus_utc(), 9 * 60 * 60);
+    /// assert_eq!(json["name"].as_datetime(), None);
+    /// ```
+    pub fn as_datetime(&self) -> Option<DateTime<FixedOffset>> {
+        self.get_string().and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    }
+}
+
+/// convert datetime into [`Value::String`] of RFC 3339. fractional seconds are written only if needed, and UTC is
+/// written as `Z`.
+/// # examples
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use dyson::Value;
+/// let datetime = Utc.with_ymd_and_hms(2024, 3, 13, 12, 0, 0).unwrap();
+/// assert_eq!(Value::from(datetime), Value::from("2024-03-13T12:00:00Z"));
+/// ```
+impl<Tz: TimeZone> From<DateTime<Tz>> for Value
+where
+    Tz::Offset: Display,
+{
+    fn from(datetime: DateTime<Tz>) -> Self {
+        Value::String(datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::chrono::{Duration, Utc};
+
+    #[test]
+    fn test_datetime_round_trip() {
+        let utc = Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap() + Duration::milliseconds(250);
+        let offset = utc.with_timezone(&FixedOffset::west_opt(5 * 60 * 60).unwrap());
+        assert_eq!(Value::from(utc), Value::from("2024-02-29T23:59:59.250Z"));
+        assert_eq!(Value::from(offset), Value::from("2024-02-29T18:59:59.250-05:00"));
+        for datetime in [Value::from(utc), Value::from(offset)] {
+            assert_eq!(datetime.as_datetime(), Some(offset));
+        }
+    }
+
+    #[test]
+    fn test_invalid_datetime() {
+        for json in [r#""2024-02-30T00:00:00Z""#, r#""2024-03-13""#, r#""2024-03-13T12:00:00""#, "1710331200", "null"] {
+            assert_eq!(Value::parse(json).unwrap().as_datetime(), None, "{json}");
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use super::{index::JsonIndexer, index_path::JsonPath, Value};
+use crate::Error;
 use itertools::Itertools;
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
 
 /// compare `a` and `b` that are expected same structure. this method's complexity is **O(max{|a|, |b|})**.
 /// # panics
@@ -70,6 +76,283 @@ pub fn diff_value_detail(a: &Value, b: &Value) -> Vec<String> {
     result
 }
 
+/// compare `a` and `b` that may have different structure. this method's complexity is **O(|a| + |b|)**.
+/// unlike [`diff_value`], this method do not panic, and report missing element as `None`.
+/// # examples
+/// ```
+/// use dyson::{diff_value_lenient, Value};
+/// let a = Value::parse(r#"{"language": "rust", "keyword": [1, 2]}"#).unwrap();
+/// let b = Value::parse(r#"{"language": "rust", "keyword": [1, 2, 3], "version": 0.1}"#).unwrap();
+///
+/// let diff = diff_value_lenient(&a, &b);
+/// assert_eq!(diff.len(), 2);
+/// assert_eq!((&diff[0].0.to_string()[..], diff[0].1, diff[0].2), ("\"keyword\">2", None, Some(&Value::Integer(3))));
+/// assert_eq!((&diff[1].0.to_string()[..], diff[1].1, diff[1].2), ("\"version\"", None, Some(&Value::Float(0.1))));
+/// ```
+pub fn diff_value_lenient<'a>(a: &'a Value, b: &'a Value) -> Vec<(JsonPath, Option<&'a Value>, Option<&'a Value>)> {
+    diff_value_lenient_with(a, b, |a, b| a == b)
+}
+
+/// compare `a` and `b` like [`diff_value_lenient`], but numbers are compared by their value regardless of
+/// representation, so `1` and `1.0` are not reported as difference.
+/// # examples
+/// ```
+/// use dyson::{diff_value_lenient_numeric, Value};
+/// let a = Value::parse(r#"{"price": 100, "rate": 0.5}"#).unwrap();
+/// let b = Value::parse(r#"{"price": 100.0, "rate": 0.25}"#).unwrap();
+///
+/// let diff = diff_value_lenient_numeric(&a, &b);
+/// assert_eq!(diff.len(), 1);
+/// assert_eq!(diff[0].0.to_string(), r#""rate""#);
+/// ```
+pub fn diff_value_lenient_numeric<'a>(
+    a: &'a Value,
+    b: &'a Value,
+) -> Vec<(JsonPath, Option<&'a Value>, Option<&'a Value>)> {
+    diff_value_lenient_with(a, b, |a, b| match (a.number(), b.number()) {
+        (Some(na), Some(nb)) => na == nb,
+        _ => a == b,
+    })
+}
+
+fn diff_value_lenient_with<'a>(
+    a: &'a Value,
+    b: &'a Value,
+    eq: fn(&Value, &Value) -> bool,
+) -> Vec<(JsonPath, Option<&'a Value>, Option<&'a Value>)> {
+    fn diff_value_lenient_recursive<'a>(
+        (a, b): (&'a Value, &'a Value),
+        eq: fn(&Value, &Value) -> bool,
+        path: &mut JsonPath,
+        differences: &mut Vec<(JsonPath, Option<&'a Value>, Option<&'a Value>)>,
+    ) {
+        match (a, b) {
+            (Value::Object(ma), Value::Object(mb)) => {
+                for (k, va) in ma.iter() {
+                    path.push(JsonIndexer::ObjInd(k.to_string()));
+                    match mb.get(k) {
+                        Some(vb) => diff_value_lenient_recursive((va, vb), eq, path, differences),
+                        None => differences.push((path.clone(), Some(va), None)),
+                    }
+                    path.pop();
+                }
+                for (k, vb) in mb.iter().filter(|(k, _)| !ma.contains_key(*k)) {
+                    path.push(JsonIndexer::ObjInd(k.to_string()));
+                    differences.push((path.clone(), None, Some(vb)));
+                    path.pop();
+                }
+            }
+            (Value::Array(va), Value::Array(vb)) => {
+                for (i, pair) in va.iter().zip_longest(vb).enumerate() {
+                    path.push(JsonIndexer::ArrInd(i));
+                    match pair {
+                        itertools::EitherOrBoth::Both(a, b) => {
+                            diff_value_lenient_recursive((a, b), eq, path, differences)
+                        }
+                        itertools::EitherOrBoth::Left(a) => differences.push((path.clone(), Some(a), None)),
+                        itertools::EitherOrBoth::Right(b) => differences.push((path.clone(), None, Some(b))),
+                    }
+                    path.pop();
+                }
+            }
+            (av, bv) => {
+                if !eq(av, bv) {
+                    differences.push((path.clone(), Some(av), Some(bv)));
+                }
+            }
+        }
+    }
+    let mut differences = Vec::new();
+    diff_value_lenient_recursive((a, b), eq, &mut JsonPath::new(), &mut differences);
+    differences
+}
+
+impl Value {
+    /// compare with `other` ignoring values under given paths. this is useful for test assertion,
+    /// that compare documents which include timestamp, generated id, and so on.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, JsonPath, Value};
+    /// let a = Value::parse(r#"{"id": 1, "created_at": "2022-01-01", "tags": ["a", "b"]}"#).unwrap();
+    /// let b = Value::parse(r#"{"id": 1, "created_at": "2022-12-31", "tags": ["a", "b"]}"#).unwrap();
+    ///
+    /// let created_at: JsonPath = vec![JsonIndexer::ObjInd("created_at".to_string())].into_iter().collect();
+    /// assert!(a.eq_ignoring(&b, &[created_at]));
+    /// assert!(!a.eq_ignoring(&b, &[]));
+    /// ```
+    pub fn eq_ignoring(&self, other: &Value, ignored: &[JsonPath]) -> bool {
+        diff_value_lenient(self, other).iter().all(|(path, _, _)| ignored.iter().any(|i| path.starts_with(i)))
+    }
+
+    /// compare with `other` like `==`, but numbers are compared by their value regardless of representation, and
+    /// order of object keys is ignored. see [`diff_value_lenient_numeric`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let a = Value::parse(r#"{"id": 1, "scores": [2, 0.5]}"#).unwrap();
+    /// let b = Value::parse(r#"{"scores": [2.0, 0.5], "id": 1e0}"#).unwrap();
+    ///
+    /// assert!(a.eq_numeric(&b));
+    /// assert!(a != b);
+    /// assert!(!Value::Integer(1).eq_numeric(&Value::from("1")));
+    /// ```
+    pub fn eq_numeric(&self, other: &Value) -> bool {
+        diff_value_lenient_numeric(self, other).is_empty()
+    }
+}
+
+/// assert that two [`Value`] are equal. if not, panic with structural difference (paths and values) instead of
+/// two giant `Debug` dumps. differences are colored unless `NO_COLOR` environment variable is set.
+/// # examples
+/// ```
+/// use dyson::{assert_json_eq, Value};
+/// let a = Value::parse(r#"{"language": "rust", "keyword": ["json", "parser"]}"#).unwrap();
+/// let b = Value::parse(r#"{ "keyword": ["json", "parser"], "language": "rust" }"#).unwrap();
+/// assert_json_eq!(a, b);
+/// ```
+/// ```should_panic
+/// use dyson::{assert_json_eq, Value};
+/// let a = Value::parse(r#"{"language": "rust", "keyword": ["json", "parser"]}"#).unwrap();
+/// let b = Value::parse(r#"{"language": "ruby", "keyword": ["json"]}"#).unwrap();
+/// assert_json_eq!(a, b);
+/// // assertion `left == right` failed: 2 differences
+/// // "language":
+/// //     - "rust"
+/// //     + "ruby"
+/// // "keyword">1:
+/// //     - "parser"
+/// //     + (missing)
+/// ```
+#[macro_export]
+macro_rules! assert_json_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(message) = $crate::ast::diff::json_eq_report(left, right) {
+                    panic!("assertion `left == right` failed: {}", message);
+                }
+            }
+        }
+    };
+}
+
+/// report of [`assert_json_eq`]. if `a` and `b` are equal, return `None`.
+#[doc(hidden)]
+pub fn json_eq_report(a: &Value, b: &Value) -> Option<String> {
+    let differences = diff_value_lenient(a, b);
+    if differences.is_empty() {
+        return None;
+    }
+    let (red, green, reset) =
+        if std::env::var_os("NO_COLOR").is_some() { ("", "", "") } else { ("\x1b[31m", "\x1b[32m", "\x1b[0m") };
+    let display = |v: Option<&Value>| v.map_or_else(|| "(missing)".to_string(), |v| v.to_string());
+    let mut report = format!("{} differences", differences.len());
+    for (path, left, right) in differences {
+        let path = if path.depth() == 0 { "(root)".to_string() } else { path.to_string() };
+        report.push_str(&format!("\n{}:", path));
+        report.push_str(&format!("\n    {}- {}{}", red, display(left), reset));
+        report.push_str(&format!("\n    {}+ {}{}", green, display(right), reset));
+    }
+    Some(report)
+}
+
+/// [`FileDiff`] is difference of a json file between two directories. see [`diff_dirs`] also.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileDiff {
+    /// json file exists only in first directory.
+    Removed(PathBuf),
+
+    /// json file exists only in second directory.
+    Added(PathBuf),
+
+    /// json file exists in both directories, but its content is different. messages are same as [`diff_value_detail`].
+    Modified(PathBuf, Vec<String>),
+}
+impl FileDiff {
+    /// get relative path of the json file from compared directories.
+    pub fn path(&self) -> &Path {
+        match self {
+            FileDiff::Removed(p) | FileDiff::Added(p) | FileDiff::Modified(p, _) => p,
+        }
+    }
+}
+impl std::fmt::Display for FileDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileDiff::Removed(p) => write!(f, "removed: {}", p.display()),
+            FileDiff::Added(p) => write!(f, "added: {}", p.display()),
+            FileDiff::Modified(p, details) => {
+                write!(f, "modified: {}", p.display())?;
+                details.iter().try_for_each(|d| write!(f, "\n    {}", d))
+            }
+        }
+    }
+}
+
+/// compare json files (`*.json`) in directory `a_dir` and `b_dir` recursively, and return only changed files.
+/// files are matched by relative path from each directory, and result is sorted by the path.
+/// unlike [`diff_value`], this function do not panic even if a pair of json do not have same structure.
+/// # errors
+/// if cannot read directory, or found invalid json file.
+/// # examples
+/// ```no_run
+/// use dyson::diff_dirs;
+/// for diff in diff_dirs("path/to/a", "path/to/b").unwrap() {
+///     println!("{}", diff);
+/// }
+/// ```
+pub fn diff_dirs<P: AsRef<Path>, Q: AsRef<Path>>(a_dir: P, b_dir: Q) -> Result<Vec<FileDiff>, Error> {
+    fn json_files(root: &Path, dir: &Path, files: &mut BTreeSet<PathBuf>) -> Result<(), Error> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                json_files(root, &path, files)?;
+            } else if path.extension().map_or(false, |ext| ext == "json") {
+                // entries of `dir` are always under `root`
+                files.insert(path.strip_prefix(root).unwrap_or(&path).to_path_buf());
+            }
+        }
+        Ok(())
+    }
+    let (a_dir, b_dir) = (a_dir.as_ref(), b_dir.as_ref());
+    let (mut a_files, mut b_files) = (BTreeSet::new(), BTreeSet::new());
+    json_files(a_dir, a_dir, &mut a_files)?;
+    json_files(b_dir, b_dir, &mut b_files)?;
+
+    let mut differences = Vec::new();
+    for file in a_files.union(&b_files) {
+        match (a_files.contains(file), b_files.contains(file)) {
+            (true, false) => differences.push(FileDiff::Removed(file.clone())),
+            (false, true) => differences.push(FileDiff::Added(file.clone())),
+            _ => {
+                let (a, b) = (Value::load(a_dir.join(file))?, Value::load(b_dir.join(file))?);
+                if a != b {
+                    let details = if same_structure(&a, &b) {
+                        diff_value_detail(&a, &b)
+                    } else {
+                        vec![format!("different structure {} and {}", a, b)]
+                    };
+                    differences.push(FileDiff::Modified(file.clone(), details));
+                }
+            }
+        }
+    }
+    Ok(differences)
+}
+
+/// check `a` and `b` can be compared by [`diff_value`] without panic.
+fn same_structure(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Object(ma), Value::Object(mb)) => {
+            ma.len() == mb.len() && ma.iter().all(|(k, va)| mb.get(k).map_or(true, |vb| same_structure(va, vb)))
+        }
+        (Value::Array(va), Value::Array(vb)) => {
+            va.len() == vb.len() && std::iter::zip(va, vb).all(|(a, b)| same_structure(a, b))
+        }
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -77,6 +360,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_diff_value_json() {
         let json1 = [
             r#"{"#,
@@ -146,4 +430,89 @@ mod tests {
         assert!(diff[0].contains("rust"));
         assert!(diff[0].contains("ruby"));
     }
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_diff_value_lenient() {
+        let a = Value::parse(r#"{"same": [1, {"x": null}], "changed": 1, "removed": true, "array": [1, 2]}"#).unwrap();
+        let b = Value::parse(r#"{"added": false, "array": [1], "changed": "1", "same": [1, {"x": null}]}"#).unwrap();
+        let diff = diff_value_lenient(&a, &b);
+        let expected: Vec<(JsonPath, _, _)> = vec![
+            (
+                vec![JsonIndexer::ObjInd("changed".to_string())].into_iter().collect(),
+                Some(&a["changed"]),
+                Some(&b["changed"]),
+            ),
+            (vec![JsonIndexer::ObjInd("removed".to_string())].into_iter().collect(), Some(&a["removed"]), None),
+            (
+                vec![JsonIndexer::ObjInd("array".to_string()), JsonIndexer::ArrInd(1)].into_iter().collect(),
+                Some(&a["array"][1]),
+                None,
+            ),
+            (vec![JsonIndexer::ObjInd("added".to_string())].into_iter().collect(), None, Some(&b["added"])),
+        ];
+        assert_eq!(diff, expected);
+        assert!(diff_value_lenient(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn test_eq_numeric() {
+        let a = Value::parse(r#"{"n": [1, -0.0, 1e2, 9007199254740993], "s": "1", "o": {"f": 2.50}}"#).unwrap();
+        let b = Value::parse(r#"{"o": {"f": 2.5}, "s": "1", "n": [1.0, 0, 100, 9007199254740993]}"#).unwrap();
+        assert!(a.eq_numeric(&b) && b.eq_numeric(&a));
+        assert!(!diff_value_lenient(&a, &b).is_empty());
+
+        let c = Value::parse(r#"{"o": {"f": 2.5}, "s": 1, "n": [1.0, 0, 100, 9007199254740992.0]}"#).unwrap();
+        let diff: Vec<_> = diff_value_lenient_numeric(&a, &c).into_iter().map(|(p, _, _)| p.to_string()).collect();
+        assert_eq!(diff.into_iter().collect::<HashSet<_>>(), [r#""n">3"#.to_string(), r#""s""#.to_string()].into());
+        assert!(!Value::Null.eq_numeric(&Value::Integer(0)));
+    }
+
+    #[test]
+    fn test_json_eq_report() {
+        let (a, b) = (Value::parse(r#"[1, "two", 3]"#).unwrap(), Value::parse(r#"[1, "2"]"#).unwrap());
+        let report = json_eq_report(&a, &b).unwrap();
+        assert!(report.starts_with("2 differences"));
+        assert!(report.contains("1:"));
+        assert!(report.contains("\"two\""));
+        assert!(report.contains("\"2\""));
+        assert!(report.contains("(missing)"));
+        assert_eq!(json_eq_report(&b, &b), None);
+        assert_json_eq!(a, a.clone());
+    }
+
+    #[test]
+    #[should_panic(expected = "(root)")]
+    fn test_assert_json_eq_panic() {
+        assert_json_eq!(Value::Null, Value::Bool(false));
+    }
+
+    #[test]
+    fn test_diff_dirs() {
+        let result = || -> anyhow::Result<()> {
+            let (a_dir, b_dir) = (tempfile::tempdir()?, tempfile::tempdir()?);
+            fs::create_dir(a_dir.path().join("sub"))?;
+            fs::create_dir(b_dir.path().join("sub"))?;
+            fs::write(a_dir.path().join("same.json"), r#"{"language": "rust"}"#)?;
+            fs::write(b_dir.path().join("same.json"), r#"{ "language" : "rust" }"#)?;
+            fs::write(a_dir.path().join("sub").join("modified.json"), r#"{"version": 0.1}"#)?;
+            fs::write(b_dir.path().join("sub").join("modified.json"), r#"{"version": 0.2}"#)?;
+            fs::write(a_dir.path().join("structure.json"), r#"[1, 2, 3]"#)?;
+            fs::write(b_dir.path().join("structure.json"), r#"[1, 2]"#)?;
+            fs::write(a_dir.path().join("removed.json"), r#"null"#)?;
+            fs::write(b_dir.path().join("added.json"), r#"null"#)?;
+            fs::write(b_dir.path().join("ignored.txt"), r#"not json"#)?;
+
+            let diff = diff_dirs(a_dir.path(), b_dir.path())?;
+            assert_eq!(diff.len(), 4);
+            assert_eq!(diff[0], FileDiff::Added(PathBuf::from("added.json")));
+            assert_eq!(diff[1], FileDiff::Removed(PathBuf::from("removed.json")));
+            assert!(matches!(&diff[2], FileDiff::Modified(p, d) if p == Path::new("structure.json") && d.len() == 1));
+            assert!(matches!(&diff[3], FileDiff::Modified(p, d) if p == &Path::new("sub").join("modified.json")));
+            assert!(diff[3].to_string().contains("0.1"));
+            assert!(diff[3].to_string().contains("0.2"));
+            Ok(())
+        }();
+        assert!(result.is_ok());
+    }
 }
@@ -1,4 +1,40 @@
-use super::Value;
+use super::{
+    index::{JsonIndex, JsonIndexer},
+    index_path::{check_array_index, JsonPath, PathError},
+    query::order,
+    Value,
+};
+use std::cmp::Ordering;
+
+/// [`SetOptions`] configure behavior of [`Value::set_with`].
+/// # examples
+/// ```
+/// use dyson::{JsonPath, SetOptions, Value};
+/// let mut json = Value::parse(r#"{"a": []}"#).unwrap();
+/// let path = JsonPath::from_dotted("a[2]").unwrap();
+///
+/// assert!(json.set(&path, true.into()).is_err());
+/// json.set_with(&path, true.into(), SetOptions::new().grow_array(true)).unwrap();
+/// assert_eq!(json, Value::parse(r#"{"a": [null, null, true]}"#).unwrap());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SetOptions {
+    grow_array: bool,
+}
+impl SetOptions {
+    /// get default options, it is same as [`Value::set`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// if `grow_array`, index beyond the end of array fill the gap with `null`. by default, only index equal to
+    /// length of array can append element, and index beyond it cause error. even if `grow_array`, gap more than
+    /// 65536 elements cause error, to avoid huge allocation.
+    pub fn grow_array(mut self, grow_array: bool) -> Self {
+        self.grow_array = grow_array;
+        self
+    }
+}
 
 impl Value {
     /// swap self and given value.
@@ -23,6 +59,32 @@ impl Value {
         value.to_owned()
     }
 
+    /// take self out and leave [`Value::Null`] in its place, without clone. see [`Value::replace`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"foo": [1, "two", 3], "bar": 4}"#).unwrap();
+    /// let foo = json["foo"].take();
+    /// assert_eq!(foo, Value::parse(r#"[1, "two", 3]"#).unwrap());
+    /// assert_eq!(json, Value::parse(r#"{"foo": null, "bar": 4}"#).unwrap());
+    /// ```
+    pub fn take(&mut self) -> Value {
+        self.replace(Value::Null)
+    }
+
+    /// replace self with `value`, and get previous value without clone.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"foo": [1, "two", 3], "bar": 4}"#).unwrap();
+    /// let bar = json["bar"].replace("four".into());
+    /// assert_eq!(bar, Value::Integer(4));
+    /// assert_eq!(json, Value::parse(r#"{"foo": [1, "two", 3], "bar": "four"}"#).unwrap());
+    /// ```
+    pub fn replace(&mut self, value: Value) -> Value {
+        std::mem::replace(self, value)
+    }
+
     // TODO Sized dyn is impossible...?
     // pub fn update<F: Fn(&dyn Into<Value>) -> &dyn Into<Value>>(self, f: F) -> Value {
     //     let data: dyn Into<Value> = match self {
@@ -58,7 +120,7 @@ impl Value {
     ///         })
     ///     }).collect()
     /// });
-    /// assert_eq!(json["foo"], vec![1.into(), 2.into(), 9.into(), 4.into(), 25.into()].into());
+    /// assert_eq!(json["foo"], Value::from(vec![1, 2, 9, 4, 25]));
     /// assert_eq!(json, Value::parse(r#"{"foo": [1, 2, 9, 4, 25], "bar": 36}"#).unwrap())
     /// ```
     pub fn update_with<F: FnOnce(&Value) -> Value>(&mut self, f: F) -> Value {
@@ -66,6 +128,372 @@ impl Value {
         std::mem::swap(self, &mut prev);
         prev
     }
+
+    /// modify value in place by `f`. unlike [`Value::update_with`], `f` need not build whole new value, so editing
+    /// small part of large value does not clone or rebuild it. this method return `self` for chaining.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"users": [{"name": "dyson", "tags": []}]}"#).unwrap();
+    ///
+    /// json["users"][0]
+    ///     .modify(|user| user["name"] = "DYSON".into())
+    ///     .modify(|user| user["tags"].extend([Value::from("json")]));
+    /// assert_eq!(json, Value::parse(r#"{"users": [{"name": "DYSON", "tags": ["json"]}]}"#).unwrap());
+    /// ```
+    pub fn modify<F: FnOnce(&mut Value)>(&mut self, f: F) -> &mut Value {
+        f(self);
+        self
+    }
+
+    /// get value that each leaf of this value is transformed by `f`, and objects and arrays keep their structure.
+    /// leaf is value other than object and array, and `f` is called with its path in dfs order.
+    /// see [`Value::map_leaves_mut`] for in-place variant.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"name": "dyson", "scores": [1, 2.5], "password": "secret"}"#).unwrap();
+    ///
+    /// let transformed = json.map_leaves(|path, v| match v {
+    ///     _ if path.to_string() == r#""password""# => Value::from("***"),
+    ///     Value::String(s) => Value::from(s.to_uppercase()),
+    ///     Value::Integer(i) => Value::from(i * 10),
+    ///     Value::Float(f) => Value::from(f * 10.),
+    ///     v => v.clone(),
+    /// });
+    /// assert_eq!(transformed, Value::parse(r#"{"name": "DYSON", "scores": [10, 25.0], "password": "***"}"#).unwrap());
+    /// ```
+    pub fn map_leaves<F: FnMut(&JsonPath, &Value) -> Value>(&self, mut f: F) -> Value {
+        fn map_leaves_recursive<F: FnMut(&JsonPath, &Value) -> Value>(
+            value: &Value,
+            path: &mut JsonPath,
+            f: &mut F,
+        ) -> Value {
+            match value {
+                Value::Object(m) => Value::Object(Box::new(
+                    m.iter()
+                        .map(|(k, v)| {
+                            path.push(JsonIndexer::ObjInd(k.to_string()));
+                            let mapped = map_leaves_recursive(v, path, f);
+                            path.pop();
+                            (k.clone(), mapped)
+                        })
+                        .collect(),
+                )),
+                Value::Array(a) => Value::Array(
+                    a.iter()
+                        .enumerate()
+                        .map(|(i, e)| {
+                            path.push(JsonIndexer::ArrInd(i));
+                            let mapped = map_leaves_recursive(e, path, f);
+                            path.pop();
+                            mapped
+                        })
+                        .collect(),
+                ),
+                leaf => f(path, leaf),
+            }
+        }
+        map_leaves_recursive(self, &mut JsonPath::new(), &mut f)
+    }
+
+    /// transform each leaf of this value by `f` in place. see [`Value::map_leaves`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"a": [1, "two", {"b": 3}]}"#).unwrap();
+    /// json.map_leaves_mut(|_, v| if let Value::Integer(i) = v { *i *= 2 });
+    /// assert_eq!(json, Value::parse(r#"{"a": [2, "two", {"b": 6}]}"#).unwrap());
+    /// ```
+    pub fn map_leaves_mut<F: FnMut(&JsonPath, &mut Value)>(&mut self, mut f: F) {
+        fn map_leaves_mut_recursive<F: FnMut(&JsonPath, &mut Value)>(
+            value: &mut Value,
+            path: &mut JsonPath,
+            f: &mut F,
+        ) {
+            match value {
+                Value::Object(m) => {
+                    for (k, v) in m.iter_mut() {
+                        path.push(JsonIndexer::ObjInd(k.to_string()));
+                        map_leaves_mut_recursive(v, path, f);
+                        path.pop();
+                    }
+                }
+                Value::Array(a) => {
+                    for (i, e) in a.iter_mut().enumerate() {
+                        path.push(JsonIndexer::ArrInd(i));
+                        map_leaves_mut_recursive(e, path, f);
+                        path.pop();
+                    }
+                }
+                leaf => f(path, leaf),
+            }
+        }
+        map_leaves_mut_recursive(self, &mut JsonPath::new(), &mut f)
+    }
+
+    /// retain only entries of [`Value::Object`] that satisfy `f`, and remove others in place. order of remained
+    /// entries is kept.
+    /// # panics
+    /// if value is not `Object`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"name": "dyson", "_secret": 1, "note": null}"#).unwrap();
+    /// json.retain_object(|k, v| !k.starts_with('_') && !v.is_null());
+    /// assert_eq!(json, Value::parse(r#"{"name": "dyson"}"#).unwrap());
+    /// ```
+    pub fn retain_object<F: FnMut(&str, &mut Value) -> bool>(&mut self, mut f: F) {
+        match self {
+            Value::Object(m) => {
+                let entries = std::mem::take(&mut **m);
+                **m = entries.into_iter().filter_map(|(k, mut v)| f(&k, &mut v).then(|| (k, v))).collect();
+            }
+            _ => panic!("only Object can retain entries, but {}", self.node_type()),
+        }
+    }
+
+    /// retain only elements of [`Value::Array`] that satisfy `f`, and remove others in place.
+    /// # panics
+    /// if value is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"[1, null, "two", null]"#).unwrap();
+    /// json.retain_array(|v| !v.is_null());
+    /// assert_eq!(json, Value::parse(r#"[1, "two"]"#).unwrap());
+    /// ```
+    pub fn retain_array<F: FnMut(&mut Value) -> bool>(&mut self, f: F) {
+        match self {
+            Value::Array(v) => v.retain_mut(f),
+            _ => panic!("only Array can retain elements, but {}", self.node_type()),
+        }
+    }
+
+    /// retain only entries of objects and elements of arrays that satisfy `f` in this value and its descendants.
+    /// `f` is called with path and value of each entry or element, and descendants of removed one are not visited.
+    /// index in the path is the one before removal.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, Value};
+    /// let mut json = Value::parse(r#"{"a": null, "b": [1, null, {"c": null, "_d": 2}], "_e": {"f": null}}"#).unwrap();
+    /// json.retain_recursive(|path, v| {
+    ///     let private = matches!(path.last(), Some(JsonIndexer::ObjInd(k)) if k.starts_with('_'));
+    ///     !private && !v.is_null()
+    /// });
+    /// assert_eq!(json, Value::parse(r#"{"b": [1, {}]}"#).unwrap());
+    /// ```
+    pub fn retain_recursive<F: FnMut(&JsonPath, &Value) -> bool>(&mut self, mut f: F) {
+        fn retain_recursive_inner<F: FnMut(&JsonPath, &Value) -> bool>(
+            value: &mut Value,
+            path: &mut JsonPath,
+            f: &mut F,
+        ) {
+            match value {
+                Value::Object(m) => {
+                    let entries = std::mem::take(&mut **m);
+                    **m = entries
+                        .into_iter()
+                        .filter_map(|(k, mut v)| {
+                            path.push(JsonIndexer::ObjInd(k.to_string()));
+                            let retained = f(path, &v);
+                            if retained {
+                                retain_recursive_inner(&mut v, path, f);
+                            }
+                            path.pop();
+                            retained.then(|| (k, v))
+                        })
+                        .collect();
+                }
+                Value::Array(a) => {
+                    let elements = std::mem::take(a);
+                    *a = elements
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(|(i, mut e)| {
+                            path.push(JsonIndexer::ArrInd(i));
+                            let retained = f(path, &e);
+                            if retained {
+                                retain_recursive_inner(&mut e, path, f);
+                            }
+                            path.pop();
+                            retained.then(|| e)
+                        })
+                        .collect();
+                }
+                _ => (),
+            }
+        }
+        retain_recursive_inner(self, &mut JsonPath::new(), &mut f);
+    }
+
+    /// sort elements of [`Value::Array`] by `compare`. this sort is stable.
+    /// # panics
+    /// if value is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"["ccc", "a", "bb"]"#).unwrap();
+    /// json.sort_array_by(|a, b| a.string().len().cmp(&b.string().len()));
+    /// assert_eq!(json, Value::parse(r#"["a", "bb", "ccc"]"#).unwrap());
+    /// ```
+    pub fn sort_array_by<F: FnMut(&Value, &Value) -> Ordering>(&mut self, compare: F) {
+        match self {
+            Value::Array(v) => v.sort_by(compare),
+            _ => panic!("only Array can be sorted, but {}", self.node_type()),
+        }
+    }
+
+    /// sort elements of [`Value::Array`] by value at `key` of each element, such as `"id"` or `&JsonPath`. values are
+    /// compared in order `null` < `false` < `true` < number < string < array < object, and element that does not
+    /// have `key` come first. this sort is stable.
+    /// # panics
+    /// if value is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, Value};
+    /// let mut json = Value::parse(r#"[{"id": 3, "user": {"name": "b"}}, {"id": 1, "user": {"name": "c"}}, {"id": 2}]"#).unwrap();
+    ///
+    /// json.sort_array_by_key("id");
+    /// assert_eq!(json.iter().map(|v| v["id"].integer()).collect::<Vec<_>>(), [&1, &2, &3]);
+    ///
+    /// json.sort_array_by_key(&JsonPath::from_dotted("user.name").unwrap());
+    /// assert_eq!(json.iter().map(|v| v["id"].integer()).collect::<Vec<_>>(), [&2, &3, &1]);
+    /// ```
+    pub fn sort_array_by_key<I: JsonIndex<Output = Value> + Copy>(&mut self, key: I) {
+        self.sort_array_by(|a, b| match (a.get(key), b.get(key)) {
+            (Some(ka), Some(kb)) => order(ka, kb),
+            (ka, kb) => ka.is_some().cmp(&kb.is_some()),
+        })
+    }
+
+    /// set `value` at `path`, and get previous value if exist. missing objects and arrays on the way are created,
+    /// and `null` on the way is replaced with them. if value on the way is other type, return error, and this value
+    /// is not changed. see [`Value::set_with`] for index beyond the end of array.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, Value};
+    /// let mut json = Value::parse(r#"{"server": {"port": 80}}"#).unwrap();
+    ///
+    /// let port = json.set(&JsonPath::from_dotted("server.port").unwrap(), 8080.into()).unwrap();
+    /// assert_eq!(port, Some(Value::Integer(80)));
+    /// json.set(&JsonPath::from_dotted("server.hosts[0].name").unwrap(), "a".into()).unwrap();
+    /// assert_eq!(json, Value::parse(r#"{"server": {"port": 8080, "hosts": [{"name": "a"}]}}"#).unwrap());
+    ///
+    /// assert!(json.set(&JsonPath::from_dotted("server.port.number").unwrap(), 1.into()).is_err());
+    /// ```
+    pub fn set(&mut self, path: &JsonPath, value: Value) -> Result<Option<Value>, PathError> {
+        self.set_with(path, value, SetOptions::default())
+    }
+
+    /// set `value` at `path` with options. see [`Value::set`] and [`SetOptions`] also.
+    pub fn set_with(&mut self, path: &JsonPath, value: Value, options: SetOptions) -> Result<Option<Value>, PathError> {
+        let (target, vacant) = self.slot_mut(path, &options)?;
+        let prev = target.replace(value);
+        Ok((!vacant).then(|| prev))
+    }
+
+    /// get mutable reference of value at `path`. if there is no value, insert value returned by `default` and get
+    /// it. missing objects and arrays on the way are created like [`Value::set`].
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, Value};
+    /// let mut json = Value::parse(r#"{"config": {}}"#).unwrap();
+    /// let plugins = JsonPath::from_dotted("config.plugins.enabled").unwrap();
+    ///
+    /// for plugin in ["fmt", "lint"] {
+    ///     json.get_or_insert_with_path(&plugins, Value::new_array).unwrap().extend([Value::from(plugin)]);
+    /// }
+    /// assert_eq!(json, Value::parse(r#"{"config": {"plugins": {"enabled": ["fmt", "lint"]}}}"#).unwrap());
+    /// ```
+    pub fn get_or_insert_with_path<F: FnOnce() -> Value>(
+        &mut self,
+        path: &JsonPath,
+        default: F,
+    ) -> Result<&mut Value, PathError> {
+        let (target, vacant) = self.slot_mut(path, &SetOptions::default())?;
+        if vacant {
+            *target = default();
+        }
+        Ok(target)
+    }
+
+    /// get mutable reference of value at `path` with creating containers on the way, and whether it is newly
+    /// created or not. whole path is checked before creating containers, so this value is not changed on error.
+    fn slot_mut(&mut self, path: &JsonPath, options: &SetOptions) -> Result<(&mut Value, bool), PathError> {
+        self.check_slot(path, options)?;
+        let mut target = self;
+        let mut vacant = false;
+        for indexer in path {
+            if target.is_null() {
+                *target = match indexer {
+                    JsonIndexer::ObjInd(_) => Value::new_object(),
+                    JsonIndexer::ArrInd(_) => Value::new_array(),
+                };
+            }
+            target = match (target, indexer) {
+                (Value::Object(m), JsonIndexer::ObjInd(k)) => {
+                    vacant = !m.contains_key(&k[..]);
+                    m.entry(k[..].into()).or_insert(Value::Null)
+                }
+                (Value::Array(a), &JsonIndexer::ArrInd(i)) => {
+                    vacant = a.len() <= i;
+                    if vacant {
+                        a.resize(i + 1, Value::Null);
+                    }
+                    &mut a[i]
+                }
+                _ => unreachable!("path is checked by check_slot"),
+            };
+        }
+        Ok((target, vacant))
+    }
+
+    /// check that [`Value::slot_mut`] can reach `path` without changing this value.
+    fn check_slot(&self, path: &JsonPath, options: &SetOptions) -> Result<(), PathError> {
+        // `None` is container that will be created
+        let mut target = Some(self);
+        for (depth, indexer) in path.iter().enumerate() {
+            let at = || path.iter().take(depth + 1).cloned().collect::<JsonPath>().to_string();
+            target = match (target, indexer) {
+                (None | Some(Value::Null), JsonIndexer::ObjInd(_)) => None,
+                (None | Some(Value::Null), &JsonIndexer::ArrInd(i)) => {
+                    check_array_index(0, i, options.grow_array, at)?;
+                    None
+                }
+                (Some(Value::Object(m)), JsonIndexer::ObjInd(k)) => m.get(&k[..]),
+                (Some(Value::Array(a)), &JsonIndexer::ArrInd(i)) => {
+                    check_array_index(a.len(), i, options.grow_array, at)?;
+                    a.get(i)
+                }
+                (Some(found), _) => return Err(PathError::Conflict { path: at(), found: found.node_type() }),
+            };
+        }
+        Ok(())
+    }
+
+    /// remove value at `path` from its parent object or array, and get it. if `path` point nothing, return `None`.
+    /// if `path` is root, take this value and leave `null`.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, Value};
+    /// let mut json = Value::parse(r#"{"a": [1, 2, 3], "b": {"c": true}}"#).unwrap();
+    ///
+    /// assert_eq!(json.remove_at_path(&JsonPath::from_dotted("a[1]").unwrap()), Some(Value::Integer(2)));
+    /// assert_eq!(json.remove_at_path(&JsonPath::from_dotted("b.c").unwrap()), Some(Value::Bool(true)));
+    /// assert_eq!(json.remove_at_path(&JsonPath::from_dotted("b.d").unwrap()), None);
+    /// assert_eq!(json, Value::parse(r#"{"a": [1, 3], "b": {}}"#).unwrap());
+    /// ```
+    pub fn remove_at_path(&mut self, path: &JsonPath) -> Option<Value> {
+        match path.split_last() {
+            None => Some(self.take()),
+            Some((parent, last)) => match (self.get_mut(&parent)?, last) {
+                (Value::Object(m), JsonIndexer::ObjInd(k)) => m.remove(&k[..]),
+                (Value::Array(a), &JsonIndexer::ArrInd(i)) => (i < a.len()).then(|| a.remove(i)),
+                _ => None,
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -97,6 +525,22 @@ mod tests {
         assert_eq!(json, Value::parse(r#"{"key": [0, 1, 2, 3, {"foo": {"bar": "baz"}}]}"#).unwrap());
     }
 
+    #[test]
+    fn test_take_ast_node() {
+        let raw = r#"{"key": ["zero", 1, {"foo": {"bar": "baz"}}]}"#;
+        let mut json = Value::parse(raw).unwrap();
+
+        let foo = json["key"][2]["foo"].take();
+        assert_eq!(foo, Value::parse(r#"{"bar": "baz"}"#).unwrap());
+        let zero = json["key"][0].replace(foo);
+        assert_eq!(zero, "zero".into());
+        assert_eq!(json, Value::parse(r#"{"key": [{"bar": "baz"}, 1, {"foo": null}]}"#).unwrap());
+
+        let taken = json.take();
+        assert_eq!(json, Value::Null);
+        assert_eq!(taken["key"][1], 1.into());
+    }
+
     #[test]
     fn test_update_ast_node() {
         let raw = r#"{"key": [0, 1, 2, 3], "foo": {"bar": "baz"}}"#;
@@ -105,9 +549,177 @@ mod tests {
         json["key"].update_with(|val| val.iter().map(|v| Value::from(v.integer() + 1)).collect());
 
         assert_eq!(json, Value::parse(r#"{"key": [1, 2, 3, 4], "foo": {"bar": "baz"}}"#).unwrap());
+
+        json.modify(|v| v["key"].retain_array(|e| e.integer() % 2 == 0)).modify(|v| v["foo"]["bar"] = Value::Null);
+        assert_eq!(json, Value::parse(r#"{"key": [2, 4], "foo": {"bar": null}}"#).unwrap());
+    }
+
+    #[test]
+    fn test_map_leaves_ast_node() {
+        let raw = r#"{"a": [1, {"b": null}, [], {}], "c": "d", "e": true}"#;
+        let mut json = Value::parse(raw).unwrap();
+
+        let mut visited = Vec::new();
+        let mapped = json.map_leaves(|path, v| {
+            visited.push(path.to_string());
+            Value::from(v.node_type())
+        });
+        assert_eq!(
+            mapped,
+            Value::parse(r#"{"a": ["Integer", {"b": "Null"}, [], {}], "c": "String", "e": "Bool"}"#).unwrap()
+        );
+        assert_eq!(visited, [r#""a">0"#, r#""a">1>"b""#, r#""c""#, r#""e""#]);
+        assert_eq!(Value::Null.map_leaves(|_, _| Value::from(1)), Value::from(1));
+
+        json.map_leaves_mut(|path, v| *v = Value::from(path.to_string()));
+        assert_eq!(
+            json,
+            Value::parse(r#"{"a": ["\"a\">0", {"b": "\"a\">1>\"b\""}, [], {}], "c": "\"c\"", "e": "\"e\""}"#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_retain_ast_node() {
+        let raw = r#"{"a": 1, "b": null, "c": [1, null, 2, 3], "d": {"e": null, "f": [null]}}"#;
+        let mut json = Value::parse(raw).unwrap();
+
+        json["c"].retain_array(|v| v.get_integer().map_or(false, |i| i % 2 == 1));
+        assert_eq!(json["c"], Value::parse("[1, 3]").unwrap());
+        json.retain_object(|k, v| {
+            *v = Value::from(k);
+            k != "b"
+        });
+        assert_eq!(json, Value::parse(r#"{"a": "a", "c": "c", "d": "d"}"#).unwrap());
+
+        let mut json = Value::parse(raw).unwrap();
+        let mut visited = Vec::new();
+        json.retain_recursive(|path, v| {
+            visited.push(path.to_string());
+            !v.is_null() && path.depth() < 3
+        });
+        assert_eq!(json, Value::parse(r#"{"a": 1, "c": [1, 2, 3], "d": {"f": []}}"#).unwrap());
+        assert_eq!(
+            visited,
+            [
+                r#""a""#,
+                r#""b""#,
+                r#""c""#,
+                r#""c">0"#,
+                r#""c">1"#,
+                r#""c">2"#,
+                r#""c">3"#,
+                r#""d""#,
+                r#""d">"e""#,
+                r#""d">"f""#,
+                r#""d">"f">0"#
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_remove_at_path() {
+        let path = |s| JsonPath::from_dotted(s).unwrap();
+        let mut json = Value::Null;
+        assert_eq!(json.set(&path("a.b[0]"), 1.into()), Ok(None));
+        assert_eq!(json.set(&path("a.b[1].c"), 2.into()), Ok(None));
+        assert_eq!(json.set(&path("a.b[0]"), 3.into()), Ok(Some(1.into())));
+        assert_eq!(json.set(&path("a.d"), Value::Null), Ok(None));
+        assert_eq!(json.set(&path("a.d[0]"), true.into()), Ok(None));
+        assert_eq!(json, Value::parse(r#"{"a": {"b": [3, {"c": 2}], "d": [true]}}"#).unwrap());
+
+        assert_eq!(
+            json.set(&path("a.b[3]"), 4.into()),
+            Err(PathError::OutOfBounds { path: r#""a">"b">3"#.into(), len: 2 })
+        );
+        assert_eq!(
+            json.set(&path("a.b.c"), 4.into()),
+            Err(PathError::Conflict { path: r#""a">"b">"c""#.into(), found: "Array" })
+        );
+        assert_eq!(
+            json.set(&path("a.b[0].c"), 4.into()),
+            Err(PathError::Conflict { path: r#""a">"b">0>"c""#.into(), found: "Integer" })
+        );
+        assert_eq!(json.set_with(&path("a.b[3]"), 4.into(), SetOptions::new().grow_array(true)), Ok(None));
+        assert_eq!(json["a"]["b"], Value::parse(r#"[3, {"c": 2}, null, 4]"#).unwrap());
+        for (index, len) in [(usize::MAX, 4), (4_000_000_000, 4), (65541, 4)] {
+            let path: JsonPath =
+                [JsonIndexer::ObjInd("a".into()), JsonIndexer::ObjInd("b".into()), JsonIndexer::ArrInd(index)]
+                    .into_iter()
+                    .collect();
+            let err = PathError::OutOfBounds { path: path.to_string(), len };
+            assert_eq!(json.set_with(&path, 4.into(), SetOptions::new().grow_array(true)), Err(err));
+        }
+
+        assert_eq!(json.remove_at_path(&path("a.b[2]")), Some(Value::Null));
+        assert_eq!(json.remove_at_path(&path("a.b[5]")), None);
+        assert_eq!(json.remove_at_path(&path("a.b[0].c")), None);
+        assert_eq!(json.remove_at_path(&path("x.y")), None);
+        assert_eq!(json.remove_at_path(&path("a.d")), Some(Value::parse("[true]").unwrap()));
+        assert_eq!(json, Value::parse(r#"{"a": {"b": [3, {"c": 2}, 4]}}"#).unwrap());
+        let prev = json.clone();
+        assert_eq!(json.set(&JsonPath::new(), 1.into()), Ok(Some(prev)));
+        assert_eq!(json.remove_at_path(&JsonPath::new()), Some(1.into()));
+        assert_eq!(json, Value::Null);
+    }
+
+    #[test]
+    fn test_set_error_unchanged() {
+        let path = |s| JsonPath::from_dotted(s).unwrap();
+        for (json, p) in [
+            (r#"{"a": 1}"#, "x.y[5]"),
+            (r#"{"a": {"b": 1}}"#, "a.c.d[1]"),
+            (r#"{"a": {"b": 1}}"#, "a.b.c"),
+            (r#"{"a": null, "b": [null]}"#, "a[0].c[2]"),
+            (r#"{"a": null, "b": [null]}"#, "b[0].c[0].d[1]"),
+            (r#"[[1], {"x": 2}]"#, "[1].x[0]"),
+        ] {
+            let mut value = Value::parse(json).unwrap();
+            assert!(value.set(&path(p), 2.into()).is_err(), "{json} {p}");
+            assert_eq!(value, Value::parse(json).unwrap(), "{json} {p}");
+            assert!(value.get_or_insert_with_path(&path(p), || unreachable!()).is_err(), "{json} {p}");
+            assert_eq!(value, Value::parse(json).unwrap(), "{json} {p}");
+        }
+    }
+
+    #[test]
+    fn test_get_or_insert_with_path() {
+        let path = |s| JsonPath::from_dotted(s).unwrap();
+        let mut json = Value::parse(r#"{"a": {"b": null}}"#).unwrap();
+        *json.get_or_insert_with_path(&path("a.c[0]"), || 1.into()).unwrap().get_mut_integer().unwrap() += 1;
+        assert_eq!(json.get_or_insert_with_path(&path("a.c[0]"), || unreachable!()), Ok(&mut 2.into()));
+        assert_eq!(json.get_or_insert_with_path(&path("a.b"), || unreachable!()), Ok(&mut Value::Null));
+        assert!(json.get_or_insert_with_path(&path("a.c[2]"), || unreachable!()).is_err());
+        assert_eq!(json, Value::parse(r#"{"a": {"b": null, "c": [2]}}"#).unwrap());
+    }
+
+    #[test]
+    fn test_sort_ast_node() {
+        let mut json = Value::parse(r#"[3, "b", null, [1], {"a": 1}, 1.5, true, "a", false, [0, 1], 2]"#).unwrap();
+        json.sort_array_by(order);
+        assert_eq!(json, Value::parse(r#"[null, false, true, 1.5, 2, 3, "a", "b", [0, 1], [1], {"a": 1}]"#).unwrap());
+        json.sort_array_by(|a, b| order(b, a));
+        assert_eq!(json[0], Value::parse(r#"{"a": 1}"#).unwrap());
+
+        let mut json =
+            Value::parse(r#"[{"k": 2, "n": 0}, {"n": 1}, {"k": 1, "n": 2}, {"k": 2, "n": 3}, {"n": 4}]"#).unwrap();
+        json.sort_array_by_key("k");
+        assert_eq!(json.iter().map(|v| *v["n"].integer()).collect::<Vec<_>>(), [1, 4, 2, 0, 3]);
+        json.sort_array_by_key(&JsonPath::from_dotted("n").unwrap());
+        assert_eq!(json.iter().map(|v| *v["n"].integer()).collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+        let mut json = Value::parse(r#"[[3, "c"], [1, "a"], [2, "b"]]"#).unwrap();
+        json.sort_array_by_key(1);
+        assert_eq!(json, Value::parse(r#"[[1, "a"], [2, "b"], [3, "c"]]"#).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_retain_unexpected_type() {
+        Value::parse("[]").unwrap().retain_object(|_, _| true);
     }
 
     #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
     fn test_insertion_order() {
         let raw = r#"{"foo": "hoge", "bar": "fuga", "baz": "piyo"}"#;
         let mut json = Value::parse(raw).unwrap();
@@ -115,8 +727,8 @@ mod tests {
         json.update_with(|val| {
             let mut cloned = val.object().clone();
             cloned.remove("bar");
-            cloned.insert("one".to_string(), Value::from(1));
-            cloned.insert("baz".to_string(), Value::from("piyo"));
+            cloned.insert("one".into(), Value::from(1));
+            cloned.insert("baz".into(), Value::from("piyo"));
             Value::from(cloned)
         });
 
@@ -0,0 +1,120 @@
+//! entry of object, like entry api of `HashMap`. see [`Value::entry`].
+
+use super::{
+    map::{Key, Map},
+    Value,
+};
+
+/// [`Entry`] is entry of object specified by key, it may be occupied or vacant. see [`Value::entry`].
+#[derive(Debug)]
+pub struct Entry<'a> {
+    object: &'a mut Map<Key, Value>,
+    key: Key,
+}
+
+impl Value {
+    /// get entry of object specified by `key` for in-place manipulation.
+    /// # panics
+    /// if self is not [`Value::Object`], it will panic. if want to get `None` instead, use [`Value::get_entry`].
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut counter = Value::parse("{}").unwrap();
+    /// for word in ["a", "b", "a"] {
+    ///     counter.entry(word).and_modify(|c| *c = Value::from(c.integer() + 1)).or_insert(Value::Integer(1));
+    /// }
+    /// assert_eq!(counter, Value::parse(r#"{"a": 2, "b": 1}"#).unwrap());
+    /// ```
+    pub fn entry<K: Into<Key>>(&mut self, key: K) -> Entry<'_> {
+        let node_type = self.node_type();
+        self.get_entry(key).unwrap_or_else(|| panic!("only Object can get entry, but {}", node_type))
+    }
+    /// get mutable reference of value of `key` in object. if there is no value, insert value returned by `default`
+    /// and get it. this is shorthand of `entry(key).or_insert_with(default)`. see
+    /// [`Value::get_or_insert_with_path`] for nested path.
+    /// # panics
+    /// if self is not [`Value::Object`].
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::new_object();
+    /// for tag in ["a", "b"] {
+    ///     json.get_or_insert_with("tags", Value::new_array).extend([Value::from(tag)]);
+    /// }
+    /// assert_eq!(json, Value::parse(r#"{"tags": ["a", "b"]}"#).unwrap());
+    /// ```
+    pub fn get_or_insert_with<K: Into<Key>, F: FnOnce() -> Value>(&mut self, key: K, default: F) -> &mut Value {
+        self.entry(key).or_insert_with(default)
+    }
+    /// get entry of object specified by `key`. if self is not [`Value::Object`], return `None`.
+    pub fn get_entry<K: Into<Key>>(&mut self, key: K) -> Option<Entry<'_>> {
+        let object = self.get_mut_object()?;
+        Some(Entry { object, key: key.into() })
+    }
+}
+
+impl<'a> Entry<'a> {
+    /// get key of this entry.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// get value of this entry, if it is occupied.
+    pub fn get(&self) -> Option<&Value> {
+        self.object.get(&self.key)
+    }
+
+    /// insert `default` if this entry is vacant, and get mutable reference to the value.
+    pub fn or_insert(self, default: Value) -> &'a mut Value {
+        self.or_insert_with(|| default)
+    }
+
+    /// insert result of `default` if this entry is vacant, and get mutable reference to the value.
+    /// `default` is called only if this entry is vacant.
+    pub fn or_insert_with<F: FnOnce() -> Value>(self, default: F) -> &'a mut Value {
+        if !self.object.contains_key(&self.key) {
+            self.object.insert(self.key.clone(), default());
+        }
+        self.object.get_mut(&self.key).expect("entry should be occupied")
+    }
+
+    /// insert [`Value::Null`] if this entry is vacant, and get mutable reference to the value.
+    pub fn or_null(self) -> &'a mut Value {
+        self.or_insert(Value::Null)
+    }
+
+    /// modify the value by `f` if this entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut Value)>(self, f: F) -> Self {
+        if let Some(value) = self.object.get_mut(&self.key) {
+            f(value);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let mut json = Value::parse(r#"{"hits": {"a": 1}, "tags": []}"#).unwrap();
+        *json["hits"].entry("a").or_insert(Value::Integer(0)) = Value::Integer(10);
+        json["hits"].entry("b").or_insert_with(|| Value::Integer(5));
+        json["hits"].entry("b").and_modify(|v| *v = Value::Integer(v.integer() * 2)).or_null();
+        json.entry("missing").or_null();
+        json.entry("tags").or_insert_with(|| unreachable!("occupied entry does not call default"));
+        assert_eq!(json, Value::parse(r#"{"hits": {"a": 10, "b": 10}, "tags": [], "missing": null}"#).unwrap());
+
+        let entry = json.entry(String::from("hits"));
+        assert_eq!(entry.key(), "hits");
+        assert_eq!(entry.get().map(Value::is_object), Some(true));
+        assert!(json["tags"].get_entry("key").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "only Object can get entry, but Array")]
+    fn test_entry_panic() {
+        Value::parse("[]").unwrap().entry("key");
+    }
+}
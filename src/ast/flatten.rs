@@ -0,0 +1,147 @@
+use super::{
+    index::JsonIndexer,
+    index_path::{check_array_index, JsonPath, PathError},
+    map::{Key, Map},
+    Value,
+};
+
+impl Value {
+    /// flatten this value into map from dotted path such as `a.b[0].c` to leaf value, in dfs order. leaf is scalar
+    /// value, `{}`, or `[]`. path is written by [`JsonPath::to_dotted`], and see [`Value::unflatten`] for inverse.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"a": {"b": [{"c": 1}, true]}, "d.e": "f", "g": []}"#).unwrap();
+    ///
+    /// let flatten = json.flatten();
+    /// assert_eq!(flatten.len(), 4);
+    /// assert_eq!(flatten["a.b[0].c"], Value::Integer(1));
+    /// assert_eq!(flatten["a.b[1]"], Value::Bool(true));
+    /// assert_eq!(flatten[r#"["d.e"]"#], Value::from("f"));
+    /// assert_eq!(flatten["g"], Value::Array(Vec::new()));
+    /// assert_eq!(Value::unflatten(flatten).unwrap(), json);
+    /// ```
+    pub fn flatten(&self) -> Map<Key, Value> {
+        #[allow(clippy::useless_conversion)] // `Key` is `String` without `interned-keys` feature
+        fn flatten_recursive(value: &Value, path: &mut JsonPath, flatten: &mut Map<Key, Value>) {
+            match value {
+                Value::Object(m) if !m.is_empty() => {
+                    for (k, v) in m.iter() {
+                        path.push(JsonIndexer::ObjInd(k.to_string()));
+                        flatten_recursive(v, path, flatten);
+                        path.pop();
+                    }
+                }
+                Value::Array(a) if !a.is_empty() => {
+                    for (i, e) in a.iter().enumerate() {
+                        path.push(JsonIndexer::ArrInd(i));
+                        flatten_recursive(e, path, flatten);
+                        path.pop();
+                    }
+                }
+                leaf => {
+                    flatten.insert(path.to_dotted().into(), leaf.clone());
+                }
+            }
+        }
+        let mut flatten = Map::new();
+        flatten_recursive(self, &mut JsonPath::new(), &mut flatten);
+        flatten
+    }
+
+    /// build value from entries of dotted path and value, that is inverse of [`Value::flatten`]. objects and arrays
+    /// are created as needed, and missing elements of array are filled with `null`. if path is invalid, or path
+    /// go through value that is already set, such as both `a` and `a.b`, return error. index that leave too large
+    /// gap (more than 65536 elements) from the end of array is also error, to avoid huge allocation.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::unflatten([("server.port", Value::from(8080)), ("server.hosts[1]", Value::from("b"))]).unwrap();
+    /// assert_eq!(json, Value::parse(r#"{"server": {"port": 8080, "hosts": [null, "b"]}}"#).unwrap());
+    ///
+    /// assert!(Value::unflatten([("a", Value::from(1)), ("a.b", Value::from(2))]).is_err());
+    /// ```
+    pub fn unflatten<K: AsRef<str>, I: IntoIterator<Item = (K, Value)>>(entries: I) -> Result<Value, PathError> {
+        let mut root = Value::Null;
+        for (dotted, value) in entries {
+            let path = JsonPath::from_dotted(dotted.as_ref())?;
+            let conflict = |found: &Value| PathError::Conflict { path: path.to_dotted(), found: found.node_type() };
+            let mut target = &mut root;
+            for indexer in &path {
+                if target.is_null() {
+                    *target = match indexer {
+                        JsonIndexer::ObjInd(_) => Value::Object(Default::default()),
+                        JsonIndexer::ArrInd(_) => Value::Array(Vec::new()),
+                    };
+                }
+                target = match (target, indexer) {
+                    (Value::Object(m), JsonIndexer::ObjInd(k)) => m.entry(k[..].into()).or_insert(Value::Null),
+                    (Value::Array(a), &JsonIndexer::ArrInd(i)) => {
+                        check_array_index(a.len(), i, true, || path.to_dotted())?;
+                        if a.len() <= i {
+                            a.resize(i + 1, Value::Null);
+                        }
+                        &mut a[i]
+                    }
+                    (found, _) => return Err(conflict(found)),
+                };
+            }
+            match target {
+                Value::Null => *target = value,
+                found => return Err(conflict(found)),
+            }
+        }
+        Ok(root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_flatten_roundtrip() {
+        for json in [
+            "1",
+            "null",
+            "{}",
+            "[]",
+            r#"{"a": 1, "b": [true, null, {"c": "d"}], "e": {"f": {}, "g": []}}"#,
+            r#"[[1, [2]], {"": 3, "x.y": {"[z]": 4}}]"#,
+            r#"{"quote\"back\\slash": [null, null]}"#,
+        ] {
+            let value = Value::parse(json).unwrap();
+            assert_eq!(Value::unflatten(value.flatten()).unwrap(), value, "{json}");
+        }
+        let flatten = Value::parse(r#"{"a": [{"b": 1}], "": {"c": 2}}"#).unwrap().flatten();
+        assert_eq!(flatten.keys().map(|k| &k[..]).collect::<HashSet<_>>(), [r#"a[0].b"#, r#"[""].c"#].into());
+        assert_eq!(Value::Integer(1).flatten().keys().map(|k| &k[..]).collect::<Vec<_>>(), [""]);
+    }
+
+    #[test]
+    fn test_unflatten_error() {
+        for (entries, path) in [
+            (vec![("a", "1"), ("a.b", "2")], Some("a.b")),
+            (vec![("a.b", "2"), ("a", "1")], Some("a")),
+            (vec![("a[0]", "1"), ("a.b", "2")], Some("a.b")),
+            (vec![("a", "{}"), ("a[0]", "1")], Some("a[0]")),
+            (vec![("", "1"), ("a", "2")], Some("a")),
+            (vec![("a", "1"), ("a", "1")], Some("a")),
+            (vec![("a..b", "1")], None),
+        ] {
+            let entries: Vec<_> = entries.into_iter().map(|(k, v)| (k, Value::parse(v).unwrap())).collect();
+            match (Value::unflatten(entries.clone()), path) {
+                (Err(PathError::Conflict { path: p, .. }), Some(path)) => assert_eq!(p, path, "{entries:?}"),
+                (Err(PathError::InvalidPath { .. }), None) => (),
+                (result, _) => panic!("{entries:?} must be error, but {result:?}"),
+            }
+        }
+        for (index, len) in [("x[18446744073709551615]", 0), ("x[4000000000]", 0), ("y[65538]", 1)] {
+            let entries = [("y[0]", Value::Null), (index, Value::Null)];
+            assert_eq!(Value::unflatten(entries), Err(PathError::OutOfBounds { path: index.to_string(), len }));
+        }
+        let json = Value::unflatten([("y[65536]", Value::Bool(true))]).unwrap();
+        assert_eq!(json["y"].len(), 65537);
+    }
+}
@@ -0,0 +1,309 @@
+use super::{serializer::Serializer, Value};
+
+/// [`ExponentStyle`] specify how float is written. see [`FormatOptions::exponent`] also.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExponentStyle {
+    /// never use exponent, write float in positional notation like `100000000000000000000`.
+    Never,
+    /// use lower exponent like `1e20` for very large or small float.
+    Lower,
+    /// use upper exponent like `1E20` for very large or small float.
+    Upper,
+}
+
+impl Default for ExponentStyle {
+    fn default() -> Self {
+        Self::Never
+    }
+}
+
+/// [`LineEnding`] specify linefeed of stringify. see [`FormatOptions::line_ending`] also.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`, POSIX style.
+    Lf,
+    /// `\r\n`, Windows style.
+    CrLf,
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        Self::Lf
+    }
+}
+impl LineEnding {
+    /// get string of this line ending.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+/// [`FormatOptions`] configure output of stringify. see [`Value::to_string_with`] and [`Value::stringify_with`].
+/// # examples
+/// ```
+/// use dyson::{ExponentStyle, FormatOptions, LineEnding, Value};
+/// let json = Value::Array(vec![Value::Float(-0.0), Value::Float(1e21)]);
+/// assert_eq!(json.to_string(), "[-0,1000000000000000000000]");
+/// let options = FormatOptions::new().negative_zero(false).exponent(ExponentStyle::Upper);
+/// assert_eq!(json.to_string_with(&options), "[0,1E21]");
+///
+/// let options = FormatOptions::new().ascii_only(true);
+/// assert_eq!(Value::from("é").to_string_with(&options), r#""\u00e9""#);
+///
+/// let options = FormatOptions::new().indent(2);
+/// assert_eq!(json.stringify_with(&options), "[\n  -0,\n  1000000000000000000000\n]");
+///
+/// let options = FormatOptions::new().indent(1).line_ending(LineEnding::CrLf).trailing_newline(true);
+/// assert_eq!(Value::parse("[1]").unwrap().stringify_with(&options), "[\r\n 1\r\n]\r\n");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    negative_zero: bool,
+    exponent: ExponentStyle,
+    indent_width: usize,
+    indent_char: char,
+    ascii_only: bool,
+    escape_solidus: bool,
+    pub(crate) inline_leaves: Option<usize>,
+    pub(crate) line_ending: LineEnding,
+    pub(crate) trailing_newline: bool,
+}
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            negative_zero: true,
+            exponent: ExponentStyle::default(),
+            indent_width: 4,
+            indent_char: ' ',
+            ascii_only: false,
+            escape_solidus: false,
+            inline_leaves: None,
+            line_ending: LineEnding::default(),
+            trailing_newline: false,
+        }
+    }
+}
+impl FormatOptions {
+    /// get default options, it is same as [`Value::to_string`] and [`Value::stringify`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// if `negative_zero`, `-0.0` is written as `-0`, otherwise `0`. by default, sign of zero is kept.
+    pub fn negative_zero(mut self, negative_zero: bool) -> Self {
+        self.negative_zero = negative_zero;
+        self
+    }
+
+    /// write float whose absolute value is `>= 1e21` or `< 1e-6` with exponent of `exponent` style.
+    /// by default, exponent is never used.
+    pub fn exponent(mut self, exponent: ExponentStyle) -> Self {
+        self.exponent = exponent;
+        self
+    }
+
+    /// indent nested element of stringify by `indent_width` of indent char. by default, 4.
+    pub fn indent(mut self, indent_width: usize) -> Self {
+        self.indent_width = indent_width;
+        self
+    }
+
+    /// indent nested element of stringify with `indent_char`, such as `'\t'`. by default, space.
+    pub fn indent_char(mut self, indent_char: char) -> Self {
+        self.indent_char = indent_char;
+        self
+    }
+
+    /// if `ascii_only`, non-ASCII characters of string are escaped as `\uXXXX`, with surrogate pair if needed.
+    /// by default, non-ASCII characters are written as raw UTF-8.
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+        self
+    }
+
+    /// if `escape_solidus`, `/` of string is escaped as `\/`, such as for embedding json in html `<script>`.
+    /// by default, `/` is written as is.
+    pub fn escape_solidus(mut self, escape_solidus: bool) -> Self {
+        self.escape_solidus = escape_solidus;
+        self
+    }
+
+    /// in stringify, write object or array in one line like `[1, 2, 3]` if it has at most `max_elements` elements
+    /// and all of them are not object or array. by default, every object and array is expanded.
+    pub fn inline_leaves(mut self, max_elements: usize) -> Self {
+        self.inline_leaves = Some(max_elements);
+        self
+    }
+
+    /// write linefeed of stringify as `line_ending`. by default, [`LineEnding::Lf`].
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// if `trailing_newline`, output is terminated by line ending, as POSIX text file.
+    /// by default, output ends with the last token of json.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// get indent string of `depth`.
+    pub(crate) fn indent_of(&self, depth: usize) -> String {
+        self.indent_char.to_string().repeat(self.indent_width * depth)
+    }
+
+    /// quote and escape string with this options.
+    pub(crate) fn quote(&self, s: &str) -> String {
+        let mut quoted = String::with_capacity(s.len() + 2);
+        quoted.push('"');
+        for c in s.chars() {
+            match c {
+                '\\' => quoted.push_str("\\\\"),
+                '"' => quoted.push_str("\\\""),
+                '/' if self.escape_solidus => quoted.push_str("\\/"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                // `\b` and `\f` are written as `\u0008` and `\u000c`, same as other control characters
+                c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c if self.ascii_only && !c.is_ascii() => {
+                    for unit in c.encode_utf16(&mut [0; 2]) {
+                        quoted.push_str(&format!("\\u{unit:04x}"));
+                    }
+                }
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    /// format float with this options.
+    pub(crate) fn float(&self, float: f64) -> String {
+        let abs = float.abs();
+        let exponential = abs != 0.0 && abs.is_finite() && !(1e-6..1e21).contains(&abs);
+        match self.exponent {
+            ExponentStyle::Lower if exponential => format!("{float:e}"),
+            ExponentStyle::Upper if exponential => format!("{float:E}"),
+            _ if float == 0.0 && !self.negative_zero => "0".to_string(),
+            _ => float.to_string(),
+        }
+    }
+}
+
+impl Value {
+    /// stringify ast without unnecessary space and linefeed, with given options. see [`FormatOptions`] also.
+    pub fn to_string_with(&self, options: &FormatOptions) -> String {
+        let mut serializer = Serializer::new(Vec::new(), options.clone());
+        serializer.value(self).expect("write into Vec should not fail");
+        String::from_utf8(serializer.into_inner()).expect("serialized json should be UTF-8")
+    }
+
+    /// stringify ast with proper indent, with given options. see [`FormatOptions`] also.
+    pub fn stringify_with(&self, options: &FormatOptions) -> String {
+        let mut serializer = Serializer::pretty(Vec::new(), options.clone());
+        serializer.value(self).expect("write into Vec should not fail");
+        String::from_utf8(serializer.into_inner()).expect("serialized json should be UTF-8")
+    }
+}
+
+pub(crate) fn quote(s: &str) -> String {
+    FormatOptions::default().quote(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_float() {
+        let json =
+            Value::Array(vec![Value::Float(-0.0), Value::Float(1.5e300), Value::Float(2.5e-7), Value::Float(0.25)]);
+        let default = FormatOptions::new();
+        assert_eq!(json.to_string_with(&default), json.to_string());
+        assert_eq!(json.stringify_with(&default), json.stringify());
+
+        let lower = FormatOptions::new().negative_zero(false).exponent(ExponentStyle::Lower);
+        assert_eq!(json.to_string_with(&lower), "[0,1.5e300,2.5e-7,0.25]");
+        let upper = FormatOptions::new().exponent(ExponentStyle::Upper);
+        assert_eq!(json.to_string_with(&upper), "[-0,1.5E300,2.5E-7,0.25]");
+        assert_eq!(Value::parse(json.to_string_with(&upper)).unwrap()[1], Value::Float(1.5e300));
+    }
+
+    #[test]
+    fn test_format_ascii_only() {
+        let json = Value::parse(r#"{"キー": ["é", "😀", "a/b\n"]}"#).unwrap();
+        let ascii = FormatOptions::new().ascii_only(true);
+        assert_eq!(json.to_string_with(&ascii), r#"{"\u30ad\u30fc":["\u00e9","\ud83d\ude00","a/b\n"]}"#);
+        assert!(json.stringify_with(&ascii).is_ascii());
+        let solidus = FormatOptions::new().escape_solidus(true);
+        assert_eq!(Value::from("</script>").to_string_with(&solidus), r#""<\/script>""#);
+        assert_eq!(Value::from("</script>").to_string(), r#""</script>""#);
+        assert_eq!(json.to_string_with(&FormatOptions::new()), json.to_string());
+    }
+
+    #[test]
+    fn test_format_control() {
+        let json = Value::from("\u{0}\u{1}\u{8}\u{c}\u{1f}\n\u{7f}");
+        assert_eq!(json.to_string(), "\"\\u0000\\u0001\\u0008\\u000c\\u001f\\n\u{7f}\"");
+        assert_eq!(Value::parse_with(json.to_string(), crate::ParserOptions::new().strict(true)).unwrap(), json);
+    }
+
+    #[test]
+    fn test_format_inline_leaves() {
+        let json =
+            Value::parse(r#"{"empty": {}, "keyword": ["a", "b"], "matrix": [[1, 2], [3, 4, 5]], "point": {"x": 1}}"#);
+        let inline = FormatOptions::new().indent(2).inline_leaves(2);
+        assert_eq!(
+            json.unwrap().stringify_with(&inline),
+            [
+                "{",
+                r#"  "empty": {},"#,
+                r#"  "keyword": ["a", "b"],"#,
+                r#"  "matrix": ["#,
+                "    [1, 2],",
+                "    [",
+                "      3,",
+                "      4,",
+                "      5",
+                "    ]",
+                "  ],",
+                r#"  "point": {"x": 1}"#,
+                "}",
+            ]
+            .join("\n")
+        );
+        let json = Value::parse(r#"[[1, 2]]"#).unwrap();
+        assert_eq!(json.to_string_with(&inline), json.to_string());
+        assert_eq!(Value::parse(json.stringify_with(&inline)).unwrap(), json);
+    }
+
+    #[test]
+    fn test_format_line_ending() {
+        let json = Value::parse(r#"{"empty": [], "key": [1, {"nested": null}]}"#).unwrap();
+        let crlf = FormatOptions::new().line_ending(LineEnding::CrLf);
+        assert_eq!(json.stringify_with(&crlf), json.stringify().replace('\n', "\r\n"));
+        assert_eq!(Value::parse(json.stringify_with(&crlf)).unwrap(), json);
+        assert_eq!(json.to_string_with(&crlf), json.to_string());
+
+        let newline = FormatOptions::new().trailing_newline(true);
+        assert_eq!(json.stringify_with(&newline), format!("{}\n", json.stringify()));
+        assert_eq!(json.to_string_with(&newline), format!("{}\n", json));
+        assert_eq!(Value::Null.to_string_with(&newline.line_ending(LineEnding::CrLf)), "null\r\n");
+    }
+
+    #[test]
+    fn test_format_indent() {
+        let json = Value::parse(r#"{"key": [1, {"nested": null}]}"#).unwrap();
+        let tab = FormatOptions::new().indent(1).indent_char('\t');
+        assert_eq!(json.stringify_with(&tab), "{\n\t\"key\": [\n\t\t1,\n\t\t{\n\t\t\t\"nested\": null\n\t\t}\n\t]\n}");
+        let two = FormatOptions::new().indent(2);
+        assert_eq!(json.stringify_with(&two), json.stringify().replace("    ", "  "));
+        assert_eq!(Value::parse(json.stringify_with(&tab)).unwrap(), json);
+        assert_eq!(json.to_string_with(&two), json.to_string());
+    }
+}
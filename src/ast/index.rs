@@ -59,6 +59,37 @@ pub enum JsonIndexer {
     ObjInd(String),
     ArrInd(usize),
 }
+/// [`JsonIndexerRef`] is borrowed [`JsonIndexer`], it is carried by edges of [`crate::DfsEvent`] without allocation.
+/// # examples
+/// ```
+/// use dyson::{JsonIndexer, JsonIndexerRef, Value};
+/// let raw_json = r#"{"key": [1, "two", 3, "four", 5]}"#;
+/// let json = Value::parse(raw_json).unwrap();
+///
+/// assert_eq!(json[JsonIndexerRef::ObjInd("key")][JsonIndexerRef::ArrInd(0)], Value::Integer(1));
+/// assert_eq!(JsonIndexer::from(JsonIndexerRef::ObjInd("key")), JsonIndexer::ObjInd("key".to_string()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonIndexerRef<'a> {
+    ObjInd(&'a str),
+    ArrInd(usize),
+}
+impl From<JsonIndexerRef<'_>> for JsonIndexer {
+    fn from(indexer: JsonIndexerRef<'_>) -> Self {
+        match indexer {
+            JsonIndexerRef::ObjInd(s) => JsonIndexer::ObjInd(s.to_string()),
+            JsonIndexerRef::ArrInd(i) => JsonIndexer::ArrInd(i),
+        }
+    }
+}
+impl<'a> From<&'a JsonIndexer> for JsonIndexerRef<'a> {
+    fn from(indexer: &'a JsonIndexer) -> Self {
+        match indexer {
+            JsonIndexer::ObjInd(s) => JsonIndexerRef::ObjInd(s),
+            &JsonIndexer::ArrInd(i) => JsonIndexerRef::ArrInd(i),
+        }
+    }
+}
 
 pub trait JsonIndex {
     type Output: ?Sized;
@@ -67,7 +98,7 @@ pub trait JsonIndex {
     fn indexed(self, value: &Value) -> &Self::Output;
     fn indexed_mut(self, value: &mut Value) -> &mut Self::Output;
 }
-impl<'a> JsonIndex for &'a str {
+impl JsonIndex for &str {
     type Output = Value;
     fn gotten(self, value: &Value) -> Option<&Self::Output> {
         match value {
@@ -152,28 +183,30 @@ impl JsonIndex for &JsonIndexer {
     type Output = Value;
     fn gotten(self, value: &Value) -> Option<&Self::Output> {
         match (self, value) {
-            (JsonIndexer::ObjInd(s), Value::Object(m)) => m.get(s),
+            (JsonIndexer::ObjInd(s), Value::Object(m)) => m.get(&s[..]),
             (&JsonIndexer::ArrInd(i), Value::Array(a)) => a.get(i),
             _ => None,
         }
     }
     fn gotten_mut(self, value: &mut Value) -> Option<&mut Self::Output> {
         match (self, value) {
-            (JsonIndexer::ObjInd(s), Value::Object(m)) => m.get_mut(s),
+            (JsonIndexer::ObjInd(s), Value::Object(m)) => m.get_mut(&s[..]),
             (&JsonIndexer::ArrInd(i), Value::Array(a)) => a.get_mut(i),
             _ => None,
         }
     }
     fn indexed(self, value: &Value) -> &Self::Output {
         match (&self, value) {
-            (JsonIndexer::ObjInd(s), Value::Object(m)) => &m[s],
+            (JsonIndexer::ObjInd(s), Value::Object(m)) => &m[&s[..]],
             (&&JsonIndexer::ArrInd(i), Value::Array(a)) => &a[i],
             _ => panic!("{} cannot be indexed by {:?}", value.node_type(), &self),
         }
     }
     fn indexed_mut(self, value: &mut Value) -> &mut Self::Output {
         match (&self, value) {
-            (JsonIndexer::ObjInd(s), Value::Object(m)) => &mut m[s],
+            (JsonIndexer::ObjInd(s), Value::Object(m)) => {
+                m.get_mut(&s[..]).unwrap_or_else(|| panic!("no such key: \"{s}\""))
+            }
             (&&JsonIndexer::ArrInd(i), Value::Array(a)) => &mut a[i],
             (_, v) => panic!("{} cannot be indexed by {:?}", v.node_type(), &self),
         }
@@ -194,6 +227,33 @@ impl JsonIndex for JsonIndexer {
         (&self).indexed_mut(value)
     }
 }
+impl JsonIndex for JsonIndexerRef<'_> {
+    type Output = Value;
+    fn gotten(self, value: &Value) -> Option<&Self::Output> {
+        match self {
+            JsonIndexerRef::ObjInd(s) => s.gotten(value),
+            JsonIndexerRef::ArrInd(i) => i.gotten(value),
+        }
+    }
+    fn gotten_mut(self, value: &mut Value) -> Option<&mut Self::Output> {
+        match self {
+            JsonIndexerRef::ObjInd(s) => s.gotten_mut(value),
+            JsonIndexerRef::ArrInd(i) => i.gotten_mut(value),
+        }
+    }
+    fn indexed(self, value: &Value) -> &Self::Output {
+        match self {
+            JsonIndexerRef::ObjInd(s) => s.indexed(value),
+            JsonIndexerRef::ArrInd(i) => i.indexed(value),
+        }
+    }
+    fn indexed_mut(self, value: &mut Value) -> &mut Self::Output {
+        match self {
+            JsonIndexerRef::ObjInd(s) => s.indexed_mut(value),
+            JsonIndexerRef::ArrInd(i) => i.indexed_mut(value),
+        }
+    }
+}
 
 impl<I: JsonIndex> std::ops::Index<I> for Value {
     type Output = I::Output;
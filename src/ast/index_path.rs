@@ -1,8 +1,11 @@
 use super::{
+    format::quote,
     index::{JsonIndex, JsonIndexer},
-    quote, Value,
+    Value,
 };
+use crate::syntax::parse_string;
 use itertools::Itertools;
+use thiserror::Error;
 
 /// [`JsonPath`] is used for accessing [`Value`]. see [`Value::get`] also.
 /// # examples
@@ -23,10 +26,10 @@ pub struct JsonPath {
 impl JsonIndex for &JsonPath {
     type Output = Value;
     fn gotten(self, value: &Value) -> Option<&Self::Output> {
-        self.iter().fold(Some(value), |v, i| v.and_then(|sv| sv.get(i)))
+        self.iter().try_fold(value, |v, i| v.get(i))
     }
     fn gotten_mut(self, value: &mut Value) -> Option<&mut Self::Output> {
-        self.iter().fold(Some(value), |v, i| v.and_then(|sv| sv.get_mut(i)))
+        self.iter().try_fold(value, |v, i| v.get_mut(i))
     }
     fn indexed(self, value: &Value) -> &Self::Output {
         self.iter().fold(value, |v, i| &v[i])
@@ -122,7 +125,7 @@ impl JsonPath {
     /// get lowest common ancestor. this method's complexity is **O(`lca.depth()`)**.
     pub fn lca(a: &Self, b: &Self) -> Self {
         let mut result = Self::new();
-        for (ai, _bi) in itertools::zip(a, b).take_while(|(ai, bi)| ai == bi) {
+        for (ai, _bi) in std::iter::zip(a, b).take_while(|(ai, bi)| ai == bi) {
             result.push(ai.clone());
         }
         result
@@ -163,7 +166,7 @@ impl std::ops::IndexMut<usize> for JsonPath {
 
 impl Extend<JsonIndexer> for JsonPath {
     fn extend<T: IntoIterator<Item = JsonIndexer>>(&mut self, iter: T) {
-        self.path.extend(iter.into_iter())
+        self.path.extend(iter)
     }
 }
 
@@ -189,7 +192,7 @@ impl<'a> IntoIterator for &'a JsonPath {
         // FIXME why cannot compile?
         // type IntoIter = std::slice::Iter<'a, Self::Item>;
         // (&self.path).into_iter()
-        (&self.path).iter().collect_vec().into_iter()
+        self.path.iter().collect_vec().into_iter()
     }
 }
 impl IntoIterator for JsonPath {
@@ -213,6 +216,363 @@ impl std::fmt::Display for JsonPath {
     }
 }
 
+/// [`PathError`] is error of parsing string into [`JsonPath`], or accessing [`Value`] by paths.
+/// see [`JsonPath::from_dotted`], [`Value::get_disjoint_mut`], [`Value::unflatten`], and [`Value::set`] also.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PathError {
+    #[error("invalid path {:?} at {}: {}", path, pos, reason)]
+    InvalidPath { path: String, pos: usize, reason: &'static str },
+
+    #[error("path {} overlap with path {}", path, other)]
+    Overlapping { path: String, other: String },
+
+    #[error("path {} conflict with existing {}", path, found)]
+    Conflict { path: String, found: &'static str },
+
+    #[error("path {} is out of bounds of array with length {}", path, len)]
+    OutOfBounds { path: String, len: usize },
+}
+
+/// the largest number of `null` that fill the gap when value is set at index beyond the end of array, such as by
+/// [`Value::unflatten`]. it prevent huge allocation by index from untrusted input.
+pub(crate) const MAX_ARRAY_GAP: usize = 1 << 16;
+
+/// check that value can be set at index `i` of array whose length is `len`. index beyond the end is allowed only if
+/// `grow`, and its gap must not exceed [`MAX_ARRAY_GAP`]. `path` is called to report error.
+pub(crate) fn check_array_index<F: FnOnce() -> String>(
+    len: usize,
+    i: usize,
+    grow: bool,
+    path: F,
+) -> Result<(), PathError> {
+    if i > len && (!grow || i - len > MAX_ARRAY_GAP) {
+        Err(PathError::OutOfBounds { path: path(), len })
+    } else {
+        Ok(())
+    }
+}
+
+impl JsonPath {
+    /// parse dotted path such as `key.nested[0]`. key that contain `.`, `[` or `]` can be quoted like `["a.b"]`,
+    /// and `\` in quoted key escape next char. empty string is root path.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, JsonPath};
+    /// let path = JsonPath::from_dotted(r#"keyword[0]["a.b"].c"#).unwrap();
+    /// assert_eq!(path.to_string(), r#""keyword">0>"a.b">"c""#);
+    /// assert_eq!(JsonPath::from_dotted("").unwrap(), JsonPath::new());
+    /// assert!(JsonPath::from_dotted("keyword[x]").is_err());
+    /// ```
+    pub fn from_dotted(s: &str) -> Result<Self, PathError> {
+        let err = |pos, reason| PathError::InvalidPath { path: s.to_string(), pos, reason };
+        let mut path = JsonPath::new();
+        let mut chars = s.char_indices().peekable();
+        let take_key = |chars: &mut std::iter::Peekable<std::str::CharIndices>| {
+            let mut key = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if matches!(c, '.' | '[' | ']') {
+                    break;
+                }
+                key.push(c);
+                chars.next();
+            }
+            key
+        };
+        while let Some(&(i, c)) = chars.peek() {
+            match c {
+                '[' => {
+                    chars.next();
+                    if let Some(&(_, '"')) = chars.peek() {
+                        chars.next();
+                        let mut key = String::new();
+                        loop {
+                            match chars.next() {
+                                Some((_, '"')) => break,
+                                Some((_, '\\')) => key.extend(chars.next().map(|(_, c)| c)),
+                                Some((_, c)) => key.push(c),
+                                None => return Err(err(s.len(), "unterminated quoted key")),
+                            }
+                        }
+                        path.push(JsonIndexer::ObjInd(key));
+                    } else {
+                        let mut digits = String::new();
+                        while let Some(&(_, d @ '0'..='9')) = chars.peek() {
+                            digits.push(d);
+                            chars.next();
+                        }
+                        let index = digits.parse().map_err(|_| err(i + 1, "expected array index"))?;
+                        path.push(JsonIndexer::ArrInd(index));
+                    }
+                    match chars.next() {
+                        Some((_, ']')) => (),
+                        Some((j, _)) => return Err(err(j, "expected `]`")),
+                        None => return Err(err(s.len(), "expected `]`")),
+                    }
+                }
+                '.' if path.depth() > 0 => {
+                    chars.next();
+                    let key = take_key(&mut chars);
+                    if key.is_empty() {
+                        return Err(err(i + 1, "expected key"));
+                    }
+                    path.push(JsonIndexer::ObjInd(key));
+                }
+                _ if i == 0 && c != '.' && c != ']' => path.push(JsonIndexer::ObjInd(take_key(&mut chars))),
+                _ => return Err(err(i, "expected `.` or `[`")),
+            }
+        }
+        Ok(path)
+    }
+
+    /// get dotted path that can be parsed by [`JsonPath::from_dotted`]. key that is empty or contain `.`, `[` or `]`
+    /// is quoted.
+    /// # examples
+    /// ```
+    /// use dyson::JsonPath;
+    /// let path = JsonPath::from_dotted(r#"keyword[0]["a.b"].c"#).unwrap();
+    /// assert_eq!(path.to_dotted(), r#"keyword[0]["a.b"].c"#);
+    /// assert_eq!(JsonPath::from_dotted(&path.to_dotted()).unwrap(), path);
+    /// ```
+    pub fn to_dotted(&self) -> String {
+        let mut dotted = String::new();
+        for indexer in self {
+            match indexer {
+                JsonIndexer::ObjInd(key) if key.is_empty() || key.contains(['.', '[', ']']) => {
+                    let escaped = key.replace('\\', "\\\\").replace('"', "\\\"");
+                    dotted.push_str(&format!("[\"{escaped}\"]"));
+                }
+                JsonIndexer::ObjInd(key) if dotted.is_empty() => dotted.push_str(key),
+                JsonIndexer::ObjInd(key) => dotted.push_str(&format!(".{key}")),
+                JsonIndexer::ArrInd(i) => dotted.push_str(&format!("[{i}]")),
+            }
+        }
+        dotted
+    }
+
+    /// parse path written by [`std::fmt::Display`] of [`JsonPath`], such as `"key">0>"a.b"`, so that path reported
+    /// by [`crate::diff_value`] can be used for accessing [`Value`]. json pointer such as `/key/0/a.b` is also
+    /// accepted, see [`JsonPath::from_pointer`].
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, JsonPath};
+    /// let path = JsonPath::parse(r#""key">0>"a.b""#).unwrap();
+    /// assert_eq!(JsonPath::parse(&path.to_string()).unwrap(), path);
+    /// assert_eq!(JsonPath::parse("/key/0/a.b").unwrap(), path);
+    /// assert_eq!(JsonPath::parse("").unwrap(), JsonPath::new());
+    /// assert!(JsonPath::parse("key>0").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, PathError> {
+        if s.starts_with('/') {
+            return Self::from_pointer(s);
+        }
+        let err = |pos, reason| PathError::InvalidPath { path: s.to_string(), pos, reason };
+        let mut path = JsonPath::new();
+        let mut pos = 0;
+        while pos < s.len() {
+            let rest = &s[pos..];
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let mut escaped = false;
+                let end = quoted.find(|c| {
+                    let closed = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closed
+                });
+                let quoted = &rest[..2 + end.ok_or_else(|| err(pos, "unterminated quoted key"))?];
+                let key = parse_string(quoted).map_err(|_| err(pos, "invalid quoted key"))?;
+                path.push(JsonIndexer::ObjInd(key));
+                pos += quoted.len();
+            } else {
+                let digits = &rest[..rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len())];
+                let index = digits.parse().map_err(|_| err(pos, "expected quoted key or array index"))?;
+                path.push(JsonIndexer::ArrInd(index));
+                pos += digits.len();
+            }
+            match s[pos..].chars().next() {
+                Some('>') if pos + 1 < s.len() => pos += 1,
+                Some('>') => return Err(err(pos + 1, "expected quoted key or array index")),
+                Some(_) => return Err(err(pos, "expected `>`")),
+                None => (),
+            }
+        }
+        Ok(path)
+    }
+}
+
+impl Value {
+    /// access json value by dotted path such as `a.b[2].c`. see [`JsonPath::from_dotted`] for its syntax.
+    /// if path is invalid, return error, and if path point nothing, return `Ok(None)`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"a": {"b": [1, 2, {"c": "d"}]}}"#).unwrap();
+    ///
+    /// assert_eq!(json.get_path("a.b[2].c").unwrap(), Some(&Value::String("d".to_string())));
+    /// assert_eq!(json.get_path("a.b[3]").unwrap(), None);
+    /// assert!(json.get_path("a..b").is_err());
+    /// ```
+    pub fn get_path(&self, path: &str) -> Result<Option<&Value>, PathError> {
+        Ok(self.get(&JsonPath::from_dotted(path)?))
+    }
+
+    /// access json value by dotted path, and get mutable reference of it. see [`Value::get_path`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"a": {"b": [1, 2]}}"#).unwrap();
+    /// *json.get_path_mut("a.b[0]").unwrap().unwrap() = "one".into();
+    /// assert_eq!(json, Value::parse(r#"{"a": {"b": ["one", 2]}}"#).unwrap());
+    /// ```
+    pub fn get_path_mut(&mut self, path: &str) -> Result<Option<&mut Value>, PathError> {
+        Ok(self.get_mut(&JsonPath::from_dotted(path)?))
+    }
+
+    /// access several json values at once, and get mutable references of them. if one of `paths` is equal to or
+    /// ancestor of another, they are not disjoint and return error. path that point nothing become `None`.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, PathError, Value};
+    /// let mut json = Value::parse(r#"{"user": {"name": "dyson", "age": 1}, "tags": ["json"]}"#).unwrap();
+    /// let (name, tag) = (JsonPath::from_dotted("user.name").unwrap(), JsonPath::from_dotted("tags[0]").unwrap());
+    ///
+    /// let [name_mut, tag_mut] = json.get_disjoint_mut([&name, &tag]).unwrap();
+    /// std::mem::swap(name_mut.unwrap(), tag_mut.unwrap());
+    /// assert_eq!(json, Value::parse(r#"{"user": {"name": "json", "age": 1}, "tags": ["dyson"]}"#).unwrap());
+    ///
+    /// let user = JsonPath::from_dotted("user").unwrap();
+    /// assert!(matches!(json.get_disjoint_mut([&user, &name]), Err(PathError::Overlapping { .. })));
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        paths: [&JsonPath; N],
+    ) -> Result<[Option<&mut Value>; N], PathError> {
+        fn get_disjoint_mut_recursive<'a>(
+            value: &'a mut Value,
+            paths: Vec<(usize, &[JsonIndexer])>,
+            found: &mut [Option<&'a mut Value>],
+        ) {
+            if let [(i, [])] = paths[..] {
+                found[i] = Some(value);
+                return;
+            }
+            let mut descend = |child, matched: &dyn Fn(&JsonIndexer) -> bool| {
+                let sub: Vec<_> = paths.iter().filter(|(_, p)| matched(&p[0])).map(|&(i, p)| (i, &p[1..])).collect();
+                if !sub.is_empty() {
+                    get_disjoint_mut_recursive(child, sub, found);
+                }
+            };
+            match value {
+                Value::Object(m) => m.iter_mut().for_each(|(k, child)| {
+                    descend(child, &|indexer| matches!(indexer, JsonIndexer::ObjInd(key) if key[..] == k[..]))
+                }),
+                Value::Array(a) => a.iter_mut().enumerate().for_each(|(i, child)| {
+                    descend(child, &|indexer| matches!(indexer, JsonIndexer::ArrInd(j) if *j == i))
+                }),
+                _ => (),
+            }
+        }
+        for (i, path) in paths.iter().enumerate() {
+            if let Some(other) = paths[i + 1..].iter().find(|other| path.starts_with(other) || other.starts_with(path))
+            {
+                return Err(PathError::Overlapping { path: path.to_string(), other: other.to_string() });
+            }
+        }
+        let mut found = [(); N].map(|_| None);
+        get_disjoint_mut_recursive(self, paths.iter().map(|p| &p.path[..]).enumerate().collect(), &mut found);
+        Ok(found)
+    }
+}
+
+/// [`StaticPath`] is [`JsonPath`] that can be used in `const` context, such as generated by `json_paths!` of
+/// `dyson-macros`. it can be used for accessing [`Value`] as well as [`JsonPath`].
+/// # examples
+/// ```
+/// use dyson::{StaticIndexer, StaticPath, Value};
+/// const FIRST_KEYWORD: StaticPath = StaticPath(&[StaticIndexer::ObjInd("keyword"), StaticIndexer::ArrInd(0)]);
+/// let json = Value::parse(r#"{"keyword": ["rust", "json"]}"#).unwrap();
+///
+/// assert_eq!(json[FIRST_KEYWORD], Value::String("rust".to_string()));
+/// assert_eq!(FIRST_KEYWORD.to_string(), r#""keyword">0"#);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticPath(
+    /// indexers from root.
+    pub &'static [StaticIndexer],
+);
+/// [`StaticIndexer`] is [`JsonIndexer`] that can be used in `const` context. see [`StaticPath`] also.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StaticIndexer {
+    ObjInd(&'static str),
+    ArrInd(usize),
+}
+
+impl StaticPath {
+    /// get owned [`JsonPath`].
+    pub fn to_path(&self) -> JsonPath {
+        self.0.iter().map(|&i| i.into()).collect()
+    }
+}
+impl From<StaticIndexer> for JsonIndexer {
+    fn from(indexer: StaticIndexer) -> Self {
+        match indexer {
+            StaticIndexer::ObjInd(s) => JsonIndexer::ObjInd(s.to_string()),
+            StaticIndexer::ArrInd(i) => JsonIndexer::ArrInd(i),
+        }
+    }
+}
+impl From<StaticPath> for JsonPath {
+    fn from(path: StaticPath) -> Self {
+        path.to_path()
+    }
+}
+
+impl JsonIndex for StaticIndexer {
+    type Output = Value;
+    fn gotten(self, value: &Value) -> Option<&Self::Output> {
+        match self {
+            StaticIndexer::ObjInd(s) => s.gotten(value),
+            StaticIndexer::ArrInd(i) => i.gotten(value),
+        }
+    }
+    fn gotten_mut(self, value: &mut Value) -> Option<&mut Self::Output> {
+        match self {
+            StaticIndexer::ObjInd(s) => s.gotten_mut(value),
+            StaticIndexer::ArrInd(i) => i.gotten_mut(value),
+        }
+    }
+    fn indexed(self, value: &Value) -> &Self::Output {
+        match self {
+            StaticIndexer::ObjInd(s) => s.indexed(value),
+            StaticIndexer::ArrInd(i) => i.indexed(value),
+        }
+    }
+    fn indexed_mut(self, value: &mut Value) -> &mut Self::Output {
+        match self {
+            StaticIndexer::ObjInd(s) => s.indexed_mut(value),
+            StaticIndexer::ArrInd(i) => i.indexed_mut(value),
+        }
+    }
+}
+impl JsonIndex for StaticPath {
+    type Output = Value;
+    fn gotten(self, value: &Value) -> Option<&Self::Output> {
+        self.0.iter().try_fold(value, |v, &i| v.get(i))
+    }
+    fn gotten_mut(self, value: &mut Value) -> Option<&mut Self::Output> {
+        self.0.iter().try_fold(value, |v, &i| v.get_mut(i))
+    }
+    fn indexed(self, value: &Value) -> &Self::Output {
+        self.0.iter().fold(value, |v, &i| &v[i])
+    }
+    fn indexed_mut(self, value: &mut Value) -> &mut Self::Output {
+        self.0.iter().fold(value, |v, &i| &mut v[i])
+    }
+}
+impl std::fmt::Display for StaticPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_path())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,4 +649,136 @@ mod tests {
         assert_eq!(pa.join(&pb), JsonPath::from(&[JsonIndexer::ObjInd("key".to_string()), JsonIndexer::ArrInd(2)][..]));
         assert_eq!(ast_root[&pa.join(&pb)], Value::parse(r#"{ "foo": "bar" }"#).unwrap());
     }
+
+    #[test]
+    fn test_from_dotted() {
+        let path = |indexers: &[JsonIndexer]| JsonPath::from(indexers);
+        let (key, i) = (|k: &str| JsonIndexer::ObjInd(k.to_string()), JsonIndexer::ArrInd);
+        assert_eq!(JsonPath::from_dotted("version").unwrap(), path(&[key("version")]));
+        assert_eq!(JsonPath::from_dotted("keyword[0]").unwrap(), path(&[key("keyword"), i(0)]));
+        assert_eq!(JsonPath::from_dotted("[1][2].a.b").unwrap(), path(&[i(1), i(2), key("a"), key("b")]));
+        assert_eq!(JsonPath::from_dotted(r#"a["b.c"]["q\"\\"]"#).unwrap(), path(&[key("a"), key("b.c"), key("q\"\\")]));
+        assert_eq!(JsonPath::from_dotted("日本.語").unwrap(), path(&[key("日本"), key("語")]));
+
+        for (invalid, pos) in [
+            (".a", 0),
+            ("a..b", 2),
+            ("a.", 2),
+            ("a[", 2),
+            ("a[x]", 2),
+            ("a[0", 3),
+            ("a[0]b", 4),
+            ("]", 0),
+            (r#"["a"#, 3),
+        ] {
+            match JsonPath::from_dotted(invalid) {
+                Err(PathError::InvalidPath { pos: p, .. }) => assert_eq!(p, pos, "{invalid:?}"),
+                result => panic!("{invalid:?} must be invalid, but {result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let path = |indexers: &[JsonIndexer]| JsonPath::from(indexers);
+        let (key, i) = (|k: &str| JsonIndexer::ObjInd(k.to_string()), JsonIndexer::ArrInd);
+        for expected in [
+            path(&[]),
+            path(&[key("key"), i(0), key("a.b")]),
+            path(&[i(12), i(3)]),
+            path(&[key(""), key(">"), key("q\"\\"), key("/~"), key("改行\n")]),
+        ] {
+            assert_eq!(JsonPath::parse(&expected.to_string()).unwrap(), expected);
+            assert_eq!(JsonPath::parse(&expected.to_pointer()).unwrap(), expected, "{expected}");
+        }
+
+        for (invalid, pos) in
+            [("key", 0), (r#""key"0"#, 5), (r#""key">"#, 6), (">0", 0), ("0>>1", 2), (r#""key>0"#, 0), ("-1", 0)]
+        {
+            match JsonPath::parse(invalid) {
+                Err(PathError::InvalidPath { pos: p, .. }) => assert_eq!(p, pos, "{invalid:?}"),
+                result => panic!("{invalid:?} must be invalid, but {result:?}"),
+            }
+        }
+
+        let a = Value::parse(r#"{"list": [{"name": "a"}, {"name": "b"}]}"#).unwrap();
+        let b = Value::parse(r#"{"list": [{"name": "a"}, {"name": "c"}]}"#).unwrap();
+        for (path_a, path_b) in crate::diff_value(&a, &b) {
+            let (path_a, path_b) =
+                (JsonPath::parse(&path_a.to_string()).unwrap(), JsonPath::parse(&path_b.to_string()).unwrap());
+            assert_eq!((&a[&path_a], &b[&path_b]), (&Value::from("b"), &Value::from("c")));
+        }
+    }
+
+    #[test]
+    fn test_to_dotted() {
+        let path = |indexers: &[JsonIndexer]| JsonPath::from(indexers);
+        let (key, i) = (|k: &str| JsonIndexer::ObjInd(k.to_string()), JsonIndexer::ArrInd);
+        for (expected, dotted) in [
+            (path(&[]), ""),
+            (path(&[key("a"), i(0), key("b")]), "a[0].b"),
+            (path(&[i(1), key("")]), r#"[1][""]"#),
+            (path(&[key("a.b"), key("[c]"), key(r#"q"\"#)]), r#"["a.b"]["[c]"].q"\"#),
+            (path(&[key("x]"), key(r#"."\"#)]), r#"["x]"][".\"\\"]"#),
+        ] {
+            assert_eq!(expected.to_dotted(), dotted);
+            assert_eq!(JsonPath::from_dotted(dotted).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_get_path() {
+        let mut json = Value::parse(r#"{"a": {"b": [1, 2, {"c": "d"}]}, "x.y": [true]}"#).unwrap();
+        assert_eq!(json.get_path("").unwrap(), Some(&json));
+        assert_eq!(json.get_path("a.b[1]").unwrap(), Some(&Value::Integer(2)));
+        assert_eq!(json.get_path(r#"["x.y"][0]"#).unwrap(), Some(&Value::Bool(true)));
+        assert_eq!(json.get_path("a.b[2].e").unwrap(), None);
+        assert_eq!(json.get_path("a[0]").unwrap(), None);
+        assert!(matches!(json.get_path("a[x]"), Err(PathError::InvalidPath { pos: 2, .. })));
+
+        json.get_path_mut("a.b[2].c").unwrap().unwrap().take();
+        assert_eq!(json["a"]["b"][2], Value::parse(r#"{"c": null}"#).unwrap());
+        assert_eq!(json.get_path_mut("nothing").unwrap(), None);
+        assert!(json.get_path_mut("a.").is_err());
+    }
+
+    #[test]
+    fn test_get_disjoint_mut() {
+        let mut json = Value::parse(r#"{"a": {"b": [1, 2, 3], "c": "d"}, "e": null}"#).unwrap();
+        let path = |s: &str| JsonPath::from_dotted(s).unwrap();
+        let (b0, b2, c, e, missing) = (path("a.b[0]"), path("a.b[2]"), path("a.c"), path("e"), path("a.b[3]"));
+
+        let [b0_mut, b2_mut, c_mut, e_mut, missing_mut] = json.get_disjoint_mut([&b0, &b2, &c, &e, &missing]).unwrap();
+        assert!(missing_mut.is_none());
+        std::mem::swap(b0_mut.unwrap(), b2_mut.unwrap());
+        *e_mut.unwrap() = c_mut.unwrap().take();
+        assert_eq!(json, Value::parse(r#"{"a": {"b": [3, 2, 1], "c": null}, "e": "d"}"#).unwrap());
+
+        let [root] = json.get_disjoint_mut([&JsonPath::new()]).unwrap();
+        assert_eq!(root.unwrap()["e"], Value::from("d"));
+        assert_eq!(json.get_disjoint_mut::<0>([]).unwrap(), []);
+        for (paths, overlapping) in [
+            ([&b0, &b0], (&b0, &b0)),
+            ([&path("a"), &c], (&path("a"), &c)),
+            ([&c, &JsonPath::new()], (&c, &JsonPath::new())),
+        ] {
+            assert_eq!(
+                json.get_disjoint_mut(paths).unwrap_err(),
+                PathError::Overlapping { path: overlapping.0.to_string(), other: overlapping.1.to_string() }
+            );
+        }
+    }
+
+    #[test]
+    fn test_static_path() {
+        const FOO: StaticPath =
+            StaticPath(&[StaticIndexer::ObjInd("key"), StaticIndexer::ArrInd(2), StaticIndexer::ObjInd("foo")]);
+        let mut json = Value::parse(r#"{ "key": [ 1, "two", { "foo": "bar" } ] }"#).unwrap();
+        assert_eq!(json[FOO], Value::String("bar".to_string()));
+        assert_eq!(json.get(FOO), json.get(&FOO.to_path()));
+        assert_eq!(JsonPath::from(FOO), JsonPath::from_dotted("key[2].foo").unwrap());
+        json[FOO] = Value::Null;
+        assert_eq!(json.get_mut(FOO), Some(&mut Value::Null));
+        assert_eq!(json.get(StaticPath(&[StaticIndexer::ArrInd(0)])), None);
+    }
 }
@@ -1,25 +1,30 @@
-use super::Value;
-use linked_hash_map::LinkedHashMap;
+use super::map::{Key, Map};
+use super::{
+    number::{exact_float, exact_integer, raw_number, Number, RawNumber},
+    Value,
+};
+use std::borrow::Cow;
+use thiserror::Error;
 
-/// evaluate `Value` to corresponded object such as `LinkedHashMap`, `Vec`, `bool`, `str`, `i64`, or `f64`.
+/// evaluate `Value` to corresponded object such as `Map`, `Vec`, `bool`, `str`, `i64`, or `f64`.
 /// # panics
 /// call different type evaluate method cause panic.
 /// for example, if call [`Value::object`] to [`Value::Array`], it will panic.
 /// if want to get `None` instead of panic, use `get_` prefixed methods.
 impl Value {
-    pub fn get_object(&self) -> Option<&LinkedHashMap<String, Value>> {
+    pub fn get_object(&self) -> Option<&Map<Key, Value>> {
         match self {
             Value::Object(m) => Some(m),
             _ => None,
         }
     }
-    pub fn get_mut_object(&mut self) -> Option<&mut LinkedHashMap<String, Value>> {
+    pub fn get_mut_object(&mut self) -> Option<&mut Map<Key, Value>> {
         match self {
             Value::Object(m) => Some(m),
             _ => None,
         }
     }
-    pub fn object(&self) -> &LinkedHashMap<String, Value> {
+    pub fn object(&self) -> &Map<Key, Value> {
         self.get_object().unwrap_or_else(|| panic!("only Object can convert into HashMap, but {}", self.node_type()))
     }
 
@@ -112,6 +117,96 @@ impl Value {
     pub fn float(&self) -> &f64 {
         self.get_float().unwrap_or_else(|| panic!("only Float can convert into f64, but {}", self.node_type()))
     }
+
+    pub fn get_raw_number(&self) -> Option<&RawNumber> {
+        match self {
+            Value::RawNumber(n) => Some(n),
+            _ => None,
+        }
+    }
+    pub fn raw_number(&self) -> &RawNumber {
+        self.get_raw_number()
+            .unwrap_or_else(|| panic!("only RawNumber can convert into RawNumber, but {}", self.node_type()))
+    }
+
+    /// get [`Number`] from `Integer`, `Float`, or `RawNumber`. `RawNumber` is `Number::Integer` if it fit in `i64`,
+    /// otherwise `Number::Float` that may be rounded. if value is not number, return `None`.
+    pub fn number(&self) -> Option<Number> {
+        match self {
+            &Value::Integer(i) => Some(Number::Integer(i)),
+            &Value::Float(f) => Some(Number::Float(f)),
+            Value::RawNumber(raw) => raw.as_i64().map(Number::Integer).or_else(|| raw.as_f64().map(Number::Float)),
+            _ => None,
+        }
+    }
+}
+
+/// coerce number `Value` across `Integer`, `Float`, and `RawNumber` only when it is exact, because json does not
+/// distinguish `2` and `2.0`. if value is not number or coercion lose precision, return `None`. `RawNumber` with
+/// fraction part or exponent part is coerced by its parsed `f64` value, so `1e-400` is coerced into `0`.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// assert_eq!(Value::Float(2.0).as_i64(), Some(2));
+/// assert_eq!(Value::Float(2.5).as_i64(), None);
+/// assert_eq!(Value::Integer(-1).as_u64(), None);
+/// assert_eq!(Value::Integer(2).as_f64(), Some(2.0));
+/// assert_eq!(Value::Integer(i64::MAX).as_f64(), None);
+/// ```
+impl Value {
+    /// get `i64` if this value is integral number in range of `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_i128().and_then(|i| i.try_into().ok())
+    }
+    /// get `u64` if this value is integral number in range of `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.as_i128().and_then(|i| i.try_into().ok())
+    }
+    /// get `f64` if this value is number that `f64` can represent exactly.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            &Value::Integer(i) => exact_float(i.into()),
+            &Value::Float(f) => Some(f),
+            Value::RawNumber(raw) if raw.is_integer() => raw.as_str().parse().ok().and_then(exact_float),
+            Value::RawNumber(raw) => raw.as_f64().filter(|f| f.is_finite()),
+            _ => None,
+        }
+    }
+
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            &Value::Integer(i) => Some(i.into()),
+            &Value::Float(f) => exact_integer(f),
+            Value::RawNumber(raw) if raw.is_integer() => raw.as_str().parse().ok(),
+            Value::RawNumber(raw) => raw.as_f64().and_then(exact_integer),
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    /// get `str` if this value is [`Value::String`]. this is same as [`Value::get_string`].
+    pub fn as_str(&self) -> Option<&str> {
+        self.get_string()
+    }
+    /// get text to display this value. [`Value::String`] is borrowed as it is without quotes and escapes, and other
+    /// values such as number, bool, and `null` are formatted as json, so any leaf can be shown as text.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// use std::borrow::Cow;
+    /// let json = Value::parse(r#"["a\"b", 1, 2.5, true, null, [3]]"#).unwrap();
+    ///
+    /// assert!(matches!(json[0].to_text(), Cow::Borrowed(r#"a"b"#)));
+    /// let texts: Vec<_> = json.iter().skip(1).map(Value::to_text).collect();
+    /// assert_eq!(texts, vec!["1", "2.5", "true", "null", "[3]"]);
+    /// ```
+    pub fn to_text(&self) -> Cow<'_, str> {
+        match self {
+            Value::String(s) => Cow::Borrowed(s),
+            _ => Cow::Owned(self.to_string()),
+        }
+    }
 }
 
 impl Value {
@@ -130,7 +225,7 @@ impl Value {
     ///     vec!["foo", "bar"].into_iter().collect()
     /// );
     /// ```
-    pub fn items(&self) -> impl Iterator<Item = (&String, &Value)> {
+    pub fn items(&self) -> impl Iterator<Item = (&Key, &Value)> {
         match self {
             Value::Object(m) => m.iter(),
             _ => panic!("only Object can iterate with items, but {}", self.node_type()),
@@ -153,106 +248,337 @@ impl Value {
             _ => panic!("only Array can iterate, but {}", self.node_type()),
         }
     }
-}
+    /// iterate keys of [`Value::Object`]
+    /// # panics
+    /// if value is not `Object`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": [1, "two", 3], "bar": 6}"#).unwrap();
+    ///
+    /// let mut keys: Vec<_> = json.keys().map(|k| &k[..]).collect();
+    /// keys.sort();
+    /// assert_eq!(keys, vec!["bar", "foo"]);
+    /// ```
+    pub fn keys(&self) -> impl Iterator<Item = &Key> {
+        match self {
+            Value::Object(m) => m.keys(),
+            _ => panic!("only Object can iterate with keys, but {}", self.node_type()),
+        }
+    }
+    /// iterate values of [`Value::Object`]
+    /// # panics
+    /// if value is not `Object`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": 1, "bar": 2}"#).unwrap();
+    ///
+    /// assert_eq!(json.values().map(|v| v.integer()).sum::<i64>(), 3);
+    /// ```
+    pub fn values(&self) -> impl Iterator<Item = &Value> {
+        match self {
+            Value::Object(m) => m.values(),
+            _ => panic!("only Object can iterate with values, but {}", self.node_type()),
+        }
+    }
+    /// iterate mutable values of [`Value::Object`]
+    /// # panics
+    /// if value is not `Object`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"foo": 1, "bar": 2}"#).unwrap();
+    ///
+    /// json.values_mut().for_each(|v| *v.get_mut_integer().unwrap() *= 10);
+    /// assert_eq!(json, Value::parse(r#"{"foo": 10, "bar": 20}"#).unwrap());
+    /// ```
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+        match self {
+            Value::Object(m) => m.iter_mut().map(|(_, v)| v),
+            _ => panic!("only Object can iterate with values, but {}", self.node_type()),
+        }
+    }
+    /// consume [`Value::Object`] and iterate its owned entries. see `IntoIterator for Value` for [`Value::Array`].
+    /// # panics
+    /// if value is not `Object`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": [1, "two", 3], "bar": 6}"#).unwrap();
+    ///
+    /// let foo = json.into_items().find(|(k, _v)| &k[..] == "foo").map(|(_k, v)| v).unwrap();
+    /// assert_eq!(foo.into_iter().collect::<Vec<_>>(), vec![Value::from(1), Value::from("two"), Value::from(3)]);
+    /// ```
+    pub fn into_items(mut self) -> impl Iterator<Item = (Key, Value)> {
+        match &mut self {
+            Value::Object(m) => std::mem::take(&mut **m).into_iter(),
+            _ => panic!("only Object can iterate with items, but {}", self.node_type()),
+        }
+    }
 
-impl From<Value> for LinkedHashMap<String, Value> {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Object(m) => m,
-            _ => panic!("only Object can convert into HashMap, but {}", val.node_type()),
+    /// get number of entries of [`Value::Object`], elements of [`Value::Array`], or chars of [`Value::String`].
+    /// # panics
+    /// if value is not `Object`, `Array`, or `String`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": [1, "two", 3], "bar": "日本語"}"#).unwrap();
+    ///
+    /// assert_eq!(json.len(), 2);
+    /// assert_eq!(json["foo"].len(), 3);
+    /// assert_eq!(json["bar"].len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        match self {
+            Value::Object(m) => m.len(),
+            Value::Array(v) => v.len(),
+            Value::String(s) => s.chars().count(),
+            _ => panic!("only Object, Array, and String have length, but {}", self.node_type()),
         }
     }
-}
-impl<'a> From<&'a Value> for &'a LinkedHashMap<String, Value> {
-    fn from(val: &'a Value) -> Self {
-        match val {
-            Value::Object(m) => m,
-            _ => panic!("only Object can convert into HashMap, but {}", val.node_type()),
+
+    /// check [`Value::len`] is zero, such as `{}`, `[]`, and `""`.
+    /// # panics
+    /// if value is not `Object`, `Array`, or `String`.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Value::Object(m) => m.is_empty(),
+            Value::Array(v) => v.is_empty(),
+            Value::String(s) => s.is_empty(),
+            _ => panic!("only Object, Array, and String have length, but {}", self.node_type()),
         }
     }
 }
 
-impl From<Value> for Vec<Value> {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Array(v) => v,
-            _ => panic!("only Array can convert into Vec, but {}", val.node_type()),
-        }
+/// [`ConvertError`] is error of conversion from [`Value`] into rust object, such as `TryFrom<Value> for bool`.
+/// # examples
+/// ```
+/// use dyson::{ConvertError, Value};
+/// let value = Value::Integer(1);
+/// assert_eq!(bool::try_from(value), Err(ConvertError::UnexpectedType { expected: "Bool", found: "Integer" }));
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    #[error("only {} can be converted, but {}", expected, found)]
+    UnexpectedType { expected: &'static str, found: &'static str },
+}
+
+/// consume `Value` and get corresponded object such as `Map`, `Vec`, `bool`, `String`, `i64`, or `f64`.
+/// # panics
+/// call different type expect method cause panic.
+/// for example, if call [`Value::expect_object`] to [`Value::Array`], it will panic.
+/// if want to get error instead of panic, use `TryFrom` such as `Map::try_from(value)`.
+///
+/// these methods replace panicking `From<Value>` impls such as `let b: bool = value.into()`. they cannot be kept as
+/// deprecated, because `From` conflict with `TryFrom` for the same types, so migrate `.into()` into `expect_*` or
+/// `try_into`.
+impl Value {
+    pub fn expect_object(self) -> Map<Key, Value> {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn expect_array(self) -> Vec<Value> {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn expect_bool(self) -> bool {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn expect_string(self) -> String {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn expect_integer(self) -> i64 {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
+    }
+    pub fn expect_float(self) -> f64 {
+        self.try_into().unwrap_or_else(|e| panic!("{}", e))
     }
 }
-impl<'a> From<&'a Value> for &'a Vec<Value> {
-    fn from(val: &'a Value) -> Self {
-        match val {
-            Value::Array(v) => v,
-            _ => panic!("only Array can convert into Vec, but {}", val.node_type()),
+
+impl Value {
+    /// consume [`Value::Object`] and move its map out without clone. if value is not `Object`, return original value
+    /// as error, so it can be used again unlike `Map::try_from(value)`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": 1}"#).unwrap();
+    /// assert_eq!(json.into_object().unwrap()["foo"], Value::Integer(1));
+    ///
+    /// let json = Value::parse("[1]").unwrap();
+    /// let original = json.into_object().unwrap_err();
+    /// assert_eq!(original.into_array().unwrap(), vec![Value::Integer(1)]);
+    /// ```
+    pub fn into_object(mut self) -> Result<Map<Key, Value>, Value> {
+        match &mut self {
+            Value::Object(m) => Ok(std::mem::take(&mut **m)),
+            _ => Err(self),
+        }
+    }
+    /// consume [`Value::Array`] and move its elements out without clone. if value is not `Array`, return original
+    /// value as error. see [`Value::into_object`] also.
+    pub fn into_array(mut self) -> Result<Vec<Value>, Value> {
+        match &mut self {
+            Value::Array(v) => Ok(std::mem::take(v)),
+            _ => Err(self),
+        }
+    }
+    /// consume [`Value::String`] and move its string out without clone. if value is not `String`, return original
+    /// value as error. see [`Value::into_object`] also.
+    pub fn into_string(mut self) -> Result<String, Value> {
+        match &mut self {
+            Value::String(s) => Ok(std::mem::take(s)),
+            _ => Err(self),
         }
     }
 }
 
-impl From<Value> for bool {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Bool(b) => b,
-            _ => panic!("only Bool can convert into bool, but {}", val.node_type()),
+impl TryFrom<Value> for Map<Key, Value> {
+    type Error = ConvertError;
+    fn try_from(mut val: Value) -> Result<Self, Self::Error> {
+        match &mut val {
+            Value::Object(m) => Ok(std::mem::take(&mut **m)),
+            _ => Err(ConvertError::UnexpectedType { expected: "Object", found: val.node_type() }),
         }
     }
 }
-impl<'a> From<&'a Value> for &'a bool {
-    fn from(val: &'a Value) -> Self {
-        match val {
-            Value::Bool(b) => b,
-            _ => panic!("only Bool can convert into bool, but {}", val.node_type()),
+impl<'a> TryFrom<&'a Value> for &'a Map<Key, Value> {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_object().ok_or(ConvertError::UnexpectedType { expected: "Object", found: val.node_type() })
+    }
+}
+
+impl TryFrom<Value> for Vec<Value> {
+    type Error = ConvertError;
+    fn try_from(mut val: Value) -> Result<Self, Self::Error> {
+        match &mut val {
+            Value::Array(v) => Ok(std::mem::take(v)),
+            _ => Err(ConvertError::UnexpectedType { expected: "Array", found: val.node_type() }),
         }
     }
 }
+impl<'a> TryFrom<&'a Value> for &'a Vec<Value> {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_array().ok_or(ConvertError::UnexpectedType { expected: "Array", found: val.node_type() })
+    }
+}
 
-impl From<Value> for String {
-    fn from(val: Value) -> Self {
+impl TryFrom<Value> for bool {
+    type Error = ConvertError;
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
         match val {
-            Value::String(s) => s,
-            _ => panic!("only String can convert into String, but {}", val.node_type()),
+            Value::Bool(b) => Ok(b),
+            _ => Err(ConvertError::UnexpectedType { expected: "Bool", found: val.node_type() }),
         }
     }
 }
-impl<'a> From<&'a Value> for &'a str {
-    fn from(val: &'a Value) -> Self {
-        match val {
-            Value::String(s) => s,
-            _ => panic!("only String can convert into String, but {}", val.node_type()),
+impl<'a> TryFrom<&'a Value> for &'a bool {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_bool().ok_or(ConvertError::UnexpectedType { expected: "Bool", found: val.node_type() })
+    }
+}
+impl TryFrom<&Value> for bool {
+    type Error = ConvertError;
+    fn try_from(val: &Value) -> Result<Self, Self::Error> {
+        <&bool>::try_from(val).copied()
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = ConvertError;
+    fn try_from(mut val: Value) -> Result<Self, Self::Error> {
+        match &mut val {
+            Value::String(s) => Ok(std::mem::take(s)),
+            _ => Err(ConvertError::UnexpectedType { expected: "String", found: val.node_type() }),
         }
     }
 }
+impl<'a> TryFrom<&'a Value> for &'a str {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_string().ok_or(ConvertError::UnexpectedType { expected: "String", found: val.node_type() })
+    }
+}
+impl TryFrom<&Value> for String {
+    type Error = ConvertError;
+    fn try_from(val: &Value) -> Result<Self, Self::Error> {
+        <&str>::try_from(val).map(String::from)
+    }
+}
 
-impl From<Value> for i64 {
-    fn from(val: Value) -> Self {
+impl TryFrom<Value> for i64 {
+    type Error = ConvertError;
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
         match val {
-            Value::Integer(i) => i,
-            _ => panic!("only Integer can convert into i64, but {}", val.node_type()),
+            Value::Integer(i) => Ok(i),
+            _ => Err(ConvertError::UnexpectedType { expected: "Integer", found: val.node_type() }),
         }
     }
 }
-impl<'a> From<&'a Value> for &'a i64 {
-    fn from(val: &'a Value) -> Self {
+impl<'a> TryFrom<&'a Value> for &'a i64 {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_integer().ok_or(ConvertError::UnexpectedType { expected: "Integer", found: val.node_type() })
+    }
+}
+impl TryFrom<&Value> for i64 {
+    type Error = ConvertError;
+    fn try_from(val: &Value) -> Result<Self, Self::Error> {
+        <&i64>::try_from(val).copied()
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = ConvertError;
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
         match val {
-            Value::Integer(i) => i,
-            _ => panic!("only Integer can convert into i64, but {}", val.node_type()),
+            Value::Float(f) => Ok(f),
+            _ => Err(ConvertError::UnexpectedType { expected: "Float", found: val.node_type() }),
         }
     }
 }
+impl<'a> TryFrom<&'a Value> for &'a f64 {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_float().ok_or(ConvertError::UnexpectedType { expected: "Float", found: val.node_type() })
+    }
+}
+impl TryFrom<&Value> for f64 {
+    type Error = ConvertError;
+    fn try_from(val: &Value) -> Result<Self, Self::Error> {
+        <&f64>::try_from(val).copied()
+    }
+}
 
-impl From<Value> for f64 {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Float(f) => f,
-            _ => panic!("only Float can convert into f64, but {}", val.node_type()),
+impl TryFrom<Value> for RawNumber {
+    type Error = ConvertError;
+    fn try_from(mut val: Value) -> Result<Self, Self::Error> {
+        match &mut val {
+            Value::RawNumber(n) => Ok(std::mem::replace(n, raw_number(String::new()))),
+            _ => Err(ConvertError::UnexpectedType { expected: "RawNumber", found: val.node_type() }),
         }
     }
 }
-impl<'a> From<&'a Value> for &'a f64 {
-    fn from(val: &'a Value) -> Self {
+impl<'a> TryFrom<&'a Value> for &'a RawNumber {
+    type Error = ConvertError;
+    fn try_from(val: &'a Value) -> Result<Self, Self::Error> {
+        val.get_raw_number().ok_or(ConvertError::UnexpectedType { expected: "RawNumber", found: val.node_type() })
+    }
+}
+
+impl TryFrom<Value> for () {
+    type Error = ConvertError;
+    fn try_from(val: Value) -> Result<Self, Self::Error> {
+        <()>::try_from(&val)
+    }
+}
+impl TryFrom<&Value> for () {
+    type Error = ConvertError;
+    fn try_from(val: &Value) -> Result<Self, Self::Error> {
         match val {
-            Value::Float(f) => f,
-            _ => panic!("only Float can convert into f64, but {}", val.node_type()),
+            Value::Null => Ok(()),
+            _ => Err(ConvertError::UnexpectedType { expected: "Null", found: val.node_type() }),
         }
     }
 }
@@ -281,7 +607,7 @@ impl Value {
         matches!(self, Value::String(_))
     }
     pub fn is_number(&self) -> bool {
-        matches!(self, Value::Integer(_) | Value::Float(_))
+        matches!(self, Value::Integer(_) | Value::Float(_) | Value::RawNumber(_))
     }
     pub fn is_integer(&self) -> bool {
         matches!(self, Value::Integer(_))
@@ -289,16 +615,36 @@ impl Value {
     pub fn is_float(&self) -> bool {
         matches!(self, Value::Float(_))
     }
+    pub fn is_raw_number(&self) -> bool {
+        matches!(self, Value::RawNumber(_))
+    }
 }
 
-impl From<LinkedHashMap<String, Value>> for Value {
-    fn from(m: LinkedHashMap<String, Value>) -> Self {
-        Value::Object(m)
+// with `sorted-map` feature, `Map` is `BTreeMap` and it is converted by `From<BTreeMap<K, T>>`
+#[cfg(not(feature = "sorted-map"))]
+impl From<Map<Key, Value>> for Value {
+    fn from(m: Map<Key, Value>) -> Self {
+        Value::Object(Box::new(m))
+    }
+}
+impl<K: Into<Key>, T: Into<Value>, S> From<std::collections::HashMap<K, T, S>> for Value {
+    fn from(m: std::collections::HashMap<K, T, S>) -> Self {
+        m.into_iter().collect()
     }
 }
-impl From<Vec<Value>> for Value {
-    fn from(v: Vec<Value>) -> Self {
-        Value::Array(v)
+impl<K: Into<Key>, T: Into<Value>> From<std::collections::BTreeMap<K, T>> for Value {
+    fn from(m: std::collections::BTreeMap<K, T>) -> Self {
+        m.into_iter().collect()
+    }
+}
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(v: Vec<T>) -> Self {
+        Value::Array(v.into_iter().map(Into::into).collect())
+    }
+}
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(o: Option<T>) -> Self {
+        o.map_or(Value::Null, Into::into)
     }
 }
 impl From<bool> for Value {
@@ -321,20 +667,61 @@ impl From<&str> for Value {
         s.to_string().into()
     }
 }
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        c.to_string().into()
+    }
+}
 impl From<i64> for Value {
     fn from(i: i64) -> Self {
         Value::Integer(i)
     }
 }
+macro_rules! from_lossless_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Value {
+            fn from(i: $t) -> Self {
+                Value::Integer(i.into())
+            }
+        })*
+    };
+}
+from_lossless_integer!(i8, i16, i32, u8, u16, u32);
+/// `usize` that overflow `i64` is converted into [`Value::RawNumber`].
+impl From<usize> for Value {
+    fn from(u: usize) -> Self {
+        i64::try_from(u).map(Value::Integer).unwrap_or_else(|_| Value::RawNumber(raw_number(u.to_string())))
+    }
+}
 impl From<f64> for Value {
     fn from(f: f64) -> Self {
         Value::Float(f)
     }
 }
+impl From<Number> for Value {
+    fn from(n: Number) -> Self {
+        match n {
+            Number::Integer(i) => Value::Integer(i),
+            Number::Float(f) => Value::Float(f),
+        }
+    }
+}
+impl From<f32> for Value {
+    fn from(f: f32) -> Self {
+        Value::Float(f.into())
+    }
+}
 
-impl FromIterator<(String, Value)> for Value {
-    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
-        Value::Object(iter.into_iter().collect())
+/// collect key-value pairs into [`Value::Object`].
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let json: Value = [("a", 1), ("b", 2)].into_iter().collect();
+/// assert_eq!(json, Value::parse(r#"{"a": 1, "b": 2}"#).unwrap());
+/// ```
+impl<K: Into<Key>, V: Into<Value>> FromIterator<(K, V)> for Value {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Value::Object(Box::new(iter.into_iter().map(|(k, v)| (k.into(), v.into())).collect()))
     }
 }
 impl FromIterator<Value> for Value {
@@ -342,44 +729,310 @@ impl FromIterator<Value> for Value {
         Value::Array(iter.into_iter().collect())
     }
 }
+/// insert key-value pairs into [`Value::Object`]. existing key is overwritten.
+/// # panics
+/// if value is not `Object`.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let mut json = Value::parse(r#"{"a": 1}"#).unwrap();
+/// json.extend([("a", 2), ("b", 3)]);
+/// assert_eq!(json, Value::parse(r#"{"a": 2, "b": 3}"#).unwrap());
+/// ```
+impl<K: Into<Key>, V: Into<Value>> Extend<(K, V)> for Value {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        match self {
+            Value::Object(m) => m.extend(iter.into_iter().map(|(k, v)| (k.into(), v.into()))),
+            _ => panic!("only Object can be extended with key-value pairs, but {}", self.node_type()),
+        }
+    }
+}
+/// append elements into [`Value::Array`].
+/// # panics
+/// if value is not `Array`.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let mut json = Value::parse(r#"[1]"#).unwrap();
+/// json.extend(vec![Value::from(2), Value::from("three")]);
+/// assert_eq!(json, Value::parse(r#"[1, 2, "three"]"#).unwrap());
+/// ```
+impl Extend<Value> for Value {
+    fn extend<I: IntoIterator<Item = Value>>(&mut self, iter: I) {
+        match self {
+            Value::Array(v) => v.extend(iter),
+            _ => panic!("only Array can be extended with elements, but {}", self.node_type()),
+        }
+    }
+}
+/// consume [`Value::Array`] and iterate its owned elements. see [`Value::into_items`] for [`Value::Object`].
+/// # panics
+/// if value is not `Array`.
+impl IntoIterator for Value {
+    type Item = Value;
+    type IntoIter = std::vec::IntoIter<Value>;
+    fn into_iter(mut self) -> Self::IntoIter {
+        match &mut self {
+            Value::Array(v) => std::mem::take(v).into_iter(),
+            _ => panic!("only Array can iterate, but {}", self.node_type()),
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::Value;
+    use super::*;
 
     #[test]
     fn test_into_bool_json() {
         let tru_ast = Value::Bool(true);
-        let t: &bool = (&tru_ast).into();
+        let t: &bool = (&tru_ast).try_into().unwrap();
         assert_eq!(t, &true);
-        let t: bool = tru_ast.into();
+        let t: bool = tru_ast.try_into().unwrap();
         assert!(t);
     }
 
     #[test]
     fn test_into_string_json() {
         let string_ast = Value::String("rust".into());
-        let s: &str = (&string_ast).into();
+        let s: &str = (&string_ast).try_into().unwrap();
         assert_eq!(s, "rust");
-        let s: String = string_ast.into();
+        let s: String = string_ast.try_into().unwrap();
         assert_eq!(s, "rust".to_string());
     }
 
     #[test]
     fn test_into_integer_json() {
         let hundred_ast = Value::Integer(100);
-        let i: &i64 = (&hundred_ast).into();
+        let i: &i64 = (&hundred_ast).try_into().unwrap();
         assert_eq!(i, &100);
-        let i: i64 = hundred_ast.into();
+        let i: i64 = hundred_ast.try_into().unwrap();
         assert_eq!(i, 100);
     }
 
     #[test]
     fn test_into_float_json() {
         let quarter_ast = Value::Float(0.25);
-        let f: &f64 = (&quarter_ast).into();
+        let f: &f64 = (&quarter_ast).try_into().unwrap();
         assert_eq!(f, &0.25);
-        let f: f64 = quarter_ast.into();
+        let f: f64 = quarter_ast.try_into().unwrap();
         assert_eq!(f, 0.25);
     }
+
+    #[test]
+    fn test_into_unexpected_type_json() {
+        let array_ast = Value::Array(vec![Value::Null]);
+        let err = <&Map<Key, Value>>::try_from(&array_ast).unwrap_err();
+        assert_eq!(err, ConvertError::UnexpectedType { expected: "Object", found: "Array" });
+        assert!(err.to_string().contains("Object"));
+        assert!(err.to_string().contains("Array"));
+        assert_eq!(
+            String::try_from(Value::Integer(1)),
+            Err(ConvertError::UnexpectedType { expected: "String", found: "Integer" })
+        );
+        assert_eq!(array_ast.expect_array(), vec![Value::Null]);
+    }
+
+    #[test]
+    fn test_from_rust_object() {
+        assert_eq!(Value::from(1_i8), Value::Integer(1));
+        assert_eq!(Value::from(u32::MAX), Value::Integer(u32::MAX.into()));
+        assert_eq!(Value::from(3_usize), Value::Integer(3));
+        if let Ok(max) = usize::try_from(u64::MAX) {
+            assert_eq!(Value::from(max).raw_number().as_str(), "18446744073709551615");
+        }
+        assert_eq!(Value::from(0.5_f32), Value::Float(0.5));
+        assert_eq!(Value::from('c'), Value::from("c"));
+        assert_eq!(Value::from(Some(1)), Value::Integer(1));
+        assert_eq!(Value::from(None::<bool>), Value::Null);
+        assert_eq!(Value::from(vec![Some("a"), None]), Value::parse(r#"["a", null]"#).unwrap());
+        assert_eq!(Value::from(vec![vec![1_u8], vec![]]), Value::parse("[[1], []]").unwrap());
+
+        let expected = Value::parse(r#"{"a": [1, 2], "b": []}"#).unwrap();
+        let hash_map: std::collections::HashMap<_, _> = [("a", vec![1, 2]), ("b", vec![])].into_iter().collect();
+        let from_hash_map = Value::from(hash_map);
+        assert_eq!(
+            (from_hash_map.len(), &from_hash_map["a"], &from_hash_map["b"]),
+            (2, &expected["a"], &expected["b"])
+        );
+        let btree_map: std::collections::BTreeMap<_, _> =
+            [("a".to_string(), vec![1, 2]), ("b".to_string(), vec![])].into_iter().collect();
+        assert_eq!(Value::from(btree_map), expected);
+        let collected: Value =
+            [("a".to_string(), Value::from(vec![1, 2])), ("b".to_string(), Value::Array(vec![]))].into_iter().collect();
+        assert_eq!(collected, expected);
+        assert_eq!(expected.items().map(|(k, v)| (k.clone(), v.clone())).collect::<Value>(), expected);
+    }
+
+    #[test]
+    fn test_extend_json() {
+        let mut object = Value::Object(Default::default());
+        for i in 0..3 {
+            object.extend([(i.to_string(), i)]);
+        }
+        object.extend(Some(("1", "one")));
+        assert_eq!((object.len(), &object["0"], &object["1"], &object["2"]), (3, &0.into(), &"one".into(), &2.into()));
+
+        let mut array = Value::Array(Vec::new());
+        for i in 0..3 {
+            array.extend((0..i).map(Value::from));
+        }
+        assert_eq!(array, Value::parse("[0, 0, 1]").unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_unexpected_type_json() {
+        Value::Array(Vec::new()).extend([("a", 1)]);
+    }
+
+    #[test]
+    fn test_into_inner_json() {
+        let json = Value::parse(r#"{"a": [1, "b"], "c": "d"}"#).unwrap();
+        let mut map = json.clone().into_object().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.remove("c").unwrap().into_string(), Ok("d".to_string()));
+        assert_eq!(map.remove("a").unwrap().into_array(), Ok(vec![Value::Integer(1), Value::from("b")]));
+
+        assert_eq!(json.clone().into_array(), Err(json.clone()));
+        assert_eq!(json["a"].clone().into_string(), Err(json["a"].clone()));
+        assert_eq!(Value::Null.into_object(), Err(Value::Null));
+    }
+
+    #[test]
+    fn test_try_from_ref_json() {
+        let json = Value::parse(r#"{"b": true, "s": "str", "i": 1, "f": 0.5, "n": null}"#).unwrap();
+        assert_eq!(bool::try_from(&json["b"]), Ok(true));
+        assert_eq!(String::try_from(&json["s"]), Ok("str".to_string()));
+        assert_eq!(i64::try_from(&json["i"]), Ok(1));
+        assert_eq!(f64::try_from(&json["f"]), Ok(0.5));
+        assert_eq!(<()>::try_from(&json["n"]), Ok(()));
+        assert_eq!(
+            i64::try_from(&json["f"]),
+            Err(ConvertError::UnexpectedType { expected: "Integer", found: "Float" })
+        );
+        assert_eq!(<()>::try_from(json), Err(ConvertError::UnexpectedType { expected: "Null", found: "Object" }));
+
+        let raw = Value::parse_with("1.0", crate::ParserOptions::new().lossless_number(true)).unwrap();
+        assert_eq!(<&RawNumber>::try_from(&raw).map(RawNumber::as_str), Ok("1.0"));
+        assert_eq!(RawNumber::try_from(raw).map(String::from), Ok("1.0".to_string()));
+        assert!(RawNumber::try_from(Value::Float(1.0)).is_err());
+    }
+
+    #[test]
+    fn test_numeric_coercion_json() {
+        for (json, i, u, f) in [
+            ("2", Some(2), Some(2), Some(2.)),
+            ("2.0", Some(2), Some(2), Some(2.)),
+            ("-3", Some(-3), None, Some(-3.)),
+            ("-0.0", Some(0), Some(0), Some(-0.)),
+            ("1e2", Some(100), Some(100), Some(100.)),
+            ("0.5", None, None, Some(0.5)),
+            ("9007199254740993", Some(9007199254740993), Some(9007199254740993), None),
+            ("9223372036854775807", Some(i64::MAX), Some(i64::MAX as u64), None),
+            ("1e19", None, Some(10_000_000_000_000_000_000), Some(1e19)),
+            ("1e300", None, None, Some(1e300)),
+            (r#""2""#, None, None, None),
+            ("true", None, None, None),
+        ] {
+            let value = Value::parse(json).unwrap();
+            assert_eq!((value.as_i64(), value.as_u64(), value.as_f64()), (i, u, f), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_raw_numeric_coercion_json() {
+        let options = crate::ParserOptions::new().lossless_number(true);
+        for (json, i, u, f) in [
+            ("2", Some(2), Some(2), Some(2.)),
+            ("2.0", Some(2), Some(2), Some(2.)),
+            ("18446744073709551615", None, Some(u64::MAX), None),
+            ("18446744073709551616", None, None, Some(18446744073709551616.)),
+            ("-1E-400", Some(0), Some(0), Some(-0.)),
+            ("1e400", None, None, None),
+        ] {
+            let value = Value::parse_with(json, options.clone()).unwrap();
+            assert!(value.is_raw_number(), "{json}");
+            assert_eq!((value.as_i64(), value.as_u64(), value.as_f64()), (i, u, f), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_keys_values_json() {
+        let mut json = Value::parse(r#"{"a": 1, "b": [2], "c": {"d": 3}}"#).unwrap();
+        let items: Vec<_> = json.items().collect();
+        assert_eq!(json.keys().collect::<Vec<_>>(), items.iter().map(|&(k, _)| k).collect::<Vec<_>>());
+        assert_eq!(json.values().collect::<Vec<_>>(), items.iter().map(|&(_, v)| v).collect::<Vec<_>>());
+
+        for v in json.values_mut() {
+            *v = Value::Null;
+        }
+        assert_eq!(json, Value::parse(r#"{"a": null, "b": null, "c": null}"#).unwrap());
+        assert_eq!(Value::parse("{}").unwrap().keys().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_keys_unexpected_type_json() {
+        Value::parse("[1, 2]").unwrap().keys().count();
+    }
+
+    #[test]
+    fn test_to_text_json() {
+        let options = crate::ParserOptions::new().lossless_number(true);
+        let json = Value::parse_with(r#"{"s": "\u3042", "raw": 1.50, "obj": {"k": ""}}"#, options).unwrap();
+        assert_eq!(json["s"].as_str(), Some("あ"));
+        assert_eq!(json["s"].to_text(), "あ");
+        assert_eq!(json["raw"].as_str(), None);
+        assert_eq!(json["raw"].to_text(), "1.50");
+        assert_eq!(json["obj"].to_text(), r#"{"k":""}"#);
+        assert_eq!(json["obj"]["k"].to_text(), "");
+    }
+
+    #[test]
+    fn test_len_json() {
+        for (json, len) in [
+            ("{}", 0),
+            (r#"{"a": 1, "b": [2, 3]}"#, 2),
+            ("[]", 0),
+            ("[[1, 2], 3, 4]", 3),
+            (r#""""#, 0),
+            (r#""aあ😀""#, 3),
+        ] {
+            let value = Value::parse(json).unwrap();
+            assert_eq!((value.len(), value.is_empty()), (len, len == 0), "{json}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_len_unexpected_type_json() {
+        Value::Integer(1).len();
+    }
+
+    #[test]
+    fn test_into_iter_json() {
+        let json = Value::parse(r#"{"a": [1, [2], {"b": 3}], "c": null}"#).unwrap();
+        let mut items: Vec<_> = json.into_items().collect();
+        items.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+        assert_eq!(items.iter().map(|(k, _)| &k[..]).collect::<Vec<_>>(), ["a", "c"]);
+        let (_, a) = items.swap_remove(0);
+        assert_eq!(
+            a.into_iter().collect::<Vec<_>>(),
+            vec![Value::Integer(1), Value::parse("[2]").unwrap(), Value::parse(r#"{"b": 3}"#).unwrap()]
+        );
+        assert_eq!(Value::Array(Vec::new()).into_iter().count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_into_iter_unexpected_type_json() {
+        for _ in Value::parse("{}").unwrap() {}
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_expect_unexpected_type_json() {
+        Value::Float(0.5).expect_integer();
+    }
 }
@@ -1,11 +1,27 @@
-use super::Value;
-use crate::syntax::{error::StructureError, lexer::Lexer, parser::Parser, rawjson::RawJson};
+use super::{
+    compress::Compression,
+    format::FormatOptions,
+    index_path::JsonPath,
+    into::ConvertError,
+    serializer::{Serializer, StandardFormatter},
+    Value,
+};
+use crate::syntax::{
+    error::{Error, StructureError},
+    lexer::Lexer,
+    parser::{ParseReport, Parser, ParserOptions},
+    rawjson::RawJson,
+    validate,
+};
 use std::{
     fs::File,
     io::{BufRead, BufReader, BufWriter, Read, Write},
     path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
 };
 
+pub use super::serializer::JsonFormatter;
+
 impl Value {
     /// parse string like raw json into ast.
     /// # examples
@@ -25,19 +41,127 @@ impl Value {
     /// assert_eq!(json, json2);
     /// assert_eq!(json1, json2);
     /// ```
-    pub fn parse<J: Into<RawJson>>(j: J) -> anyhow::Result<Value> {
+    pub fn parse<J: Into<RawJson>>(j: J) -> Result<Value, Error> {
+        Value::parse_with(j, ParserOptions::default())
+    }
+    /// parse string like raw json into ast with given options. see [`ParserOptions`] also.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let raw = "{ \"key\": \"raw\u{0}null\" }";
+    /// let err = Value::parse_with(raw, ParserOptions::new().strict(true)).unwrap_err();
+    /// assert!(err.to_string().contains("control character"));
+    /// ```
+    pub fn parse_with<J: Into<RawJson>>(j: J, options: ParserOptions) -> Result<Value, Error> {
+        Ok(Value::parse_report(j, options)?.value)
+    }
+    /// parse string like raw json into ast with given options, and collect warnings of lenient parsing.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let raw = r#"{1: "one", "2": "two"}"#;
+    /// assert!(Value::parse(raw).is_err());
+    /// let report = Value::parse_report(raw, ParserOptions::new().numeric_keys(true)).unwrap();
+    /// assert_eq!(report.value["1"], Value::from("one"));
+    /// assert_eq!(report.warnings.len(), 1);
+    /// ```
+    pub fn parse_report<J: Into<RawJson>>(j: J, options: ParserOptions) -> Result<ParseReport, Error> {
+        Value::parse_by(j, Parser::with_options(options))
+    }
+    /// parse string like raw json into ast, and transform each completed node by `reviver` like `JSON.parse` of
+    /// javascript. `reviver` is called with path and value of the node, children first and root last.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let raw = r#"{ "name": " dyson ", "keyword": [" json "] }"#;
+    /// let json = Value::parse_with_reviver(raw, |_path, value| match &value {
+    ///     Value::String(s) => Value::String(s.trim().to_string()),
+    ///     _ => value,
+    /// })
+    /// .unwrap();
+    /// assert_eq!(json, Value::parse(r#"{ "name": "dyson", "keyword": ["json"] }"#).unwrap());
+    /// ```
+    pub fn parse_with_reviver<J, F>(j: J, reviver: F) -> Result<Value, Error>
+    where
+        J: Into<RawJson>,
+        F: FnMut(&JsonPath, Value) -> Value,
+    {
+        Ok(Value::parse_by(j, Parser::new().reviver(reviver))?.value)
+    }
+    fn parse_by<J: Into<RawJson>>(j: J, parser: Parser) -> Result<ParseReport, Error> {
         let json = j.into();
-        let (mut lexer, parser) = (Lexer::new(&json), Parser::new());
-        let result = parser.parse_value(&mut lexer);
-        if result.is_ok() {
-            if let Some(&(p, _)) = lexer.skip_whitespace() {
-                let eof = lexer.json.eof();
-                return Err(StructureError::FoundSurplus { start: p, end: eof })?;
-            }
+        Value::parse_all(Lexer::new(&json), parser)
+    }
+    /// parse whole input of `lexer`. if reading input is stopped by io error, the error is returned.
+    fn parse_all(mut lexer: Lexer, parser: Parser) -> Result<ParseReport, Error> {
+        let parsed = parser.parse_value(&mut lexer).and_then(|value| match lexer.skip_whitespace() {
+            Some(&(p, _)) => Err(StructureError::FoundSurplus { start: p, end: lexer.eof() })?,
+            None => Ok(value),
+        });
+        if let Some(e) = lexer.take_error() {
+            return Err(e.into());
         }
-        result
+        let value = parsed.map_err(|e| Error::located(e, |pos| lexer.byte_offset(pos)))?;
+        Ok(ParseReport { value, warnings: parser.take_warnings() })
+    }
+    /// parse json from chars of caller-provided iterator, without collecting them into [`RawJson`] or [`String`].
+    /// byte range of error span is in UTF-8 encoding of the chars.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let chars = r#"{"key": [1, 2]}"#.chars(); // e.g. chars from decrypting reader
+    /// assert_eq!(Value::parse_chars(chars).unwrap(), Value::parse(r#"{"key": [1, 2]}"#).unwrap());
+    /// ```
+    pub fn parse_chars<I: IntoIterator<Item = char>>(chars: I) -> Result<Value, Error> {
+        Ok(Value::parse_all(Lexer::from_chars(chars.into_iter()), Parser::new())?.value)
+    }
+    /// parse json from UTF-8 bytes of caller-provided iterator such as [`Read::bytes`], without collecting them.
+    /// io error of the iterator and invalid UTF-8 is returned as [`Error::Io`].
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// use std::io::Read;
+    /// let reader = r#"{"key": [1, 2]}"#.as_bytes();
+    /// assert_eq!(Value::parse_bytes(reader.bytes()).unwrap(), Value::parse(r#"{"key": [1, 2]}"#).unwrap());
+    /// ```
+    pub fn parse_bytes<I: IntoIterator<Item = std::io::Result<u8>>>(bytes: I) -> Result<Value, Error> {
+        Ok(Value::parse_all(Lexer::from_bytes(bytes.into_iter()), Parser::new())?.value)
+    }
+    /// parse one json value at the start of `s`, and return it with the number of consumed bytes.
+    /// trailing content after the value is not error, so json embedded in other text can be parsed.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let line = r#"{"id": 1} trailing protocol data"#;
+    /// let (json, consumed) = Value::parse_prefix(line).unwrap();
+    /// assert_eq!(json["id"], Value::Integer(1));
+    /// assert_eq!(&line[consumed..], " trailing protocol data");
+    /// ```
+    pub fn parse_prefix(s: &str) -> Result<(Value, usize), Error> {
+        let json = RawJson::from(s);
+        let (mut lexer, parser) = (Lexer::new(&json), Parser::new());
+        let value = parser.parse_value(&mut lexer).map_err(|e| Error::located(e, |pos| lexer.byte_offset(pos)))?;
+        let consumed = lexer.peek().map_or(s.len(), |&(pos, _)| lexer.byte_offset(pos));
+        Ok((value, consumed))
+    }
+    /// parse string like raw json into ast, but materialize only values on or under `paths`.
+    /// other values are skipped without allocation, see [`ParserOptions::projection`] also.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, JsonPath, Value};
+    /// let raw = r#"{ "id": 1, "payload": { "large": [1, 2, 3] }, "tags": ["a", "b"] }"#;
+    /// let id: JsonPath = vec![JsonIndexer::ObjInd("id".to_string())].into_iter().collect();
+    /// let tag: JsonPath = vec![JsonIndexer::ObjInd("tags".to_string()), JsonIndexer::ArrInd(1)].into_iter().collect();
+    /// let json = Value::parse_projection(raw, &[id, tag]).unwrap();
+    /// assert_eq!(json.to_string(), r#"{"id":1,"tags":[null,"b"]}"#);
+    /// ```
+    pub fn parse_projection<J: Into<RawJson>>(j: J, paths: &[JsonPath]) -> Result<Value, Error> {
+        Value::parse_with(j, ParserOptions::new().projection(paths))
     }
     /// parse file like raw json into ast. see [`Value::load`] also.
+    /// input is read and lexed as stream through [`BufReader`], it is neither collected into one buffer nor split into
+    /// lines. encoding of input is detected by BOM or by null bytes of its first 4 bytes, and UTF-16 or UTF-32 input
+    /// is transcoded char by char. byte range of error span is in the transcoded UTF-8 text without BOM.
     /// # examples
     /// ```no_run
     /// use dyson::Value;
@@ -47,9 +171,21 @@ impl Value {
     ///
     /// println!("{json}");
     /// ```
-    pub fn read<R: Read>(r: R) -> anyhow::Result<Value> {
-        let json: RawJson = BufReader::new(r).lines().map(|l| l.expect("could not read line")).collect();
-        Value::parse(json)
+    pub fn read<R: Read>(r: R) -> Result<Value, Error> {
+        let lexer = Lexer::from_encoded_bytes(BufReader::new(r).bytes())?;
+        Ok(Value::parse_all(lexer, Parser::new())?.value)
+    }
+    /// check syntax of raw json read from `r` without building ast. error message has position of the error.
+    /// input is checked as stream, so memory usage is bounded by nesting depth, not by input size.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// assert!(Value::validate(r#"{"key": [1, 2, {"three": 3}]}"#.as_bytes()).is_ok());
+    /// let err = Value::validate(r#"{"key": [1, 2, {"three": 3]}"#.as_bytes()).unwrap_err();
+    /// assert!(err.to_string().contains("line 1 (col 27)"));
+    /// ```
+    pub fn validate<R: Read>(r: R) -> Result<(), Error> {
+        validate::validate(BufReader::new(r))
     }
     /// parse raw json file specified by path into ast. see [`Value::parse`] also.
     /// # examples
@@ -67,12 +203,17 @@ impl Value {
     /// println!("{json}");
     /// // {"language":"rust","version":0.1,"keyword":["rust","json","parser"],"notation":"json"}
     /// ```
-    pub fn load<P: AsRef<Path>>(p: P) -> anyhow::Result<Value> {
-        let file = File::open(p)?;
-        Self::read(file)
+    /// gzip or zstd compressed file is detected by its magic bytes and decompressed, with `gzip` or `zstd` feature.
+    pub fn load<P: AsRef<Path>>(p: P) -> Result<Value, Error> {
+        let mut reader = BufReader::new(File::open(p)?);
+        match Compression::detect(reader.fill_buf()?) {
+            Some(compression) => Self::read(compression.decoder(reader)?),
+            None => Self::read(reader),
+        }
     }
 
     /// write ast to file. written string has proper indent. see [`Value::dump`] also.
+    /// json is written incrementally, whole string is not built in memory.
     /// # examples
     /// ```no_run
     /// use dyson::Value;
@@ -83,8 +224,8 @@ impl Value {
     /// let file = File::create("path/to/write.json").unwrap();
     /// json.write(file).unwrap();
     /// ```
-    pub fn write<W: Write>(&self, w: W) -> anyhow::Result<usize> {
-        Ok(BufWriter::new(w).write(Indent::<1>::format(self).as_bytes())?)
+    pub fn write<W: Write>(&self, w: W) -> Result<usize, Error> {
+        self.write_with::<W, Indent<1>>(w)
     }
     /// write ast to file specified by path. written string has proper indent. see [`Value::stringify`] also.
     /// # examples
@@ -101,13 +242,82 @@ impl Value {
     /// use std::path::PathBuf;
     /// json.dump(PathBuf::from("path").join("to").join("write.json")).unwrap();
     /// ```
-    pub fn dump<P: AsRef<Path>>(&self, p: P) -> anyhow::Result<usize> {
-        let file = File::create(p)?;
-        self.write(file)
+    /// path with `.gz` or `.zst` extension is compressed, with `gzip` or `zstd` feature.
+    pub fn dump<P: AsRef<Path>>(&self, p: P) -> Result<usize, Error> {
+        self.dump_with::<P, Indent<1>>(p)
     }
     /// write ast to file with indent. see [`Value::write`] and [`Value::dump_with`] also.
-    pub fn write_with<W: Write, F: JsonFormatter>(&self, w: W) -> anyhow::Result<usize> {
-        Ok(BufWriter::new(w).write(F::format(self).as_bytes())?)
+    pub fn write_with<W: Write, F: JsonFormatter + Default>(&self, w: W) -> Result<usize, Error> {
+        self.write_with_formatter(w, F::default())
+    }
+    /// write ast to file formatted by `formatter`. see [`JsonFormatter`] also.
+    pub fn write_with_formatter<W: Write, F: JsonFormatter>(&self, w: W, formatter: F) -> Result<usize, Error> {
+        let mut serializer = Serializer::with_formatter(BufWriter::new(w), formatter);
+        serializer.value(self)?;
+        serializer.flush()?;
+        Ok(serializer.written())
+    }
+    /// write ast to file with given options, such as indent width and indent char. see [`FormatOptions`] also.
+    /// # examples
+    /// ```
+    /// use dyson::{FormatOptions, Value};
+    /// let json = Value::parse(r#"{"key": [1, 2]}"#).unwrap();
+    /// let mut buf = Vec::new();
+    /// json.write_with_options(&mut buf, &FormatOptions::new().indent(1).indent_char('\t')).unwrap();
+    /// assert_eq!(String::from_utf8(buf).unwrap(), "{\n\t\"key\": [\n\t\t1,\n\t\t2\n\t]\n}");
+    /// ```
+    pub fn write_with_options<W: Write>(&self, w: W, options: &FormatOptions) -> Result<usize, Error> {
+        let mut serializer = Serializer::pretty(BufWriter::new(w), options.clone());
+        serializer.value(self)?;
+        serializer.flush()?;
+        Ok(serializer.written())
+    }
+    /// write ast into [`std::fmt::Write`] such as preallocated [`String`]. written string has proper indent like
+    /// [`Value::write`]. json is written incrementally, so `w` can be reused as buffer without other allocation.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"key": [1, 2]}"#).unwrap();
+    /// let mut buf = String::with_capacity(64);
+    /// assert_eq!(json.write_fmt_to(&mut buf).unwrap(), buf.len());
+    /// assert_eq!(buf, json.stringify());
+    /// ```
+    pub fn write_fmt_to<W: std::fmt::Write + ?Sized>(&self, w: &mut W) -> Result<usize, Error> {
+        let mut serializer = Serializer::with_formatter(FmtWriter(w), Indent::<1>);
+        serializer.value(self)?;
+        Ok(serializer.written())
+    }
+    /// write each element of top-level array as minified json line, that is JSON Lines (NDJSON).
+    /// every line, including the last one, is terminated by `\n`. if ast is not array, return error.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"[{"id": 1}, {"id": 2}, "three"]"#).unwrap();
+    /// let mut buf = Vec::new();
+    /// assert_eq!(json.write_ndjson(&mut buf).unwrap(), 26);
+    /// assert_eq!(String::from_utf8(buf).unwrap(), "{\"id\":1}\n{\"id\":2}\n\"three\"\n");
+    /// ```
+    pub fn write_ndjson<W: Write>(&self, w: W) -> Result<usize, Error> {
+        let array =
+            self.get_array().ok_or(ConvertError::UnexpectedType { expected: "Array", found: self.node_type() })?;
+        let (mut writer, mut written) = (BufWriter::new(w), 0);
+        for element in array {
+            let mut serializer = Serializer::new(&mut writer, FormatOptions::default());
+            serializer.value(element)?;
+            written += serializer.written();
+            writer.write_all(b"\n")?;
+            written += 1;
+        }
+        writer.flush()?;
+        Ok(written)
+    }
+    /// write ast to file specified by path with given options. see [`Value::write_with_options`] also.
+    pub fn dump_with_options<P: AsRef<Path>>(&self, p: P, options: &FormatOptions) -> Result<usize, Error> {
+        let file = File::create(p.as_ref())?;
+        match Compression::from_extension(p.as_ref()) {
+            Some(compression) => compression.compress(file, |w| self.write_with_options(w, options)),
+            None => self.write_with_options(file, options),
+        }
     }
     /// write ast to file specified by path with indent. see [`Indent`] also
     /// # examples
@@ -147,9 +357,69 @@ impl Value {
     /// json.dump_with::<_, Indent<2>>("path/to/write.json");
     /// ```
     /// see `Value::to_string` and `Value::stringify` also.
-    pub fn dump_with<P: AsRef<Path>, F: JsonFormatter>(&self, p: P) -> anyhow::Result<usize> {
-        let file = File::create(p)?;
-        self.write_with::<File, F>(file)
+    pub fn dump_with<P: AsRef<Path>, F: JsonFormatter + Default>(&self, p: P) -> Result<usize, Error> {
+        self.dump_into::<F>(&File::create(p.as_ref())?, p.as_ref())
+    }
+    /// write ast to file specified by path like [`Value::dump`], but create missing parent directories of the path.
+    /// # examples
+    /// ```no_run
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"report": [1, 2, 3]}"#).unwrap();
+    /// json.dump_create_dirs("out/2024-03-13/report.json").unwrap();
+    /// ```
+    pub fn dump_create_dirs<P: AsRef<Path>>(&self, p: P) -> Result<usize, Error> {
+        if let Some(dir) = p.as_ref().parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        self.dump(p)
+    }
+    /// write ast to file specified by path atomically. written string has proper indent like [`Value::dump`].
+    /// ast is written into temporary file in the same directory, synced, and renamed into the path.
+    /// so if writing fails or process crashes, the file specified by path is never left half-written.
+    /// if the path already exists, its permissions are copied to the temporary file before renaming.
+    /// # examples
+    /// ```no_run
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"config": {"retry": 3}}"#).unwrap();
+    /// json.dump_atomic("path/to/config.json").unwrap();
+    /// ```
+    pub fn dump_atomic<P: AsRef<Path>>(&self, p: P) -> Result<usize, Error> {
+        static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+        let path = p.as_ref();
+        let name = path.file_name().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{} is not file path", path.display()))
+        })?;
+        let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+        let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let temp = dir.join(format!(".{}.{}.{sequence}.tmp", name.to_string_lossy(), std::process::id()));
+
+        let written = || -> Result<usize, Error> {
+            let file = File::create(&temp)?;
+            let written = self.dump_into::<Indent<1>>(&file, path)?;
+            file.sync_all()?;
+            drop(file);
+            match std::fs::metadata(path) {
+                Ok(metadata) => std::fs::set_permissions(&temp, metadata.permissions())?, // keep mode of target
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+                Err(e) => Err(e)?,
+            }
+            std::fs::rename(&temp, path)?;
+            Ok(written)
+        }();
+        if written.is_err() {
+            std::fs::remove_file(&temp).ok();
+        } else {
+            #[cfg(unix)]
+            File::open(dir)?.sync_all()?; // persist rename
+        }
+        written
+    }
+    /// write ast into `file` with formatter `F`, compressed by extension of `p`.
+    fn dump_into<F: JsonFormatter + Default>(&self, file: &File, p: &Path) -> Result<usize, Error> {
+        match Compression::from_extension(p) {
+            Some(compression) => compression.compress(file, |w| self.write_with::<_, F>(w)),
+            None => self.write_with::<_, F>(file),
+        }
     }
 }
 
@@ -161,18 +431,43 @@ impl Value {
 ///
 /// default is `Indent<1>`, so `Indent` mean `Indent<1>`.
 /// see [`Value::write_with`] and [`Value::dump_with`] also.
+/// for other indent width or indent char, see [`Value::write_with_options`] and [`FormatOptions`].
+/// for other output style, implement [`JsonFormatter`] and see [`Value::write_with_formatter`].
+#[derive(Debug, Clone, Copy, Default)]
 pub struct Indent<const N: u8 = 1>;
-pub trait JsonFormatter {
-    fn format(value: &Value) -> String;
-}
-impl JsonFormatter for Indent<0> {
-    fn format(value: &Value) -> String {
-        value.to_string()
+impl JsonFormatter for Indent<0> {}
+impl Indent<1> {
+    /// `Indent<1>` is pretty [`StandardFormatter`] with default options. it has no state because `inline_leaves` is
+    /// not set, so new one can be used for each token.
+    fn pretty() -> StandardFormatter {
+        StandardFormatter::pretty(FormatOptions::default())
     }
 }
 impl JsonFormatter for Indent<1> {
-    fn format(value: &Value) -> String {
-        value.stringify()
+    fn end_object<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, empty: bool) -> std::io::Result<()> {
+        Self::pretty().end_object(w, depth, empty)
+    }
+    fn end_array<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, empty: bool) -> std::io::Result<()> {
+        Self::pretty().end_array(w, depth, empty)
+    }
+    fn begin_element<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, first: bool) -> std::io::Result<()> {
+        Self::pretty().begin_element(w, depth, first)
+    }
+    fn key<W: ?Sized + Write>(&mut self, w: &mut W, key: &str) -> std::io::Result<()> {
+        Self::pretty().key(w, key)
+    }
+}
+
+/// [`FmtWriter`] adapt [`std::fmt::Write`] into [`Write`]. serializer write only whole UTF-8 string at once.
+struct FmtWriter<'a, W: ?Sized>(&'a mut W);
+impl<W: std::fmt::Write + ?Sized> Write for FmtWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let s = std::str::from_utf8(buf).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.0.write_str(s).map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
@@ -201,6 +496,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_prefix() {
+        let (json, consumed) = Value::parse_prefix("[1, \"あ\"]\r\nnext").unwrap();
+        assert_eq!(json, Value::Array(vec![1.into(), "あ".into()]));
+        assert_eq!(consumed, 10);
+        let (json, consumed) = Value::parse_prefix("{\r\n\"k\": \"v\"\r\n}, {}").unwrap();
+        assert_eq!(json["k"], Value::from("v"));
+        assert_eq!(consumed, 14);
+        assert_eq!(Value::parse_prefix("123abc").unwrap(), (Value::Integer(123), 3));
+        assert_eq!(Value::parse_prefix("true").unwrap(), (Value::Bool(true), 4));
+        assert_eq!(Value::parse_prefix("  null\n").unwrap(), (Value::Null, 6));
+        assert!(Value::parse_prefix("[1, 2").is_err());
+        assert!(Value::parse_prefix(" x").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let json = format!("[{}]", vec![r#"{"key": ["value", 1, 2.5, true, null]}"#; 1000].join(","));
+        assert!(Value::validate(json.as_bytes()).is_ok());
+        for invalid in ["", "[1, 2", r#"{"key" 1}"#, "[1,]", "tru", "[1] 2", r#""\x""#] {
+            assert!(Value::validate(invalid.as_bytes()).is_err(), "{invalid:?} must be invalid");
+        }
+    }
+
     #[test]
     fn test_file_io_json() {
         let json: RawJson = [
@@ -246,6 +565,169 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_dump_atomic() {
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            let path = dir.path().join("config.json");
+            std::fs::write(&path, "old content")?;
+            let json = Value::parse(r#"{"retry": 3, "hosts": ["a", "b"]}"#)?;
+            let written = json.dump_atomic(&path)?;
+            assert_eq!(std::fs::read_to_string(&path)?, json.stringify());
+            assert_eq!(written, json.stringify().len());
+            assert_eq!(std::fs::read_dir(dir.path())?.count(), 1); // temporary file is renamed
+
+            let missing = dir.path().join("missing").join("config.json");
+            assert!(matches!(json.dump_atomic(&missing), Err(Error::Io(_))));
+            assert!(json.dump_atomic(dir.path().join("..")).is_err());
+            assert_eq!(std::fs::read_dir(dir.path())?.count(), 1);
+            Ok(())
+        };
+        result().unwrap();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_dump_atomic_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            let path = dir.path().join("secret.json");
+            std::fs::write(&path, "old content")?;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+            Value::parse(r#"{"token": "xxx"}"#)?.dump_atomic(&path)?;
+            assert_eq!(std::fs::metadata(&path)?.permissions().mode() & 0o777, 0o600);
+
+            let created = dir.path().join("created.json");
+            Value::parse(r#"{"token": "xxx"}"#)?.dump_atomic(&created)?;
+            let file = File::create(dir.path().join("reference.json"))?;
+            assert_eq!(std::fs::metadata(&created)?.permissions(), file.metadata()?.permissions());
+            Ok(())
+        };
+        result().unwrap();
+    }
+
+    #[test]
+    fn test_dump_create_dirs() {
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            let path = dir.path().join("out").join("deep").join("report.json");
+            let json = Value::parse(r#"{"report": [1, 2, 3]}"#)?;
+            assert!(matches!(json.dump(&path), Err(Error::Io(_))));
+            json.dump_create_dirs(&path)?;
+            assert_eq!(Value::load(&path)?, json);
+            json.dump_create_dirs(&path)?; // existing directories are ok
+            Ok(())
+        };
+        result().unwrap();
+    }
+
+    #[test]
+    fn test_load_compressed() {
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            let json = Value::parse(r#"{"key": [1, "two", 3.5]}"#)?;
+            for (name, enabled) in [("a.json.gz", cfg!(feature = "gzip")), ("a.json.zst", cfg!(feature = "zstd"))] {
+                let path = dir.path().join(name);
+                match json.dump(&path) {
+                    Ok(_) => {
+                        // compression is detected by content, not by extension
+                        let renamed = dir.path().join(format!("{name}.json"));
+                        std::fs::rename(&path, &renamed)?;
+                        assert_eq!(Value::load(&renamed)?, json);
+                    }
+                    Err(e) => assert!(!enabled && e.to_string().contains("feature")),
+                }
+            }
+            json.dump(dir.path().join("plain.json"))?;
+            assert_eq!(Value::load(dir.path().join("plain.json"))?, json);
+            let options = FormatOptions::new().indent(2);
+            let written = json.dump_with_options(dir.path().join("indent.json"), &options)?;
+            assert_eq!(std::fs::read_to_string(dir.path().join("indent.json"))?, json.stringify_with(&options));
+            assert_eq!(written, json.stringify_with(&options).len());
+            Ok(())
+        };
+        result().unwrap();
+    }
+
+    #[test]
+    fn test_read_invalid_utf8() {
+        let invalid = [b'"', 0xff, 0xfe, b'"'];
+        let err = Value::read(&invalid[..]).unwrap_err();
+        assert!(err.to_string().contains("UTF-8"));
+    }
+
+    #[test]
+    fn test_read_without_line_splitting() {
+        assert_eq!(Value::read(&b"[1,\r\n\"\xe3\x81\x82\"]"[..]).unwrap(), Value::Array(vec![1.into(), "あ".into()]));
+        assert_eq!(Value::read(&b"  true"[..]).unwrap(), Value::Bool(true));
+        let err = Value::read(&b"{\r\n  \"k\": tru\r\n}"[..]).unwrap_err();
+        assert_eq!(err.span().map(|s| (s.start, s.byte_range.clone())), Some(((1, 7), 10..13)));
+        let err = Value::read(&b"[1] 2\n"[..]).unwrap_err();
+        assert_eq!(err.span().map(|s| (s.start, s.end, s.byte_range.clone())), Some(((0, 4), (1, 0), 4..6)));
+        assert!(Value::read(&b""[..]).is_err());
+
+        let utf16: Vec<_> = "\u{feff}[\"😀\", tru]".encode_utf16().flat_map(u16::to_be_bytes).collect();
+        let err = Value::read(&utf16[..]).unwrap_err();
+        assert_eq!(err.span().map(|s| (s.start, s.byte_range.clone())), Some(((0, 6), 9..13)));
+    }
+
+    #[test]
+    fn test_parse_iterator() {
+        let raw = "{\"あ\": [1, \"two\"],\n \"b\": null}";
+        assert_eq!(Value::parse_chars(raw.chars()).unwrap(), Value::parse(raw).unwrap());
+        assert_eq!(Value::parse_bytes(raw.bytes().map(Ok)).unwrap(), Value::parse(raw).unwrap());
+
+        let raw = "{\"あ\": [1, tru],\n \"b\": null}";
+        let err = Value::parse_chars(raw.chars()).unwrap_err();
+        assert_eq!(err.span().map(|s| &raw[s.byte_range.clone()]), Some("tru]"));
+        let err = Value::parse_chars("[1]\n  2 ".chars()).unwrap_err();
+        assert_eq!(err.span().map(|s| (s.start, s.end, s.byte_range.clone())), Some(((1, 2), (1, 4), 6..8)));
+
+        let broken = b"[1, 2".iter().map(|&b| Ok(b)).chain([Err(std::io::Error::from(std::io::ErrorKind::BrokenPipe))]);
+        let err = Value::parse_bytes(broken).unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == std::io::ErrorKind::BrokenPipe));
+        let err = Value::parse_bytes(b"[\"\xff\"]".iter().map(|&b| Ok(b))).unwrap_err();
+        assert!(err.to_string().contains("UTF-8"));
+    }
+
+    #[test]
+    fn test_write_written_bytes() {
+        struct Partial(Vec<u8>);
+        impl Write for Partial {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.extend(buf.iter().take(3));
+                Ok(buf.len().min(3))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let json = Value::parse(r#"{"key": ["value", 1, {"é": null}]}"#).unwrap();
+        let mut partial = Partial(Vec::new());
+        assert_eq!(json.write(&mut partial).unwrap(), json.stringify().len());
+        assert_eq!(String::from_utf8(partial.0).unwrap(), json.stringify());
+
+        let mut buf = String::from("reused: ");
+        assert_eq!(json.write_fmt_to(&mut buf).unwrap(), json.stringify().len());
+        assert_eq!(buf, format!("reused: {}", json.stringify()));
+    }
+
+    #[test]
+    fn test_write_ndjson() {
+        let json = Value::parse(r#"[{"nested": [1, 2]}, null, 3.5]"#).unwrap();
+        let mut buf = Vec::new();
+        let written = json.write_ndjson(&mut buf).unwrap();
+        let ndjson = String::from_utf8(buf).unwrap();
+        assert_eq!(written, ndjson.len());
+        let lines: Vec<_> = ndjson.lines().map(|l| Value::parse(l).unwrap()).collect();
+        assert_eq!(Value::Array(lines), json);
+
+        assert_eq!(Value::Array(Vec::new()).write_ndjson(Vec::new()).unwrap(), 0);
+        let err = Value::parse(r#"{"key": 1}"#).unwrap().write_ndjson(Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::Convert(ConvertError::UnexpectedType { expected: "Array", found: "Object" })));
+    }
+
     #[test]
     fn test_json_to_same_string() {
         let json: RawJson = [
@@ -0,0 +1,76 @@
+//! map of [`super::Value::Object`]. by default, it is [`linked_hash_map::LinkedHashMap`] that preserve insertion
+//! order of keys. with `sorted-map` feature, it is [`std::collections::BTreeMap`] that iterate keys in sorted order.
+
+//!
+//! key of object is [`String`] by default. with `interned-keys` feature, it is [`std::sync::Arc<str>`], and parser
+//! share one allocation among the same keys. it reduce memory of large array of objects that have the same keys.
+
+#[cfg(not(feature = "interned-keys"))]
+pub type Key = String;
+#[cfg(feature = "interned-keys")]
+pub type Key = std::sync::Arc<str>;
+
+#[cfg(not(feature = "sorted-map"))]
+pub type Map<K, V> = linked_hash_map::LinkedHashMap<K, V>;
+#[cfg(not(feature = "sorted-map"))]
+pub type Iter<'a, K, V> = linked_hash_map::Iter<'a, K, V>;
+#[cfg(not(feature = "sorted-map"))]
+pub type IntoIter<K, V> = linked_hash_map::IntoIter<K, V>;
+
+#[cfg(feature = "sorted-map")]
+pub type Map<K, V> = std::collections::BTreeMap<K, V>;
+#[cfg(feature = "sorted-map")]
+pub type Iter<'a, K, V> = std::collections::btree_map::Iter<'a, K, V>;
+#[cfg(feature = "sorted-map")]
+pub type IntoIter<K, V> = std::collections::btree_map::IntoIter<K, V>;
+
+/// [`Interner`] share allocation of the same keys. without `interned-keys` feature, it does nothing.
+#[derive(Debug, Default)]
+pub(crate) struct Interner {
+    #[cfg(feature = "interned-keys")]
+    keys: std::collections::HashSet<Key>,
+}
+impl Interner {
+    /// get key that share allocation with the same key interned before.
+    #[cfg(feature = "interned-keys")]
+    pub(crate) fn intern(&mut self, key: String) -> Key {
+        match self.keys.get(&key[..]) {
+            Some(interned) => interned.clone(),
+            None => {
+                let interned = Key::from(key);
+                self.keys.insert(interned.clone());
+                interned
+            }
+        }
+    }
+    /// get key as it is.
+    #[cfg(not(feature = "interned-keys"))]
+    pub(crate) fn intern(&mut self, key: String) -> Key {
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn test_map_order() {
+        let json = Value::parse(r#"{"b": 1, "c": 2, "a": 3}"#).unwrap();
+        let keys: Vec<_> = json.object().keys().map(|k| &k[..]).collect();
+        if cfg!(feature = "sorted-map") {
+            assert_eq!(keys, ["a", "b", "c"]);
+        } else {
+            assert_eq!(keys, ["b", "c", "a"]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "interned-keys")]
+    fn test_interned_keys() {
+        let json = Value::parse(r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "b"}, {"id": 3}]"#).unwrap();
+        let ids: Vec<_> = json.iter().map(|o| o.object().keys().next().unwrap().clone()).collect();
+        assert!(ids.windows(2).all(|w| std::sync::Arc::ptr_eq(&w[0], &w[1])));
+        assert_eq!(json[2]["id"], Value::Integer(3));
+    }
+}
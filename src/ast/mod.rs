@@ -1,12 +1,45 @@
+#[cfg(feature = "base64")]
+pub mod binary;
+#[cfg(feature = "bson")]
+pub mod bson;
+pub mod builder;
+pub mod cache;
+pub(crate) mod canonical;
+pub(crate) mod compress;
+#[cfg(feature = "chrono")]
+pub mod datetime;
 pub mod diff;
 pub mod edit;
+pub mod entry;
+pub mod flatten;
+pub mod format;
 pub mod index;
 pub mod index_path;
 pub mod into;
 pub mod io;
+pub mod map;
+pub mod number;
+pub(crate) mod ord;
+pub mod patch;
+pub mod pointer;
+pub mod query;
+pub mod redact;
+pub mod refs;
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod serializer;
+pub mod set;
+pub mod size;
+pub mod template;
+#[cfg(feature = "toml")]
+pub mod toml;
+pub mod truncate;
 pub mod visit;
 
-use linked_hash_map::LinkedHashMap;
+use format::FormatOptions;
+use map::{Key, Map};
+use number::RawNumber;
 
 /// [`Value`] is ast node of json. see [Introducing JSON](https://www.json.org/json-en.html) also.
 /// # supports
@@ -15,7 +48,7 @@ use linked_hash_map::LinkedHashMap;
 /// - ***indexing*** access to parsed json element (support index access). see [`index::Ranger`], [`Value::get`], and so on.
 ///   - and evaluate it expected type (unexpected type cause panic). see [`Value::object`] and so on.
 /// - ***recombination*** edit ast structure. see [`Value::swap`], [`Value::update_with`] and so on.
-/// - ***visitor*** iterate with dfs order. see [`Value::walk`], [`Value::visitor`].
+/// - ***visitor*** iterate with dfs or bfs order. see [`Value::walk`], [`Value::visitor`], and [`Value::bfs`].
 ///
 /// # examples
 /// this example is read from and write to `String`.
@@ -53,10 +86,11 @@ use linked_hash_map::LinkedHashMap;
 /// let str_json = json.stringify();
 /// assert!(str_json.contains("\"language\""));
 /// ```
-#[derive(PartialEq, Debug, Clone)]
+#[derive(Debug, Clone)]
 pub enum Value {
-    /// correspond to object of json. object can be represented by `HashMap` in rust.
-    Object(LinkedHashMap<String, Value>),
+    /// correspond to object of json. object can be represented by `HashMap` in rust. see [`Map`] also.
+    /// it is boxed because map is much larger than other variants.
+    Object(Box<Map<Key, Value>>),
 
     /// correspond to array of json. array can be represented by `Vec` in rust.
     Array(Vec<Value>),
@@ -75,66 +109,83 @@ pub enum Value {
 
     /// correspond to float of json. json has only number, but rust has float.
     Float(f64),
+
+    /// correspond to number of json that keep its source text. see [`number::RawNumber`] also.
+    RawNumber(RawNumber),
 }
 
+/// `{}` write minified json like [`Value::to_string`], and `{:#}` write json with proper indent like
+/// [`Value::stringify`]. width of `{:#}` is used as indent width, such as `{:#2}`.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let json = Value::parse(r#"{"key": [1]}"#).unwrap();
+/// assert_eq!(format!("{json}"), r#"{"key":[1]}"#);
+/// assert_eq!(format!("{json:#}"), "{\n    \"key\": [\n        1\n    ]\n}");
+/// assert_eq!(format!("{json:#2}"), "{\n  \"key\": [\n    1\n  ]\n}");
+/// ```
 impl std::fmt::Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let json_display = match self {
-            Value::Object(object) => format!(
-                "{{{}}}",
-                object.iter().map(|(k, v)| format!("{}:{}", quote(k), v)).collect::<Vec<_>>().join(","),
-            ),
-            Value::Array(array) => {
-                format!("[{}]", array.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(","))
-            }
-            Value::Bool(bool) => bool.to_string(),
-            Value::Null => "null".to_string(),
-            Value::String(string) => quote(string),
-            Value::Integer(integer) => integer.to_string(),
-            Value::Float(float) => float.to_string(),
+        if f.alternate() {
+            let options = f.width().into_iter().fold(FormatOptions::default(), FormatOptions::indent);
+            f.write_str(&self.stringify_with(&options))
+        } else {
+            f.write_str(&self.to_string_with(&FormatOptions::default()))
+        }
+    }
+}
+
+/// default value is [`Value::Null`], so `std::mem::take` can move value out of tree.
+impl Default for Value {
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+/// construct empty `Value` that will be assembled incrementally.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let mut json = Value::new_object();
+/// let tags = json.entry("tags").or_insert(Value::array_with_capacity(2));
+/// tags.extend([Value::from("a"), Value::from("b")]);
+/// assert_eq!(json, Value::parse(r#"{"tags": ["a", "b"]}"#).unwrap());
+/// ```
+impl Value {
+    /// get empty [`Value::Object`], that is `{}`.
+    pub fn new_object() -> Self {
+        Value::Object(Box::default())
+    }
+    /// get empty [`Value::Array`], that is `[]`.
+    pub fn new_array() -> Self {
+        Value::Array(Vec::new())
+    }
+    /// get empty [`Value::Object`] with capacity of at least `capacity` entries. with `sorted-map` feature,
+    /// `capacity` is ignored because `BTreeMap` has no capacity.
+    pub fn object_with_capacity(capacity: usize) -> Self {
+        #[cfg(not(feature = "sorted-map"))]
+        let map = Map::with_capacity(capacity);
+        #[cfg(feature = "sorted-map")]
+        let map = {
+            let _ = capacity;
+            Map::new()
         };
-        write!(f, "{json_display}")
+        Value::Object(Box::new(map))
+    }
+    /// get empty [`Value::Array`] with capacity of at least `capacity` elements.
+    pub fn array_with_capacity(capacity: usize) -> Self {
+        Value::Array(Vec::with_capacity(capacity))
     }
 }
 
 impl Value {
     /// stringify ast with proper indent.
     pub fn stringify(&self) -> String {
-        fn stringify_recursive(value: &Value, indent: usize) -> String {
-            let indent_unit = " ".repeat(4);
-            let indent_internal = indent_unit.repeat(indent + 1);
-            let indent_external = indent_unit.repeat(indent);
-            match value {
-                Value::Object(object) => format!(
-                    "{{\n{}\n{indent_external}}}",
-                    object
-                        .iter()
-                        .map(|(k, v)| format!("{indent_internal}{}: {}", quote(k), stringify_recursive(v, indent + 1)))
-                        .collect::<Vec<_>>()
-                        .join(",\n"),
-                ),
-                Value::Array(array) => {
-                    format!(
-                        "[\n{}\n{indent_external}]",
-                        array
-                            .iter()
-                            .map(|v| format!("{indent_internal}{}", stringify_recursive(v, indent + 1)))
-                            .collect::<Vec<_>>()
-                            .join(",\n")
-                    )
-                }
-                Value::Bool(bool) => bool.to_string(),
-                Value::Null => "null".to_string(),
-                Value::String(string) => quote(string),
-                Value::Integer(integer) => integer.to_string(),
-                Value::Float(float) => float.to_string(),
-            }
-        }
-        stringify_recursive(self, 0)
+        self.stringify_with(&FormatOptions::default())
     }
 
     /// get ast node type as `&str`. mainly for debugging purposes.
-    pub fn node_type(&self) -> &str {
+    pub fn node_type(&self) -> &'static str {
         match self {
             Value::Object(_) => "Object",
             Value::Array(_) => "Array",
@@ -143,20 +194,47 @@ impl Value {
             Value::String(_) => "String",
             Value::Integer(_) => "Integer",
             Value::Float(_) => "Float",
+            Value::RawNumber(_) => "RawNumber",
         }
     }
 }
 
-fn quote(s: &str) -> String {
-    format!(
-        "\"{}\"",
-        s.replace('\\', "\\\\")
-            .replace('"', "\\\"")
-            .replace('/', "\\/")
-            .replace('\n', "\\n")
-            .replace('\r', "\\r")
-            .replace('\t', "\\t")
-    )
+/// drop deeply nested value without recursion, so that dropping value parsed from pathological input (such as
+/// `[[[[...]]]]`) cannot overflow the stack. because of this, field of value cannot be moved out by pattern
+/// matching, use `std::mem::take` or conversion such as `Vec::<Value>::try_from` instead.
+impl Drop for Value {
+    fn drop(&mut self) {
+        fn take_children(value: &mut Value, stack: &mut Vec<Value>) {
+            match value {
+                Value::Object(m) => {
+                    for (_key, v) in std::mem::take(&mut **m) {
+                        stack.push(v);
+                    }
+                }
+                Value::Array(a) => stack.append(a),
+                _ => (),
+            }
+        }
+        let nested = |v: &Value| match v {
+            Value::Object(m) => !m.is_empty(),
+            Value::Array(a) => !a.is_empty(),
+            _ => false,
+        };
+        let shallow = match self {
+            Value::Object(m) => !m.values().any(nested),
+            Value::Array(a) => !a.iter().any(nested),
+            _ => true,
+        };
+        if shallow {
+            // children have no grandchild, so default drop glue is not deep
+            return;
+        }
+        let mut stack = Vec::new();
+        take_children(self, &mut stack);
+        while let Some(mut value) = stack.pop() {
+            take_children(&mut value, &mut stack);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -184,4 +262,49 @@ mod tests {
         assert_eq!(ast_root2, ast_root3);
         assert_eq!(ast_root3, ast_root);
     }
+
+    #[test]
+    fn test_empty_constructor() {
+        assert_eq!(Value::default(), Value::Null);
+        for (value, expected) in [
+            (Value::new_object(), "{}"),
+            (Value::object_with_capacity(8), "{}"),
+            (Value::new_array(), "[]"),
+            (Value::array_with_capacity(8), "[]"),
+        ] {
+            assert_eq!(value, Value::parse(expected).unwrap());
+        }
+        assert!(Value::array_with_capacity(8).array().capacity() >= 8);
+
+        let mut json = Value::parse(r#"{"a": [1, 2]}"#).unwrap();
+        let taken = std::mem::take(&mut json["a"]);
+        assert_eq!((taken, json), (Value::parse("[1, 2]").unwrap(), Value::parse(r#"{"a": null}"#).unwrap()));
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        let json = Value::parse(r#"{"nested": {"array": [true, null]}}"#).unwrap();
+        assert_eq!(format!("{json}"), json.to_string());
+        assert_eq!(format!("{json:#}"), json.stringify());
+        assert_eq!(format!("{json:#1}"), json.stringify().replace("    ", " "));
+        assert_eq!(format!("{json:#3}"), json.stringify_with(&FormatOptions::new().indent(3)));
+        assert_eq!(format!("{json:10}"), json.to_string());
+    }
+
+    #[test]
+    fn test_value_size() {
+        // element of large array of scalars should not pay for the size of map
+        assert!(std::mem::size_of::<Value>() <= 32, "{}", std::mem::size_of::<Value>());
+    }
+
+    #[test]
+    fn test_drop_deep_value() {
+        let (mut array, mut object) = (Value::Array(Vec::new()), Value::Object(Default::default()));
+        for _ in 0..1_000_000 {
+            array = Value::Array(vec![Value::Null, array]);
+            object = vec![("key".to_string(), object)].into_iter().collect();
+        }
+        drop(array);
+        drop(object);
+    }
 }
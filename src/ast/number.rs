@@ -0,0 +1,283 @@
+/// [`RawNumber`] is json number that keep its source text, so it can be written back exactly.
+/// it is gotten by [`crate::ParserOptions::lossless_number`].
+/// # examples
+/// ```
+/// use dyson::{ParserOptions, Value};
+/// let raw_json = "[1.230e2, 1234567890123456789012345678901234567890]";
+/// let json = Value::parse_with(raw_json, ParserOptions::new().lossless_number(true)).unwrap();
+///
+/// assert_eq!(json.to_string(), "[1.230e2,1234567890123456789012345678901234567890]");
+/// assert_eq!(json[0].get_raw_number().unwrap().as_f64(), Some(123.));
+/// assert_eq!(json[1].get_raw_number().unwrap().as_i64(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawNumber(String);
+
+impl RawNumber {
+    /// get source text of the number.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// check it has no fraction part and exponent part.
+    pub fn is_integer(&self) -> bool {
+        !self.0.contains(['.', 'e', 'E'])
+    }
+
+    /// convert into `i64`. if it is not integer or it overflow `i64`, return `None`.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+
+    /// convert into `f64`. the result may be rounded.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.0.parse().ok()
+    }
+}
+
+/// [`Number`] is json number regardless of its representation, it is gotten by [`crate::Value::number`].
+/// arithmetic of integers is done with integers, and it fall back to float when the result is not integer or it
+/// overflow. equality and ordering compare the exact value, so `Integer(2) == Float(2.0)`.
+/// # examples
+/// ```
+/// use dyson::{Number, Value};
+/// let json = Value::parse(r#"[3, 0.5, 9223372036854775807]"#).unwrap();
+/// let (a, b, max) = (json[0].number().unwrap(), json[1].number().unwrap(), json[2].number().unwrap());
+///
+/// assert_eq!(a + a, Number::Integer(6));
+/// assert_eq!(a * b, Number::Float(1.5));
+/// assert_eq!(a / Number::Integer(2), Number::Float(1.5));
+/// assert_eq!(a + Number::Float(1.0), Number::Integer(4));
+/// assert!(max + Number::Integer(1) > max);
+/// assert_eq!(Value::from(a - a), Value::Integer(0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Number::Integer(_))
+    }
+    pub fn is_float(&self) -> bool {
+        matches!(self, Number::Float(_))
+    }
+
+    /// get `i64` if this number is integral number in range of `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Number::Integer(i) => Some(i),
+            Number::Float(f) => exact_integer(f).and_then(|i| i.try_into().ok()),
+        }
+    }
+
+    /// get `f64`. the result may be rounded.
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Number::Integer(i) => i as f64,
+            Number::Float(f) => f,
+        }
+    }
+
+    fn arithmetic<I, F>(self, rhs: Number, integer: I, float: F) -> Number
+    where
+        I: FnOnce(i64, i64) -> Option<i64>,
+        F: FnOnce(f64, f64) -> f64,
+    {
+        match (self, rhs) {
+            (Number::Integer(l), Number::Integer(r)) => integer(l, r).map(Number::Integer),
+            _ => None,
+        }
+        .unwrap_or_else(|| Number::Float(float(self.as_f64(), rhs.as_f64())))
+    }
+}
+
+impl std::ops::Add for Number {
+    type Output = Number;
+    fn add(self, rhs: Number) -> Number {
+        self.arithmetic(rhs, i64::checked_add, std::ops::Add::add)
+    }
+}
+impl std::ops::Sub for Number {
+    type Output = Number;
+    fn sub(self, rhs: Number) -> Number {
+        self.arithmetic(rhs, i64::checked_sub, std::ops::Sub::sub)
+    }
+}
+impl std::ops::Mul for Number {
+    type Output = Number;
+    fn mul(self, rhs: Number) -> Number {
+        self.arithmetic(rhs, i64::checked_mul, std::ops::Mul::mul)
+    }
+}
+impl std::ops::Div for Number {
+    type Output = Number;
+    fn div(self, rhs: Number) -> Number {
+        let exact = |l: i64, r: i64| l.checked_rem(r).filter(|&m| m == 0).and_then(|_| l.checked_div(r));
+        self.arithmetic(rhs, exact, std::ops::Div::div)
+    }
+}
+impl std::ops::Neg for Number {
+    type Output = Number;
+    fn neg(self) -> Number {
+        match self {
+            Number::Integer(i) => i.checked_neg().map_or(Number::Float(-(i as f64)), Number::Integer),
+            Number::Float(f) => Number::Float(-f),
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(std::cmp::Ordering::Equal)
+    }
+}
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match (*self, *other) {
+            (Number::Integer(l), Number::Integer(r)) => Some(l.cmp(&r)),
+            (Number::Float(l), Number::Float(r)) => l.partial_cmp(&r),
+            (Number::Integer(l), Number::Float(r)) => compare_integer_float(l, r),
+            (Number::Float(l), Number::Integer(r)) => compare_integer_float(r, l).map(std::cmp::Ordering::reverse),
+        }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(i: i64) -> Self {
+        Number::Integer(i)
+    }
+}
+impl From<f64> for Number {
+    fn from(f: f64) -> Self {
+        Number::Float(f)
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", crate::Value::from(*self))
+    }
+}
+
+/// compare integer and float exactly, without rounding integer into float.
+fn compare_integer_float(i: i64, f: f64) -> Option<std::cmp::Ordering> {
+    let trunc = f.trunc();
+    if f.is_nan() {
+        None
+    } else if trunc >= 2f64.powi(63) {
+        Some(std::cmp::Ordering::Less)
+    } else if trunc < -(2f64.powi(63)) {
+        Some(std::cmp::Ordering::Greater)
+    } else {
+        Some(i.cmp(&(trunc as i64)).then(0f64.partial_cmp(&(f - trunc))?))
+    }
+}
+
+/// get integer that is exactly equal to `f`.
+pub(crate) fn exact_integer(f: f64) -> Option<i128> {
+    // 2^127 is exactly representable, and `f64` whose absolute value is less than it is in range of `i128`
+    (f.is_finite() && f.fract() == 0. && f.abs() < 2f64.powi(127)).then(|| f as i128)
+}
+
+/// get float that is exactly equal to `i`.
+pub(crate) fn exact_float(i: i128) -> Option<f64> {
+    let f = i as f64;
+    (exact_integer(f) == Some(i)).then(|| f)
+}
+
+impl std::fmt::Display for RawNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<RawNumber> for String {
+    fn from(raw: RawNumber) -> Self {
+        raw.0
+    }
+}
+
+/// only parser can construct [`RawNumber`], so its source text is always valid json number.
+pub(crate) fn raw_number(number: String) -> RawNumber {
+    RawNumber(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Number;
+    use crate::{ParserOptions, Value};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn test_number_arithmetic() {
+        let (i, f) = (Number::Integer, Number::Float);
+        assert_eq!(i(2) + i(3), i(5));
+        assert!((i(2) + i(3)).is_integer() && (i(2) + f(3.)).is_float());
+        assert_eq!(i(i64::MAX) + i(1), f(9223372036854775808.));
+        assert_eq!(i(7) - f(0.5), f(6.5));
+        assert_eq!(i(i64::MIN) * i(-1), f(9223372036854775808.));
+        assert_eq!(i(6) / i(3), i(2));
+        assert!((i(6) / i(3)).is_integer() && (i(7) / i(2)).is_float());
+        assert_eq!(i(7) / i(2), f(3.5));
+        assert!((i(1) / i(0)).as_f64().is_infinite());
+        assert_eq!(-i(i64::MIN), f(9223372036854775808.));
+        assert_eq!(-f(0.5), f(-0.5));
+    }
+
+    #[test]
+    fn test_number_compare() {
+        let (i, f) = (Number::Integer, Number::Float);
+        for (l, r, expected) in [
+            (i(1), i(2), Some(Ordering::Less)),
+            (i(2), f(2.), Some(Ordering::Equal)),
+            (i(2), f(1.5), Some(Ordering::Greater)),
+            (i(-2), f(-2.5), Some(Ordering::Greater)),
+            (i(i64::MAX), f(9223372036854775807.), Some(Ordering::Less)),
+            (i(i64::MIN), f(-9223372036854775808.), Some(Ordering::Equal)),
+            (i(i64::MIN), f(f64::NEG_INFINITY), Some(Ordering::Greater)),
+            (f(0.5), i(0), Some(Ordering::Greater)),
+            (i(0), f(f64::NAN), None),
+        ] {
+            assert_eq!(l.partial_cmp(&r), expected, "{l:?} {r:?}");
+        }
+        assert_eq!(f(2.5).as_i64(), None);
+        assert_eq!(f(-3.).as_i64(), Some(-3));
+        assert_eq!((i(1).to_string(), f(1.5).to_string()), ("1".to_string(), "1.5".to_string()));
+    }
+
+    #[test]
+    fn test_number() {
+        let json = Value::parse_with(r#"[1, 2.5, 1e400, "1"]"#, ParserOptions::new().lossless_number(true)).unwrap();
+        assert!(json[0].number().unwrap().is_integer());
+        assert_eq!(json[1].number(), Some(Number::Float(2.5)));
+        assert_eq!(json[2].number(), Some(Number::Float(f64::INFINITY)));
+        assert_eq!(json[3].number(), None);
+        assert_eq!(Value::Float(0.5).number(), Some(Number::Float(0.5)));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_lossless_number_round_trip() {
+        let raw_json =
+            r#"{"float": 1.230e2, "big": -1234567890123456789012345678901234567890, "integer": 10, "small": 1E-400}"#;
+        let options = ParserOptions::new().lossless_number(true);
+        let json = Value::parse_with(raw_json, options.clone()).unwrap();
+        assert_eq!(json["float"].get_raw_number().unwrap().as_str(), "1.230e2");
+        assert!(json["big"].is_number());
+        assert!(json["integer"].get_raw_number().unwrap().is_integer());
+        assert_eq!(json["integer"].get_raw_number().unwrap().as_i64(), Some(10));
+        assert_eq!(json["small"].get_raw_number().unwrap().as_f64(), Some(0.));
+
+        let minified = json.to_string();
+        assert_eq!(
+            minified,
+            r#"{"float":1.230e2,"big":-1234567890123456789012345678901234567890,"integer":10,"small":1E-400}"#
+        );
+        assert_eq!(Value::parse_with(json.stringify(), options).unwrap(), json);
+        assert!(Value::parse(raw_json).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! canonical total order of [`Value`].
+
+use super::{query::order, Value};
+use std::cmp::Ordering;
+
+/// [`Value::Float`] is compared by its value, but all NaN are equal each other, unlike `f64`.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Value {}
+
+/// canonical total order of [`Value`]. it refine the order of [`Value::eval`] (jq order), so values are ordered by
+/// type first, `null` < bool < number < string < array < object, and then by value. `==` of [`Value`] is consistent
+/// with this order.
+/// - bool: `false` < `true`.
+/// - number: compared by its exact value, so `1` < `1.5` < `2`. NaN is greater than any other number. numbers that
+///   have the same value are ordered by representation, `Integer` < `Float` < `RawNumber`, so `Integer(1)` and
+///   `Float(1.0)` are not equal. `RawNumber`s of the same value are ordered by its source text.
+/// - string: lexicographic order of bytes.
+/// - array: lexicographic order of elements, and shorter array is less if one is prefix of the other.
+/// - object: order of sorted keys, and then values of the keys. objects that are equal in this way are ordered by
+///   their entries (key and value) in iteration order of [`crate::Map`].
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let mut values = Value::parse(r#"[{"a": 1}, [2], "3", 4.5, 4, true, null]"#).unwrap();
+/// values.sort_array_by(Ord::cmp);
+/// assert_eq!(values, Value::parse(r#"[null, true, 4, 4.5, "3", [2], {"a": 1}]"#).unwrap());
+///
+/// use std::collections::BTreeMap;
+/// let counts: BTreeMap<_, _> = values.iter().map(|v| (v, 1)).collect();
+/// assert_eq!(counts.keys().next(), Some(&&Value::Null));
+/// ```
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        order(self, other).then_with(|| refine(self, other))
+    }
+}
+
+/// order values that are equal in [`order`] by their representation.
+fn refine(a: &Value, b: &Value) -> Ordering {
+    let representation = |value: &Value| match value {
+        Value::Integer(_) => 0,
+        Value::Float(_) => 1,
+        _ => 2,
+    };
+    match (a, b) {
+        (Value::Array(a), Value::Array(b)) => a.iter().cmp(b.iter()),
+        (Value::Object(a), Value::Object(b)) => {
+            a.iter().map(|(k, v)| (&k[..], v)).cmp(b.iter().map(|(k, v)| (&k[..], v)))
+        }
+        (Value::RawNumber(ra), Value::RawNumber(rb)) => ra.as_str().cmp(rb.as_str()),
+        _ if a.is_number() && b.is_number() => representation(a).cmp(&representation(b)),
+        _ => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_order() {
+        let sorted = [
+            Value::Null,
+            Value::Bool(false),
+            Value::Bool(true),
+            Value::Float(f64::NEG_INFINITY),
+            Value::Integer(i64::MIN),
+            Value::Float(-0.5),
+            Value::Integer(0),
+            Value::Float(0.0),
+            Value::Integer(1),
+            Value::Float(1.0),
+            Value::Integer(i64::MAX),
+            Value::Float(9223372036854775808.),
+            Value::Float(f64::NAN),
+            Value::from(""),
+            Value::from("a"),
+            Value::from("b"),
+            Value::parse("[]").unwrap(),
+            Value::parse("[1]").unwrap(),
+            Value::parse("[1, null]").unwrap(),
+            Value::parse("[2]").unwrap(),
+            Value::parse("{}").unwrap(),
+            Value::parse(r#"{"a": 1}"#).unwrap(),
+            Value::parse(r#"{"a": 2}"#).unwrap(),
+            Value::parse(r#"{"b": 0}"#).unwrap(),
+        ];
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{a:?} {b:?}");
+                assert_eq!(a == b, i == j, "{a:?} {b:?}");
+            }
+        }
+        assert_eq!(Value::Float(0.0), Value::Float(-0.0));
+        assert_eq!(Value::Float(f64::NAN), Value::Float(-f64::NAN));
+    }
+
+    #[test]
+    fn test_refine_jq_order() {
+        let values = Value::parse(
+            r#"[1, 1.0, 2, "a", [1], [1.0], [1, 0], {"a": 1, "b": 2}, {"b": 2, "a": 1}, {"a": 1.0, "b": 2}, {"c": 0}]"#,
+        )
+        .unwrap();
+        for a in values.iter() {
+            for b in values.iter() {
+                if order(a, b).is_ne() {
+                    assert_eq!(a.cmp(b), order(a, b), "{a:?} {b:?}");
+                }
+            }
+        }
+        assert!(values[7] < values[9] && order(&values[7], &values[9]).is_eq());
+        assert!(values[4] < values[5] && order(&values[4], &values[5]).is_eq());
+    }
+
+    #[test]
+    fn test_raw_number_order() {
+        let options = crate::ParserOptions::new().lossless_number(true);
+        let raw = Value::parse_with("[1, 1.0, 1.00, 2e0, 12345678901234567890]", options).unwrap();
+        assert!(raw[0] < raw[1] && raw[1] < raw[2] && raw[2] < raw[3] && raw[3] < raw[4]);
+        assert!(Value::Float(1.0) < raw[0] && raw[0] < Value::Integer(2));
+        assert_eq!(raw[0], raw[0].clone());
+    }
+}
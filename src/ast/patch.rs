@@ -0,0 +1,528 @@
+//! JSON Patch of [RFC 6902](https://www.rfc-editor.org/rfc/rfc6902), that is list of operations such as add and
+//! remove, applied to json document in order. location of each operation is json pointer such as `"/foo/0"`.
+
+use super::{
+    diff::diff_value_lenient,
+    into::ConvertError,
+    map::{Key, Map},
+    pointer::{array_index, escape, tokens},
+    Value,
+};
+use thiserror::Error;
+
+/// [`PatchError`] is error of parsing or applying [`Patch`]. `index` is index of the operation in the patch.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    #[error("patch must be array of operations, but {found}")]
+    NotArray { found: &'static str },
+
+    #[error("operation {index} is invalid: {reason}")]
+    InvalidOperation { index: usize, reason: String },
+
+    #[error("operation {index} failed at {pointer:?}: {reason}")]
+    Failed { index: usize, pointer: String, reason: &'static str },
+
+    #[error("operation {index} failed: value at {pointer:?} is not expected one")]
+    TestFailed { index: usize, pointer: String },
+}
+
+/// [`Operation`] is one operation of [`Patch`]. `path` and `from` are json pointer such as `"/foo/0"`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    /// add `value` into object or array, or replace value of existing key. `-` of array means its end.
+    Add { path: String, value: Value },
+    /// remove existing value.
+    Remove { path: String },
+    /// replace existing value with `value`.
+    Replace { path: String, value: Value },
+    /// remove value at `from` and add it to `path`.
+    Move { from: String, path: String },
+    /// add copy of value at `from` to `path`.
+    Copy { from: String, path: String },
+    /// test value at `path` is equal to `value`.
+    Test { path: String, value: Value },
+}
+
+/// [`Patch`] is JSON Patch, list of [`Operation`]. see [`Value::apply_patch`] also.
+/// # examples
+/// ```
+/// use dyson::{Patch, Value};
+/// let patch = Patch::parse(Value::parse(r#"[
+///     {"op": "add", "path": "/tags/-", "value": "new"},
+///     {"op": "remove", "path": "/draft"},
+///     {"op": "test", "path": "/title", "value": "dyson"}
+/// ]"#).unwrap()).unwrap();
+///
+/// let mut json = Value::parse(r#"{"title": "dyson", "draft": true, "tags": ["json"]}"#).unwrap();
+/// json.apply_patch(&patch).unwrap();
+/// assert_eq!(json, Value::parse(r#"{"title": "dyson", "tags": ["json", "new"]}"#).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Patch(pub Vec<Operation>);
+
+impl Patch {
+    /// parse JSON Patch document, that is array of operation objects.
+    pub fn parse(value: Value) -> Result<Self, PatchError> {
+        let found = value.node_type();
+        let operations: Vec<Value> = value.try_into().map_err(|_| PatchError::NotArray { found })?;
+        operations.into_iter().enumerate().map(|(index, operation)| Operation::parse(index, operation)).collect()
+    }
+}
+impl FromIterator<Operation> for Patch {
+    fn from_iter<T: IntoIterator<Item = Operation>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl From<&Patch> for Value {
+    fn from(patch: &Patch) -> Self {
+        Value::Array(patch.0.iter().map(Value::from).collect())
+    }
+}
+
+impl Operation {
+    /// get name of this operation, such as `"add"`.
+    pub fn op(&self) -> &'static str {
+        match self {
+            Operation::Add { .. } => "add",
+            Operation::Remove { .. } => "remove",
+            Operation::Replace { .. } => "replace",
+            Operation::Move { .. } => "move",
+            Operation::Copy { .. } => "copy",
+            Operation::Test { .. } => "test",
+        }
+    }
+
+    /// get json pointer of target of this operation.
+    pub fn path(&self) -> &str {
+        match self {
+            Operation::Add { path, .. } | Operation::Remove { path } | Operation::Replace { path, .. } => path,
+            Operation::Move { path, .. } | Operation::Copy { path, .. } | Operation::Test { path, .. } => path,
+        }
+    }
+
+    fn parse(index: usize, operation: Value) -> Result<Self, PatchError> {
+        let invalid = |reason: String| PatchError::InvalidOperation { index, reason };
+        let mut object: Map<Key, Value> =
+            operation.try_into().map_err(|e: ConvertError| invalid(format!("operation must be object, {e}")))?;
+        let mut member = |name: &str| object.remove(name).ok_or_else(|| invalid(format!("missing {name:?}")));
+        let op = member("op")?.get_string().map(str::to_string).ok_or_else(|| invalid("op must be string".into()))?;
+        let path = member("path")?;
+        let pointer = |name: &str, value: Value| match value.get_string() {
+            Some(pointer) if tokens(pointer).is_some() => Ok(pointer.to_string()),
+            _ => Err(invalid(format!("{name:?} must be json pointer, but {value}"))),
+        };
+        let path = pointer("path", path)?;
+        Ok(match &op[..] {
+            "add" => Operation::Add { path, value: member("value")? },
+            "remove" => Operation::Remove { path },
+            "replace" => Operation::Replace { path, value: member("value")? },
+            "move" => Operation::Move { from: pointer("from", member("from")?)?, path },
+            "copy" => Operation::Copy { from: pointer("from", member("from")?)?, path },
+            "test" => Operation::Test { path, value: member("value")? },
+            _ => return Err(invalid(format!("unknown op {op:?}"))),
+        })
+    }
+
+    fn apply(&self, index: usize, doc: &mut Value) -> Result<(), PatchError> {
+        let failed = |pointer: &str, reason| PatchError::Failed { index, pointer: pointer.to_string(), reason };
+        let parse = |pointer: &str| tokens(pointer).ok_or_else(|| failed(pointer, "invalid json pointer"));
+        match self {
+            Operation::Add { path, value } => add(doc, &parse(path)?, value.clone()).map_err(|r| failed(path, r)),
+            Operation::Remove { path } => remove(doc, &parse(path)?).map(drop).map_err(|r| failed(path, r)),
+            Operation::Replace { path, value } => {
+                *get_mut(doc, &parse(path)?).ok_or_else(|| failed(path, "path does not exist"))? = value.clone();
+                Ok(())
+            }
+            Operation::Move { from, path } => {
+                let (from_tokens, path_tokens) = (parse(from)?, parse(path)?);
+                if path_tokens.len() > from_tokens.len() && path_tokens.starts_with(&from_tokens) {
+                    return Err(failed(path, "value cannot be moved into its own child"));
+                }
+                let value = remove(doc, &from_tokens).map_err(|r| failed(from, r))?;
+                add(doc, &path_tokens, value).map_err(|r| failed(path, r))
+            }
+            Operation::Copy { from, path } => {
+                let value = get(doc, &parse(from)?).ok_or_else(|| failed(from, "path does not exist"))?.clone();
+                add(doc, &parse(path)?, value).map_err(|r| failed(path, r))
+            }
+            Operation::Test { path, value } => {
+                let actual = get(doc, &parse(path)?).ok_or_else(|| failed(path, "path does not exist"))?;
+                match diff_value_lenient(actual, value).is_empty() {
+                    true => Ok(()),
+                    false => Err(PatchError::TestFailed { index, pointer: path.to_string() }),
+                }
+            }
+        }
+    }
+}
+impl From<&Operation> for Value {
+    fn from(operation: &Operation) -> Self {
+        let mut object = Map::new();
+        object.insert("op".into(), Value::from(operation.op()));
+        match operation {
+            Operation::Move { from, .. } | Operation::Copy { from, .. } => {
+                object.insert("from".into(), Value::from(&from[..]));
+            }
+            _ => (),
+        }
+        object.insert("path".into(), Value::from(operation.path()));
+        match operation {
+            Operation::Add { value, .. } | Operation::Replace { value, .. } | Operation::Test { value, .. } => {
+                object.insert("value".into(), value.clone());
+            }
+            _ => (),
+        }
+        Value::Object(Box::new(object))
+    }
+}
+
+impl Value {
+    /// apply JSON Patch to this document. operations are applied in order, and if one of them fails, the document
+    /// is not changed at all. error has index of the failed operation. see [`Patch`] also.
+    pub fn apply_patch(&mut self, patch: &Patch) -> Result<(), PatchError> {
+        let mut patched = self.clone();
+        for (index, operation) in patch.0.iter().enumerate() {
+            operation.apply(index, &mut patched)?;
+        }
+        *self = patched;
+        Ok(())
+    }
+}
+
+/// generate JSON Patch that transform `a` into `b`. object is compared key by key, and array is aligned by
+/// minimal edit of elements, so inserted or removed element become one `add` or `remove` operation. but too large
+/// arrays (product of their lengths is over 2^20 after common prefix and suffix) are compared index by index.
+/// # examples
+/// ```
+/// use dyson::{diff_as_patch, Value};
+/// let a = Value::parse(r#"{"title": "dyson", "tags": ["json", "parser"], "wip": true}"#).unwrap();
+/// let b = Value::parse(r#"{"title": "dyson", "tags": ["rust", "json", "parser"]}"#).unwrap();
+///
+/// let patch = diff_as_patch(&a, &b);
+/// assert_eq!(
+///     Value::from(&patch),
+///     Value::parse(r#"[{"op": "add", "path": "/tags/0", "value": "rust"}, {"op": "remove", "path": "/wip"}]"#).unwrap()
+/// );
+/// let mut patched = a.clone();
+/// patched.apply_patch(&patch).unwrap();
+/// assert_eq!(patched, b);
+/// ```
+pub fn diff_as_patch(a: &Value, b: &Value) -> Patch {
+    let mut operations = Vec::new();
+    diff_recursive(a, b, &mut String::new(), &mut operations);
+    Patch(operations)
+}
+
+fn diff_recursive(a: &Value, b: &Value, pointer: &mut String, operations: &mut Vec<Operation>) {
+    let len = pointer.len();
+    match (a, b) {
+        (Value::Object(oa), Value::Object(ob)) => {
+            for (k, va) in oa.iter() {
+                pointer.push('/');
+                pointer.push_str(&escape(k));
+                match ob.get(k) {
+                    Some(vb) => diff_recursive(va, vb, pointer, operations),
+                    None => operations.push(Operation::Remove { path: pointer.clone() }),
+                }
+                pointer.truncate(len);
+            }
+            for (k, vb) in ob.iter().filter(|(k, _)| !oa.contains_key(*k)) {
+                let path = format!("{}/{}", pointer, escape(k));
+                operations.push(Operation::Add { path, value: vb.clone() });
+            }
+        }
+        (Value::Array(va), Value::Array(vb)) => {
+            let mut index = 0;
+            for edit in align(va, vb) {
+                pointer.push_str(&format!("/{index}"));
+                match edit {
+                    Edit::Keep => index += 1,
+                    Edit::Substitute(ea, eb) => {
+                        diff_recursive(ea, eb, pointer, operations);
+                        index += 1;
+                    }
+                    Edit::Remove => operations.push(Operation::Remove { path: pointer.clone() }),
+                    Edit::Insert(eb) => {
+                        operations.push(Operation::Add { path: pointer.clone(), value: eb.clone() });
+                        index += 1;
+                    }
+                }
+                pointer.truncate(len);
+            }
+        }
+        (a, b) if !same(a, b) => operations.push(Operation::Replace { path: pointer.clone(), value: b.clone() }),
+        _ => (),
+    }
+}
+
+/// max size of edit distance table of [`align`], larger arrays are aligned index by index.
+const MAX_ALIGN_TABLE: usize = 1 << 20;
+
+/// edit of array element from `a` to `b`.
+enum Edit<'a> {
+    Keep,
+    Substitute(&'a Value, &'a Value),
+    Remove,
+    Insert(&'a Value),
+}
+
+/// align elements of `a` and `b` by minimal edit distance, common prefix and suffix are kept without comparison.
+/// if edit distance table is larger than [`MAX_ALIGN_TABLE`], elements are aligned index by index.
+fn align<'a>(a: &'a [Value], b: &'a [Value]) -> Vec<Edit<'a>> {
+    let prefix = a.iter().zip(b).take_while(|(ea, eb)| same(ea, eb)).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(ea, eb)| same(ea, eb)).count();
+    let (ma, mb) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+    if (ma.len() + 1).saturating_mul(mb.len() + 1) > MAX_ALIGN_TABLE {
+        return align_index_wise(a, b);
+    }
+
+    // distance[i][j] is edit distance between ma[i..] and mb[j..]
+    let mut distance = vec![vec![0; mb.len() + 1]; ma.len() + 1];
+    for i in (0..=ma.len()).rev() {
+        for j in (0..=mb.len()).rev() {
+            distance[i][j] = match (i == ma.len(), j == mb.len()) {
+                (true, _) => mb.len() - j,
+                (_, true) => ma.len() - i,
+                _ if same(&ma[i], &mb[j]) => distance[i + 1][j + 1],
+                _ => 1 + distance[i + 1][j + 1].min(distance[i + 1][j]).min(distance[i][j + 1]),
+            };
+        }
+    }
+    let mut edits: Vec<_> = (0..prefix).map(|_| Edit::Keep).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < ma.len() || j < mb.len() {
+        if i < ma.len() && j < mb.len() && same(&ma[i], &mb[j]) {
+            edits.push(Edit::Keep);
+            (i, j) = (i + 1, j + 1);
+        } else if i < ma.len() && j < mb.len() && distance[i][j] == 1 + distance[i + 1][j + 1] {
+            edits.push(Edit::Substitute(&ma[i], &mb[j]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < ma.len() && (j == mb.len() || distance[i][j] == 1 + distance[i + 1][j]) {
+            edits.push(Edit::Remove);
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(&mb[j]));
+            j += 1;
+        }
+    }
+    edits.extend((0..suffix).map(|_| Edit::Keep));
+    edits
+}
+
+/// align elements of `a` and `b` at the same index, rest of longer one is removed or inserted.
+fn align_index_wise<'a>(a: &'a [Value], b: &'a [Value]) -> Vec<Edit<'a>> {
+    let pairs = a.iter().zip(b).map(|(ea, eb)| if same(ea, eb) { Edit::Keep } else { Edit::Substitute(ea, eb) });
+    let removes = a.iter().skip(b.len()).map(|_| Edit::Remove);
+    let inserts = b.iter().skip(a.len()).map(Edit::Insert);
+    pairs.chain(removes).chain(inserts).collect()
+}
+
+/// compare json values, order of object keys is ignored.
+fn same(a: &Value, b: &Value) -> bool {
+    diff_value_lenient(a, b).is_empty()
+}
+
+fn get<'a>(doc: &'a Value, tokens: &[String]) -> Option<&'a Value> {
+    tokens.iter().try_fold(doc, |value, token| match value {
+        Value::Object(object) => object.get(&token[..]),
+        Value::Array(array) => array_index(token).and_then(|i| array.get(i)),
+        _ => None,
+    })
+}
+
+fn get_mut<'a>(doc: &'a mut Value, tokens: &[String]) -> Option<&'a mut Value> {
+    tokens.iter().try_fold(doc, |value, token| match value {
+        Value::Object(object) => object.get_mut(&token[..]),
+        Value::Array(array) => array_index(token).and_then(move |i| array.get_mut(i)),
+        _ => None,
+    })
+}
+
+fn add(doc: &mut Value, tokens: &[String], value: Value) -> Result<(), &'static str> {
+    let (last, parent) = match tokens.split_last() {
+        Some(split) => split,
+        None => {
+            *doc = value;
+            return Ok(());
+        }
+    };
+    match get_mut(doc, parent).ok_or("parent does not exist")? {
+        Value::Object(object) => match object.get_mut(&last[..]) {
+            Some(existing) => *existing = value,
+            None => {
+                object.insert(last[..].into(), value);
+            }
+        },
+        Value::Array(array) => {
+            let index = match &last[..] {
+                "-" => array.len(),
+                _ => array_index(last).filter(|&i| i <= array.len()).ok_or("array index out of range")?,
+            };
+            array.insert(index, value);
+        }
+        _ => return Err("parent is not object nor array"),
+    }
+    Ok(())
+}
+
+fn remove(doc: &mut Value, tokens: &[String]) -> Result<Value, &'static str> {
+    let (last, parent) = tokens.split_last().ok_or("root cannot be removed")?;
+    match get_mut(doc, parent).ok_or("path does not exist")? {
+        Value::Object(object) => object.remove(&last[..]).ok_or("path does not exist"),
+        Value::Array(array) => {
+            let index = array_index(last).filter(|&i| i < array.len()).ok_or("array index out of range")?;
+            Ok(array.remove(index))
+        }
+        _ => Err("path does not exist"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_json_eq;
+
+    fn patched(doc: &str, patch: &str) -> Result<Value, PatchError> {
+        let mut doc = Value::parse(doc).unwrap();
+        doc.apply_patch(&Patch::parse(Value::parse(patch).unwrap())?)?;
+        Ok(doc)
+    }
+
+    #[test]
+    fn test_apply_patch() {
+        for (doc, patch, expected) in [
+            (
+                r#"{"foo": "bar"}"#,
+                r#"[{"op": "add", "path": "/baz", "value": "qux"}]"#,
+                r#"{"baz": "qux", "foo": "bar"}"#,
+            ),
+            (
+                r#"{"foo": ["bar", "baz"]}"#,
+                r#"[{"op": "add", "path": "/foo/1", "value": "qux"}]"#,
+                r#"{"foo": ["bar", "qux", "baz"]}"#,
+            ),
+            (r#"{"baz": "qux", "foo": "bar"}"#, r#"[{"op": "remove", "path": "/baz"}]"#, r#"{"foo": "bar"}"#),
+            (
+                r#"{"foo": ["bar", "qux", "baz"]}"#,
+                r#"[{"op": "remove", "path": "/foo/1"}]"#,
+                r#"{"foo": ["bar", "baz"]}"#,
+            ),
+            (
+                r#"{"baz": "qux", "foo": "bar"}"#,
+                r#"[{"op": "replace", "path": "/baz", "value": "boo"}]"#,
+                r#"{"baz": "boo", "foo": "bar"}"#,
+            ),
+            (
+                r#"{"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}"#,
+                r#"[{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]"#,
+                r#"{"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}}"#,
+            ),
+            (
+                r#"{"foo": ["all", "grass", "cows", "eat"]}"#,
+                r#"[{"op": "move", "from": "/foo/1", "path": "/foo/3"}]"#,
+                r#"{"foo": ["all", "cows", "eat", "grass"]}"#,
+            ),
+            (
+                r#"{"foo": {"bar": [1]}}"#,
+                r#"[{"op": "copy", "from": "/foo/bar", "path": "/baz"}]"#,
+                r#"{"baz": [1], "foo": {"bar": [1]}}"#,
+            ),
+            (
+                r#"{"foo": ["bar"]}"#,
+                r#"[{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]"#,
+                r#"{"foo": ["bar", ["abc", "def"]]}"#,
+            ),
+            (
+                r#"{"/": 0, "~1": 1}"#,
+                r#"[{"op": "remove", "path": "/~01"}, {"op": "replace", "path": "/~1", "value": 2}]"#,
+                r#"{"/": 2}"#,
+            ),
+            (r#"{"foo": 1}"#, r#"[{"op": "replace", "path": "", "value": [1]}]"#, "[1]"),
+            (
+                r#"{"baz": "qux", "foo": ["a", 2, "c"]}"#,
+                r#"[{"op": "test", "path": "/foo", "value": ["a", 2, "c"]}]"#,
+                r#"{"baz": "qux", "foo": ["a", 2, "c"]}"#,
+            ),
+        ] {
+            assert_json_eq!(patched(doc, patch).unwrap(), Value::parse(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_apply_patch_error() {
+        let doc = r#"{"foo": ["bar"], "baz": "qux"}"#;
+        let patch = r#"[{"op": "remove", "path": "/baz"}, {"op": "test", "path": "/baz", "value": "qux"}]"#;
+        assert!(matches!(patched(doc, patch), Err(PatchError::Failed { index: 1, reason: "path does not exist", .. })));
+        let patch = r#"[{"op": "add", "path": "/a", "value": 1}, {"op": "test", "path": "/foo/0", "value": "x"}]"#;
+        assert!(matches!(patched(doc, patch), Err(PatchError::TestFailed { index: 1, .. })));
+        let patch = r#"[{"op": "add", "path": "/foo/2", "value": 1}]"#;
+        assert!(matches!(patched(doc, patch), Err(PatchError::Failed { reason: "array index out of range", .. })));
+        let patch = r#"[{"op": "add", "path": "/foo/01", "value": 1}]"#;
+        assert!(matches!(patched(doc, patch), Err(PatchError::Failed { reason: "array index out of range", .. })));
+        let patch = r#"[{"op": "move", "from": "/foo", "path": "/foo/0"}]"#;
+        assert!(matches!(patched(doc, patch), Err(PatchError::Failed { index: 0, .. })));
+
+        let mut json = Value::parse(doc).unwrap();
+        let patch = r#"[{"op": "remove", "path": "/baz"}, {"op": "remove", "path": "/nothing"}]"#;
+        assert!(json.apply_patch(&Patch::parse(Value::parse(patch).unwrap()).unwrap()).is_err());
+        assert_eq!(json, Value::parse(doc).unwrap());
+    }
+
+    #[test]
+    fn test_diff_as_patch() {
+        for (a, b, count) in [
+            (r#"{"a": 1, "b": [1, 2, 3]}"#, r#"{"a": 1, "b": [1, 2, 3]}"#, 0),
+            (r#"{"a": 1, "b": {"c": 2}}"#, r#"{"b": {"c": 3}, "a": 1}"#, 1),
+            ("[1, 2, 3, 4, 5]", "[1, 3, 4, 5, 6]", 2),
+            ("[1, 2, 3]", "[0, 1, 2, 3, 4]", 2),
+            (r#"[{"id": 1, "v": "a"}, {"id": 2, "v": "b"}]"#, r#"[{"id": 1, "v": "x"}, {"id": 2, "v": "b"}]"#, 1),
+            (r#"["a", "b", "c"]"#, r#"["c", "b", "a"]"#, 2),
+            ("[]", "[[], {}]", 2),
+            (r#"{"a/b": {"~": 1}}"#, r#"{"a/b": {"~": 2}, "": null}"#, 2),
+            ("1", r#"{"root": "replaced"}"#, 1),
+        ] {
+            let (a, b) = (Value::parse(a).unwrap(), Value::parse(b).unwrap());
+            let patch = diff_as_patch(&a, &b);
+            assert_eq!(patch.0.len(), count, "{}", Value::from(&patch));
+            let mut patched = a.clone();
+            patched.apply_patch(&patch).unwrap();
+            assert_json_eq!(patched, b);
+        }
+    }
+
+    #[test]
+    fn test_diff_as_patch_large_array() {
+        let a: Value = (0..2000).map(Value::Integer).collect();
+        let b: Value = (0..2000).map(|i| Value::Integer(1999 - i)).chain([Value::Null]).collect();
+        let patch = diff_as_patch(&a, &b);
+        assert_eq!(patch.0.len(), 2001);
+        let mut patched = a.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, b);
+
+        let patch = diff_as_patch(&b, &a);
+        assert_eq!(patch.0.len(), 2001);
+        let mut patched = b.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, a);
+    }
+
+    #[test]
+    fn test_parse_patch() {
+        let parse = |patch: &str| Patch::parse(Value::parse(patch).unwrap());
+        assert!(matches!(parse("{}"), Err(PatchError::NotArray { found: "Object" })));
+        assert!(matches!(
+            parse(r#"[{"op": "add", "path": "/a"}]"#),
+            Err(PatchError::InvalidOperation { index: 0, .. })
+        ));
+        let err = parse(r#"[{"op": "remove", "path": "/a"}, {"op": "jump", "path": "/a"}]"#).unwrap_err();
+        assert_eq!(err.to_string(), r#"operation 1 is invalid: unknown op "jump""#);
+        assert!(matches!(parse(r#"[{"op": "remove", "path": "a"}]"#), Err(PatchError::InvalidOperation { .. })));
+
+        let raw = r#"[{"op":"move","from":"/a","path":"/b"},{"op":"test","path":"/b","value":[1]}]"#;
+        let patch = parse(raw).unwrap();
+        assert_eq!(patch.0[0], Operation::Move { from: "/a".to_string(), path: "/b".to_string() });
+        assert_json_eq!(Value::from(&patch), Value::parse(raw).unwrap());
+        assert_eq!(Patch::parse(Value::from(&patch)).unwrap(), patch);
+    }
+}
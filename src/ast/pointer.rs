@@ -0,0 +1,151 @@
+//! JSON Pointer of [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901), such as `"/foo/0/bar"`. each reference
+//! token is escaped, `~0` means `~` and `~1` means `/`.
+
+use super::{
+    index::JsonIndexer,
+    index_path::{JsonPath, PathError},
+    Value,
+};
+
+impl Value {
+    /// access json value by json pointer. empty pointer point whole value, and pointer that does not start with
+    /// `/` point nothing. see [`Value::get`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"foo": [{"bar": 1}], "a/b": {"c~d": 2}}"#).unwrap();
+    ///
+    /// assert_eq!(json.pointer("/foo/0/bar"), Some(&Value::Integer(1)));
+    /// assert_eq!(json.pointer("/a~1b/c~0d"), Some(&Value::Integer(2)));
+    /// assert_eq!(json.pointer(""), Some(&json));
+    /// assert_eq!(json.pointer("/foo/1"), None);
+    /// assert_eq!(json.pointer("foo"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        tokens(pointer)?.iter().try_fold(self, |value, token| match value {
+            Value::Object(object) => object.get(&token[..]),
+            Value::Array(array) => array_index(token).and_then(|i| array.get(i)),
+            _ => None,
+        })
+    }
+
+    /// access json value by json pointer, and get mutable reference of it. see [`Value::pointer`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{"foo": [{"bar": 1}]}"#).unwrap();
+    /// *json.pointer_mut("/foo/0/bar").unwrap() = "one".into();
+    /// assert_eq!(json, Value::parse(r#"{"foo": [{"bar": "one"}]}"#).unwrap());
+    /// ```
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        tokens(pointer)?.iter().try_fold(self, |value, token| match value {
+            Value::Object(object) => object.get_mut(&token[..]),
+            Value::Array(array) => array_index(token).and_then(move |i| array.get_mut(i)),
+            _ => None,
+        })
+    }
+}
+
+impl JsonPath {
+    /// parse json pointer such as `/foo/0/bar`. json pointer does not distinguish object key from array index,
+    /// so reference token that can be array index, such as `0`, become [`JsonIndexer::ArrInd`].
+    /// # examples
+    /// ```
+    /// use dyson::{JsonIndexer, JsonPath};
+    /// let path = JsonPath::from_pointer("/foo/0/a~1b").unwrap();
+    /// assert_eq!(path.to_string(), r#""foo">0>"a/b""#);
+    /// assert_eq!(path.to_pointer(), "/foo/0/a~1b");
+    /// assert_eq!(JsonPath::from_pointer("").unwrap(), JsonPath::new());
+    /// assert!(JsonPath::from_pointer("foo").is_err());
+    /// ```
+    pub fn from_pointer(pointer: &str) -> Result<Self, PathError> {
+        let tokens = tokens(pointer).ok_or_else(|| PathError::InvalidPath {
+            path: pointer.to_string(),
+            pos: 0,
+            reason: "json pointer must start with `/`",
+        })?;
+        Ok(tokens
+            .into_iter()
+            .map(|token| match array_index(&token) {
+                Some(i) => JsonIndexer::ArrInd(i),
+                None => JsonIndexer::ObjInd(token),
+            })
+            .collect())
+    }
+
+    /// get json pointer that point same location as this path. see [`JsonPath::from_pointer`] also.
+    pub fn to_pointer(&self) -> String {
+        self.iter()
+            .map(|indexer| match indexer {
+                JsonIndexer::ObjInd(key) => format!("/{}", escape(key)),
+                JsonIndexer::ArrInd(i) => format!("/{i}"),
+            })
+            .collect()
+    }
+}
+
+/// escape `~` and `/` of reference token of json pointer.
+pub(crate) fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// split json pointer into unescaped reference tokens. if it is not json pointer, return `None`.
+pub(crate) fn tokens(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(Vec::new());
+    }
+    Some(pointer.strip_prefix('/')?.split('/').map(|token| token.replace("~1", "/").replace("~0", "~")).collect())
+}
+
+/// parse reference token as array index, leading zeros are not allowed.
+pub(crate) fn array_index(token: &str) -> Option<usize> {
+    match token.strip_prefix('0') {
+        Some(rest) if !rest.is_empty() => None,
+        _ if token.bytes().all(|b| b.is_ascii_digit()) => token.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer() {
+        let json = Value::parse(r#"{"a/b": {"c~d": [1, 2]}, "": 3, "~01": 4, "arr": [[5]]}"#).unwrap();
+        assert_eq!(json.pointer(""), Some(&json));
+        assert_eq!(json.pointer("/a~1b/c~0d/1"), Some(&Value::Integer(2)));
+        assert_eq!(json.pointer("/"), Some(&Value::Integer(3)));
+        assert_eq!(json.pointer("/~001"), Some(&Value::Integer(4)));
+        assert_eq!(json.pointer("/arr/0/0"), Some(&Value::Integer(5)));
+        assert_eq!(json.pointer("/a~1b/c~0d/2"), None);
+        assert_eq!(json.pointer("/a~1b/c~0d/01"), None);
+        assert_eq!(json.pointer("/a~1b/c~0d/-"), None);
+        assert_eq!(json.pointer("/arr/0/0/0"), None);
+        assert_eq!(json.pointer("a"), None);
+
+        let mut json = json;
+        json.pointer_mut("/arr/0").unwrap().take();
+        assert_eq!(json.pointer("/arr"), Some(&Value::parse("[null]").unwrap()));
+        assert_eq!(json.pointer_mut("/nothing"), None);
+    }
+
+    #[test]
+    fn test_pointer_path_conversion() {
+        let (key, i) = (|k: &str| JsonIndexer::ObjInd(k.to_string()), JsonIndexer::ArrInd);
+        for (pointer, path) in [
+            ("", JsonPath::new()),
+            ("/", JsonPath::from(&[key("")][..])),
+            ("/foo/0/bar", JsonPath::from(&[key("foo"), i(0), key("bar")][..])),
+            ("/a~1b/~0/01/-", JsonPath::from(&[key("a/b"), key("~"), key("01"), key("-")][..])),
+        ] {
+            assert_eq!(JsonPath::from_pointer(pointer).unwrap(), path);
+            assert_eq!(path.to_pointer(), pointer);
+        }
+        assert!(matches!(JsonPath::from_pointer("foo"), Err(PathError::InvalidPath { pos: 0, .. })));
+
+        let json = Value::parse(r#"{"a/b": [{"~": true}]}"#).unwrap();
+        let path = JsonPath::from_pointer("/a~1b/0/~0").unwrap();
+        assert_eq!(json.get(&path), json.pointer("/a~1b/0/~0"));
+    }
+}
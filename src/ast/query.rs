@@ -0,0 +1,630 @@
+//! small subset of [jq](https://jqlang.github.io/jq/manual/) expression, such as
+//! `.items[] | select(.active) | .name`. see [`Value::eval`] also.
+
+use super::{
+    map::{Key, Map},
+    number::{raw_number, Number},
+    Value,
+};
+use crate::syntax::{parse_number, parse_string, parser::ParserOptions};
+use std::cmp::Ordering;
+use thiserror::Error;
+
+/// [`QueryError`] is error of parsing or evaluating expression. see [`Value::eval`] also.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("invalid expression {expr:?} at {pos}: {reason}")]
+    Parse { expr: String, pos: usize, reason: &'static str },
+
+    #[error("cannot evaluate expression: {reason}")]
+    Eval { reason: String },
+}
+
+impl Value {
+    /// evaluate jq like expression, and get its outputs in order. supported expressions are
+    /// - path: `.`, `.foo`, `.["foo"]`, `.[0]`, `.[-1]`, and iteration `.[]`
+    /// - pipe `|` and comma `,`
+    /// - literal: string, number, `true`, `false`, `null`, array `[...]`, and object `{key: ...}`
+    /// - comparison `==`, `!=`, `<`, `<=`, `>`, `>=`, and logical `and`, `or`
+    /// - function: `select(f)`, `map(f)`, `has(key)`, `length`, `keys`, `type`, `not`, and `empty`
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"items": [
+    ///     {"name": "dyson", "active": true, "stars": 3},
+    ///     {"name": "serde", "active": false, "stars": 5},
+    ///     {"name": "jq", "active": true, "stars": 9}
+    /// ]}"#).unwrap();
+    ///
+    /// let names = json.eval(".items[] | select(.active) | .name").unwrap();
+    /// assert_eq!(names, vec![Value::from("dyson"), Value::from("jq")]);
+    ///
+    /// let popular = json.eval("[.items[] | select(.stars > 4) | {name, stars}]").unwrap();
+    /// assert_eq!(popular, vec![Value::parse(r#"[{"name": "serde", "stars": 5}, {"name": "jq", "stars": 9}]"#).unwrap()]);
+    ///
+    /// assert_eq!(json.eval(".items | map(.stars) | length").unwrap(), vec![Value::Integer(3)]);
+    /// assert!(json.eval(".items[").is_err());
+    /// ```
+    pub fn eval(&self, expr: &str) -> Result<Vec<Value>, QueryError> {
+        Expr::parse(expr)?.eval(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    Field(String),
+    Ident(String),
+    Literal(Value),
+    Symbol(&'static str),
+}
+
+/// split expression into tokens with its byte position.
+fn tokenize(expr: &str) -> Result<Vec<(usize, Token)>, QueryError> {
+    let err = |pos, reason| QueryError::Parse { expr: expr.to_string(), pos, reason };
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let take_while = |start: usize, f: &dyn Fn(char) -> bool| {
+        let end = expr[start..].find(|c| !f(c)).map_or(expr.len(), |len| start + len);
+        &expr[start..end]
+    };
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = expr[pos..].chars().next() {
+        let (token, len) = match c {
+            _ if c.is_whitespace() => (None, c.len_utf8()),
+            '.' => match take_while(pos + 1, &is_ident) {
+                "" if expr[pos + 1..].starts_with('.') => return Err(err(pos, "recursive descent is not supported")),
+                "" => (Some(Token::Dot), 1),
+                name => (Some(Token::Field(name.to_string())), 1 + name.len()),
+            },
+            '"' => {
+                let mut escaped = false;
+                let end = expr[pos + 1..].find(|c| {
+                    let closed = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closed
+                });
+                let literal = &expr[pos..pos + 2 + end.ok_or_else(|| err(pos, "unterminated string"))?];
+                let string = parse_string(literal).map_err(|_| err(pos, "invalid string"))?;
+                (Some(Token::Literal(Value::String(string))), literal.len())
+            }
+            '-' | '0'..='9' => {
+                let literal =
+                    &expr[pos..pos + 1 + take_while(pos + 1, &|c| c.is_ascii_digit() || ".eE+-".contains(c)).len()];
+                let number = parse_number(literal, ParserOptions::default()).map_err(|_| err(pos, "invalid number"))?;
+                (Some(Token::Literal(number)), literal.len())
+            }
+            _ if is_ident(c) => {
+                let ident = take_while(pos, &is_ident);
+                (Some(Token::Ident(ident.to_string())), ident.len())
+            }
+            _ => {
+                let symbols = ["==", "!=", "<=", ">=", "<", ">", "|", ",", ";", ":", "(", ")", "[", "]", "{", "}"];
+                let symbol = symbols.into_iter().find(|s| expr[pos..].starts_with(s));
+                let symbol = symbol.ok_or_else(|| err(pos, "unexpected character"))?;
+                (Some(Token::Symbol(symbol)), symbol.len())
+            }
+        };
+        tokens.extend(token.map(|t| (pos, t)));
+        pos += len;
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Identity,
+    Literal(Value),
+    Field(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Iterate(Box<Expr>),
+    Array(Option<Box<Expr>>),
+    Object(Vec<(String, Expr)>),
+    Pipe(Box<Expr>, Box<Expr>),
+    Comma(Box<Expr>, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+/// recursive descent parser of [`Expr`]. precedence is `|` < `,` < `or` < `and` < comparison < postfix.
+struct Parser<'a> {
+    expr: &'a str,
+    tokens: Vec<(usize, Token)>,
+    cursor: usize,
+}
+
+impl Expr {
+    fn parse(expr: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser { expr, tokens: tokenize(expr)?, cursor: 0 };
+        let parsed = parser.parse_pipe()?;
+        match parser.peek() {
+            Some(_) => Err(parser.err("unexpected token")),
+            None => Ok(parsed),
+        }
+    }
+}
+
+impl Parser<'_> {
+    fn err(&self, reason: &'static str) -> QueryError {
+        let pos = self.tokens.get(self.cursor).map_or(self.expr.len(), |(pos, _)| *pos);
+        QueryError::Parse { expr: self.expr.to_string(), pos, reason }
+    }
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.cursor).map(|(_, token)| token)
+    }
+    fn eat(&mut self, token: &Token) -> bool {
+        let matched = self.peek() == Some(token);
+        self.cursor += matched as usize;
+        matched
+    }
+    fn expect(&mut self, symbol: &'static str, reason: &'static str) -> Result<(), QueryError> {
+        match self.eat(&Token::Symbol(symbol)) {
+            true => Ok(()),
+            false => Err(self.err(reason)),
+        }
+    }
+
+    fn parse_pipe(&mut self) -> Result<Expr, QueryError> {
+        let mut lhs = self.parse_comma()?;
+        while self.eat(&Token::Symbol("|")) {
+            lhs = Expr::Pipe(Box::new(lhs), Box::new(self.parse_comma()?));
+        }
+        Ok(lhs)
+    }
+    fn parse_comma(&mut self) -> Result<Expr, QueryError> {
+        let mut lhs = self.parse_logical(0)?;
+        while self.eat(&Token::Symbol(",")) {
+            lhs = Expr::Comma(Box::new(lhs), Box::new(self.parse_logical(0)?));
+        }
+        Ok(lhs)
+    }
+    fn parse_logical(&mut self, level: usize) -> Result<Expr, QueryError> {
+        const OPERATORS: [&str; 2] = ["or", "and"];
+        let op = match OPERATORS.get(level) {
+            Some(&op) => op,
+            None => return self.parse_comparison(),
+        };
+        let mut lhs = self.parse_logical(level + 1)?;
+        while self.eat(&Token::Ident(op.to_string())) {
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.parse_logical(level + 1)?));
+        }
+        Ok(lhs)
+    }
+    fn parse_comparison(&mut self) -> Result<Expr, QueryError> {
+        let lhs = self.parse_postfix()?;
+        match self.peek() {
+            Some(&Token::Symbol(op @ ("==" | "!=" | "<" | "<=" | ">" | ">="))) => {
+                self.cursor += 1;
+                Ok(Expr::Binary(op, Box::new(lhs), Box::new(self.parse_postfix()?)))
+            }
+            _ => Ok(lhs),
+        }
+    }
+    fn parse_postfix(&mut self) -> Result<Expr, QueryError> {
+        let mut term = self.parse_primary()?;
+        loop {
+            if let Some(Token::Field(name)) = self.peek() {
+                term = Expr::Field(Box::new(term), name.clone());
+                self.cursor += 1;
+            } else if self.eat(&Token::Symbol("[")) {
+                if self.eat(&Token::Symbol("]")) {
+                    term = Expr::Iterate(Box::new(term));
+                } else {
+                    term = Expr::Index(Box::new(term), Box::new(self.parse_pipe()?));
+                    self.expect("]", "expected `]`")?;
+                }
+            } else {
+                return Ok(term);
+            }
+        }
+    }
+    fn parse_primary(&mut self) -> Result<Expr, QueryError> {
+        let token = self.peek().cloned().ok_or_else(|| self.err("expected expression"))?;
+        self.cursor += 1;
+        match token {
+            Token::Dot => Ok(Expr::Identity),
+            Token::Field(name) => Ok(Expr::Field(Box::new(Expr::Identity), name)),
+            Token::Literal(value) => Ok(Expr::Literal(value)),
+            Token::Ident(ident) => match &ident[..] {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ => self.parse_call(ident),
+            },
+            Token::Symbol("(") => {
+                let expr = self.parse_pipe()?;
+                self.expect(")", "expected `)`")?;
+                Ok(expr)
+            }
+            Token::Symbol("[") if self.eat(&Token::Symbol("]")) => Ok(Expr::Array(None)),
+            Token::Symbol("[") => {
+                let expr = self.parse_pipe()?;
+                self.expect("]", "expected `]`")?;
+                Ok(Expr::Array(Some(Box::new(expr))))
+            }
+            Token::Symbol("{") => self.parse_object(),
+            Token::Symbol(_) => {
+                self.cursor -= 1;
+                Err(self.err("expected expression"))
+            }
+        }
+    }
+    fn parse_call(&mut self, name: String) -> Result<Expr, QueryError> {
+        let start = self.cursor - 1;
+        let mut args = Vec::new();
+        if self.eat(&Token::Symbol("(")) {
+            args.push(self.parse_pipe()?);
+            while self.eat(&Token::Symbol(";")) {
+                args.push(self.parse_pipe()?);
+            }
+            self.expect(")", "expected `)`")?;
+        }
+        match (&name[..], args.len()) {
+            ("select" | "map" | "has", 1) | ("length" | "keys" | "type" | "not" | "empty", 0) => {
+                Ok(Expr::Call(name, args))
+            }
+            _ => {
+                self.cursor = start;
+                Err(self.err("unknown function"))
+            }
+        }
+    }
+    fn parse_object(&mut self) -> Result<Expr, QueryError> {
+        let mut entries = Vec::new();
+        while !self.eat(&Token::Symbol("}")) {
+            if !entries.is_empty() {
+                self.expect(",", "expected `,` or `}`")?;
+            }
+            let key = match self.peek() {
+                Some(Token::Ident(key) | Token::Literal(Value::String(key))) => key.clone(),
+                _ => return Err(self.err("expected key of object")),
+            };
+            self.cursor += 1;
+            let value = match self.eat(&Token::Symbol(":")) {
+                true => self.parse_postfix()?,
+                false => Expr::Field(Box::new(Expr::Identity), key.clone()),
+            };
+            entries.push((key, value));
+        }
+        Ok(Expr::Object(entries))
+    }
+}
+
+impl Expr {
+    fn eval(&self, input: &Value) -> Result<Vec<Value>, QueryError> {
+        match self {
+            Expr::Identity => Ok(vec![input.clone()]),
+            Expr::Literal(value) => Ok(vec![value.clone()]),
+            Expr::Field(term, name) => {
+                term.eval(input)?.iter().map(|value| index(value, &Value::from(&name[..]))).collect()
+            }
+            Expr::Index(term, index_expr) => {
+                let indices = index_expr.eval(input)?;
+                let values = term.eval(input)?;
+                values.iter().flat_map(|value| indices.iter().map(move |i| index(value, i))).collect()
+            }
+            Expr::Iterate(term) => {
+                let mut outputs = Vec::new();
+                for value in term.eval(input)? {
+                    outputs.extend(iterate(&value)?);
+                }
+                Ok(outputs)
+            }
+            Expr::Array(expr) => {
+                let elements = expr.as_ref().map_or(Ok(Vec::new()), |expr| expr.eval(input))?;
+                Ok(vec![Value::Array(elements)])
+            }
+            Expr::Object(entries) => {
+                let mut objects = vec![Map::new()];
+                for (key, expr) in entries {
+                    let values = expr.eval(input)?;
+                    objects = objects
+                        .into_iter()
+                        .flat_map(|object| {
+                            values.iter().map(move |value| {
+                                let mut object = object.clone();
+                                object.insert(Key::from(&key[..]), value.clone());
+                                object
+                            })
+                        })
+                        .collect();
+                }
+                Ok(objects.into_iter().map(|object| Value::Object(Box::new(object))).collect())
+            }
+            Expr::Pipe(lhs, rhs) => {
+                let mut outputs = Vec::new();
+                for value in lhs.eval(input)? {
+                    outputs.extend(rhs.eval(&value)?);
+                }
+                Ok(outputs)
+            }
+            Expr::Comma(lhs, rhs) => Ok(lhs.eval(input)?.into_iter().chain(rhs.eval(input)?).collect()),
+            Expr::Binary(op, lhs, rhs) => {
+                let (rhs, mut outputs) = (rhs.eval(input)?, Vec::new());
+                for l in lhs.eval(input)? {
+                    match *op {
+                        "and" if !truthy(&l) => outputs.push(Value::Bool(false)),
+                        "or" if truthy(&l) => outputs.push(Value::Bool(true)),
+                        "and" | "or" => outputs.extend(rhs.iter().map(|r| Value::Bool(truthy(r)))),
+                        _ => outputs.extend(rhs.iter().map(|r| Value::Bool(compare(op, &l, r)))),
+                    }
+                }
+                Ok(outputs)
+            }
+            Expr::Call(name, args) => call(name, args, input),
+        }
+    }
+}
+
+fn call(name: &str, args: &[Expr], input: &Value) -> Result<Vec<Value>, QueryError> {
+    let err = |reason: String| QueryError::Eval { reason };
+    match name {
+        "select" => {
+            let selected = args[0].eval(input)?.iter().filter(|&v| truthy(v)).count();
+            Ok(vec![input.clone(); selected])
+        }
+        "map" => {
+            let mut outputs = Vec::new();
+            for value in iterate(input)? {
+                outputs.extend(args[0].eval(&value)?);
+            }
+            Ok(vec![Value::Array(outputs)])
+        }
+        "has" => args[0]
+            .eval(input)?
+            .iter()
+            .map(|key| match (input, key) {
+                (Value::Object(object), Value::String(key)) => Ok(Value::Bool(object.contains_key(&key[..]))),
+                (Value::Array(array), key) if key.is_number() => {
+                    // index must be non negative integer, such as `has(-1)` and `has(1.5)` are false
+                    let index = number(key);
+                    Ok(Value::Bool(index >= 0. && index.fract() == 0. && index < array.len() as f64))
+                }
+                _ => Err(err(format!("cannot check whether {} has {}", input.node_type(), key.node_type()))),
+            })
+            .collect(),
+        "length" => match input {
+            Value::Null => Ok(vec![Value::Integer(0)]),
+            Value::Bool(_) => Err(err("Bool has no length".to_string())),
+            Value::String(s) => Ok(vec![Value::Integer(s.chars().count() as i64)]),
+            Value::Array(array) => Ok(vec![Value::Integer(array.len() as i64)]),
+            Value::Object(object) => Ok(vec![Value::Integer(object.len() as i64)]),
+            Value::Integer(i) => Ok(vec![i
+                .checked_abs()
+                .map_or_else(|| Value::RawNumber(raw_number(i.unsigned_abs().to_string())), Value::Integer)]),
+            number_value => Ok(vec![Value::Float(number(number_value).abs())]),
+        },
+        "keys" => match input {
+            Value::Object(object) => {
+                let mut keys: Vec<_> = object.keys().map(|k| &k[..]).collect();
+                keys.sort_unstable();
+                Ok(vec![keys.into_iter().map(Value::from).collect()])
+            }
+            Value::Array(array) => Ok(vec![(0..array.len() as i64).map(Value::Integer).collect()]),
+            _ => Err(err(format!("{} has no keys", input.node_type()))),
+        },
+        "type" => {
+            let name = match input {
+                Value::Object(_) => "object",
+                Value::Array(_) => "array",
+                Value::Bool(_) => "boolean",
+                Value::Null => "null",
+                Value::String(_) => "string",
+                Value::Integer(_) | Value::Float(_) | Value::RawNumber(_) => "number",
+            };
+            Ok(vec![Value::from(name)])
+        }
+        "not" => Ok(vec![Value::Bool(!truthy(input))]),
+        "empty" => Ok(Vec::new()),
+        _ => unreachable!("parser accept known function only"),
+    }
+}
+
+/// get `value[index]`. like jq, index of `null` is `null`, and out of range index of array is also `null`.
+fn index(value: &Value, index: &Value) -> Result<Value, QueryError> {
+    match (value, index) {
+        (Value::Null, Value::String(_)) => Ok(Value::Null),
+        (Value::Null, i) if i.is_number() => Ok(Value::Null),
+        (Value::Object(object), Value::String(key)) => Ok(object.get(&key[..]).cloned().unwrap_or(Value::Null)),
+        (Value::Array(array), i) if i.is_number() => {
+            let i = number(i).floor();
+            let i = if i < 0. { array.len() as f64 + i } else { i };
+            Ok((i >= 0.).then(|| array.get(i as usize)).flatten().cloned().unwrap_or(Value::Null))
+        }
+        _ => {
+            let reason = format!("cannot index {} with {}", value.node_type(), index.node_type());
+            Err(QueryError::Eval { reason })
+        }
+    }
+}
+
+/// get elements of array or values of object.
+fn iterate(value: &Value) -> Result<Vec<Value>, QueryError> {
+    match value {
+        Value::Array(array) => Ok(array.clone()),
+        Value::Object(object) => Ok(object.values().cloned().collect()),
+        _ => Err(QueryError::Eval { reason: format!("cannot iterate over {}", value.node_type()) }),
+    }
+}
+
+/// like jq, only `false` and `null` are false.
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Bool(false) | Value::Null)
+}
+
+fn number(value: &Value) -> f64 {
+    match value {
+        Value::Integer(i) => *i as f64,
+        Value::Float(f) => *f,
+        Value::RawNumber(raw) => raw.as_f64().unwrap_or(f64::NAN),
+        _ => f64::NAN,
+    }
+}
+
+fn compare(op: &str, a: &Value, b: &Value) -> bool {
+    let ordering = order(a, b);
+    match op {
+        "==" => ordering == Ordering::Equal,
+        "!=" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        _ => unreachable!("parser accept comparison operator only"),
+    }
+}
+
+/// total order of jq, `null` < `false` < `true` < number < string < array < object. number is compared by its exact
+/// value, so `1` and `1.0` are equal.
+pub(crate) fn order(a: &Value, b: &Value) -> Ordering {
+    let rank = |value: &Value| match value {
+        Value::Null => 0,
+        Value::Bool(false) => 1,
+        Value::Bool(true) => 2,
+        Value::Integer(_) | Value::Float(_) | Value::RawNumber(_) => 3,
+        Value::String(_) => 4,
+        Value::Array(_) => 5,
+        Value::Object(_) => 6,
+    };
+    match (a, b) {
+        (Value::String(sa), Value::String(sb)) => sa.cmp(sb),
+        (Value::Array(va), Value::Array(vb)) => {
+            va.iter().zip(vb).map(|(ea, eb)| order(ea, eb)).find(|o| o.is_ne()).unwrap_or(va.len().cmp(&vb.len()))
+        }
+        (Value::Object(oa), Value::Object(ob)) => {
+            fn sorted_keys(object: &Map<Key, Value>) -> Vec<&str> {
+                let mut keys: Vec<_> = object.keys().map(|k| &k[..]).collect();
+                keys.sort_unstable();
+                keys
+            }
+            let (ka, kb) = (sorted_keys(oa), sorted_keys(ob));
+            ka.cmp(&kb)
+                .then_with(|| ka.iter().map(|k| order(&oa[*k], &ob[*k])).find(|o| o.is_ne()).unwrap_or(Ordering::Equal))
+        }
+        _ => match (a.number(), b.number()) {
+            // compare exact value instead of `f64` to distinguish large integers, and NaN is greater than any number
+            (Some(na), Some(nb)) => {
+                let is_nan = |n: Number| matches!(n, Number::Float(f) if f.is_nan());
+                na.partial_cmp(&nb).unwrap_or_else(|| is_nan(na).cmp(&is_nan(nb)))
+            }
+            _ => rank(a).cmp(&rank(b)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(json: &str, expr: &str) -> Vec<Value> {
+        Value::parse(json).unwrap().eval(expr).unwrap_or_else(|e| panic!("{expr:?}: {e}"))
+    }
+    fn values(jsons: &[&str]) -> Vec<Value> {
+        jsons.iter().map(|&json| Value::parse(json).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_eval_path() {
+        let json = r#"{"a": {"b": [1, 2, {"c": "d"}]}, "e f": true}"#;
+        assert_eq!(eval(json, "."), values(&[json]));
+        assert_eq!(eval(json, ".a.b[2].c"), values(&[r#""d""#]));
+        assert_eq!(eval(json, r#".["e f"]"#), values(&["true"]));
+        assert_eq!(eval(json, ".a.b[-1]"), values(&[r#"{"c": "d"}"#]));
+        assert_eq!(eval(json, ".a.b[5]"), values(&["null"]));
+        assert_eq!(eval(json, ".nothing.deep"), values(&["null"]));
+        assert_eq!(eval(json, ".a.b[]"), values(&["1", "2", r#"{"c": "d"}"#]));
+        assert_eq!(eval(json, ".a[]"), values(&[r#"[1, 2, {"c": "d"}]"#]));
+        assert_eq!(eval(json, ".a.b[0, 1]"), values(&["1", "2"]));
+        assert_eq!(eval(json, ".a.b | .[1]"), values(&["2"]));
+    }
+
+    #[test]
+    fn test_eval_construction() {
+        let json = r#"{"user": "dyson", "titles": ["a", "b"]}"#;
+        assert_eq!(eval(json, "[.titles[], .user]"), values(&[r#"["a", "b", "dyson"]"#]));
+        assert_eq!(eval(json, "[]"), values(&["[]"]));
+        assert_eq!(
+            eval(json, r#"{user, "title": .titles[]}"#),
+            values(&[r#"{"user": "dyson", "title": "a"}"#, r#"{"user": "dyson", "title": "b"}"#])
+        );
+        assert_eq!(
+            eval(json, r#"1, -2.5, "s", true, false, null"#),
+            values(&["1", "-2.5", r#""s""#, "true", "false", "null"])
+        );
+    }
+
+    #[test]
+    fn test_eval_operator() {
+        let json = r#"[{"n": 1, "ok": true}, {"n": 2, "ok": false}, {"n": 3, "ok": null}, {"n": 4.0, "ok": "yes"}]"#;
+        assert_eq!(eval(json, "[.[] | select(.ok) | .n]"), values(&["[1, 4.0]"]));
+        assert_eq!(eval(json, "[.[] | select(.n >= 2 and .n < 4) | .n]"), values(&["[2, 3]"]));
+        assert_eq!(eval(json, "[.[] | select(.n == 1 or .ok == null) | .n]"), values(&["[1, 3]"]));
+        assert_eq!(eval(json, "[.[] | select(.ok | not) | .n]"), values(&["[2, 3]"]));
+        assert_eq!(eval(json, ".[3].n == 4, .[0].n != 1"), values(&["true", "false"]));
+        assert_eq!(eval("null", r#"null < false, false < 0, 0 < "", "" < [], [] < {}"#), values(&["true"; 5]));
+        assert_eq!(
+            eval("null", r#"[1, 2] < [1, 3], {"a": 1} == {"a": 1.0}, "a" > "b""#),
+            values(&["true", "true", "false"])
+        );
+    }
+
+    #[test]
+    fn test_order_large_number() {
+        let (a, b) = (Value::Integer(9007199254740992), Value::Integer(9007199254740993));
+        assert_eq!(order(&a, &b), Ordering::Less);
+        assert_eq!(order(&b, &Value::Float(9007199254740992.)), Ordering::Greater);
+        assert_eq!(order(&a, &Value::Float(9007199254740992.)), Ordering::Equal);
+        assert_eq!(order(&Value::Float(f64::NAN), &Value::Float(f64::INFINITY)), Ordering::Greater);
+        assert_eq!(
+            eval("[9007199254740992, 9007199254740993]", ".[0] == .[1], .[0] < .[1]"),
+            values(&["false", "true"])
+        );
+    }
+
+    #[test]
+    fn test_eval_function() {
+        let json = r#"{"b": [1, "two", null], "a": {"x": 1}, "s": "日本"}"#;
+        assert_eq!(eval(json, "keys, (.b | keys)"), values(&[r#"["a", "b", "s"]"#, "[0, 1, 2]"]));
+        assert_eq!(eval(json, "length, (.b, .a, .s, .b[2], -3 | length)"), values(&["3", "3", "1", "2", "0", "3"]));
+        assert_eq!(eval(json, ".b | map(type)"), values(&[r#"["number", "string", "null"]"#]));
+        assert_eq!(eval(json, ".a | map(. == 1)"), values(&["[true]"]));
+        assert_eq!(
+            eval(json, r#"has("a"), has("z"), (.b | has(2), has(3))"#),
+            values(&["true", "false", "true", "false"])
+        );
+        assert_eq!(
+            eval(json, ".b | has(-1), has(-0.5), has(1.5), has(1.0), has(2.5)"),
+            values(&["false", "false", "false", "true", "false"])
+        );
+        assert_eq!(eval(json, "[.b[] | empty]"), values(&["[]"]));
+
+        let min = Value::Integer(i64::MIN).eval("length").unwrap();
+        assert_eq!(min, vec![Value::RawNumber(raw_number("9223372036854775808".to_string()))]);
+    }
+
+    #[test]
+    fn test_eval_error() {
+        for (invalid, pos) in [
+            ("", 0),
+            (".a |", 4),
+            (".a[", 3),
+            ("(.a", 3),
+            (".a b", 3),
+            ("..", 0),
+            (r#"."unterminated"#, 1),
+            ("undefined(.)", 0),
+            ("select", 0),
+            ("{1: 2}", 1),
+            ("@base64", 0),
+        ] {
+            match Value::Null.eval(invalid) {
+                Err(QueryError::Parse { pos: p, .. }) => assert_eq!(p, pos, "{invalid:?}"),
+                result => panic!("{invalid:?} must be parse error, but {result:?}"),
+            }
+        }
+        let json = Value::parse(r#"{"a": [1], "b": true}"#).unwrap();
+        for invalid in [".a.b", ".b[0]", ".b[]", ".b | length", ".b | keys", r#".a | has("x")"#] {
+            assert!(matches!(json.eval(invalid), Err(QueryError::Eval { .. })), "{invalid:?}");
+        }
+    }
+}
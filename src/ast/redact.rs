@@ -0,0 +1,151 @@
+//! redaction of [`Value`] at paths that match glob patterns, to mask secrets such as tokens and passwords before
+//! logging or dumping. pattern is dotted path parsed by [`JsonPath::from_dotted`], and its key can be wildcard.
+//! - `*` match any one key of object or index of array, such as `users.*.token`.
+//! - `**` match any number of keys and indices including zero, such as `**.password`.
+
+use super::{
+    index::{JsonIndexer, JsonIndexerRef},
+    index_path::{JsonPath, PathError},
+    Value,
+};
+use itertools::Itertools;
+
+impl Value {
+    /// replace values at paths that match any of `patterns` with `replacement`, and get number of replaced values.
+    /// if matched value contain other matched values, only outer one is replaced. if any pattern is invalid, this
+    /// value is not changed and return error. see [module document](self) for pattern syntax.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"{
+    ///     "user": {"name": "dyson", "password": "hunter2"},
+    ///     "tokens": [{"id": 1, "secret": "abc"}, {"id": 2, "secret": "def"}],
+    ///     "password": "root"
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(json.redact(&["**.password", "tokens.*.secret"], Value::from("***")), Ok(4));
+    /// assert_eq!(json, Value::parse(r#"{
+    ///     "user": {"name": "dyson", "password": "***"},
+    ///     "tokens": [{"id": 1, "secret": "***"}, {"id": 2, "secret": "***"}],
+    ///     "password": "***"
+    /// }"#).unwrap());
+    /// ```
+    pub fn redact<S: AsRef<str>>(&mut self, patterns: &[S], replacement: Value) -> Result<usize, PathError> {
+        let patterns: Vec<_> = patterns.iter().map(|p| JsonPath::from_dotted(p.as_ref())).collect::<Result<_, _>>()?;
+        let patterns: Vec<Vec<_>> = patterns.into_iter().map(|p| p.into_iter().map(Glob::from).collect()).collect();
+        let start = patterns.iter().enumerate().flat_map(|(i, p)| closure(p, i, 0)).collect();
+        Ok(redact_states(self, &patterns, start, &replacement))
+    }
+}
+
+enum Glob {
+    Indexer(JsonIndexer),
+    Any,
+    AnyDepth,
+}
+impl From<JsonIndexer> for Glob {
+    fn from(indexer: JsonIndexer) -> Self {
+        match indexer {
+            JsonIndexer::ObjInd(k) if k == "*" => Glob::Any,
+            JsonIndexer::ObjInd(k) if k == "**" => Glob::AnyDepth,
+            indexer => Glob::Indexer(indexer),
+        }
+    }
+}
+
+/// states that can be reached from `(i, pos)` without consuming indexer, because `**` can match nothing.
+fn closure(pattern: &[Glob], i: usize, pos: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let skipped = pattern[pos..].iter().take_while(|g| matches!(g, Glob::AnyDepth)).count();
+    (pos..=pos + skipped).map(move |p| (i, p))
+}
+
+/// redact `value` by walking it with states of each patterns, this function does not recurse, so it can be used for
+/// deeply nested value. state `(i, pos)` means `patterns[i][..pos]` is matched.
+fn redact_states(value: &mut Value, patterns: &[Vec<Glob>], start: Vec<(usize, usize)>, replacement: &Value) -> usize {
+    let next = |states: &[(usize, usize)], indexer: JsonIndexerRef| -> Vec<_> {
+        states
+            .iter()
+            .flat_map(|&(i, pos)| match &patterns[i][pos] {
+                Glob::AnyDepth => closure(&patterns[i], i, pos).collect(),
+                Glob::Any => closure(&patterns[i], i, pos + 1).collect(),
+                Glob::Indexer(ind) if JsonIndexerRef::from(ind) == indexer => {
+                    closure(&patterns[i], i, pos + 1).collect()
+                }
+                Glob::Indexer(_) => Vec::new(),
+            })
+            .sorted()
+            .dedup()
+            .collect()
+    };
+    let (mut redacted, mut stack) = (0, vec![(value, start)]);
+    while let Some((value, states)) = stack.pop() {
+        if states.iter().any(|&(i, pos)| patterns[i].len() == pos) {
+            *value = replacement.clone();
+            redacted += 1;
+            continue;
+        }
+        match value {
+            Value::Object(m) => {
+                for (k, v) in m.iter_mut() {
+                    let states = next(&states, JsonIndexerRef::ObjInd(k));
+                    if !states.is_empty() {
+                        stack.push((v, states));
+                    }
+                }
+            }
+            Value::Array(a) => {
+                for (i, e) in a.iter_mut().enumerate() {
+                    let states = next(&states, JsonIndexerRef::ArrInd(i));
+                    if !states.is_empty() {
+                        stack.push((e, states));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_glob() {
+        let json = Value::parse(r#"{"a": {"b": [{"c": 1}, {"c": 2, "d": {"c": 3}}]}, "c": 4, "e": [5, 6]}"#).unwrap();
+        for (patterns, count, expected) in [
+            (vec!["c"], 1, r#"{"a": {"b": [{"c": 1}, {"c": 2, "d": {"c": 3}}]}, "c": 0, "e": [5, 6]}"#),
+            (vec!["a.b[1].c", "e[0]"], 2, r#"{"a": {"b": [{"c": 1}, {"c": 0, "d": {"c": 3}}]}, "c": 4, "e": [0, 6]}"#),
+            (vec!["a.b.*.c"], 2, r#"{"a": {"b": [{"c": 0}, {"c": 0, "d": {"c": 3}}]}, "c": 4, "e": [5, 6]}"#),
+            (vec!["**.c"], 4, r#"{"a": {"b": [{"c": 0}, {"c": 0, "d": {"c": 0}}]}, "c": 0, "e": [5, 6]}"#),
+            (vec!["a.**.d.c", "*"], 3, r#"{"a": 0, "c": 0, "e": 0}"#),
+            (vec!["a.**"], 1, r#"{"a": 0, "c": 4, "e": [5, 6]}"#),
+            (vec!["**.**.d", "**"], 1, "0"),
+            (vec!["x.**", "a.b[2]", "c.d"], 0, &json.to_string()),
+        ] {
+            let mut redacted = json.clone();
+            assert_eq!(redacted.redact(&patterns, Value::Integer(0)), Ok(count), "{patterns:?}");
+            assert_eq!(redacted, Value::parse(expected).unwrap(), "{patterns:?}");
+        }
+
+        let mut deep = Value::from("secret");
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.redact(&["**"], Value::Null), Ok(1));
+        let mut deep: Value = [("password", Value::from("secret"))].into_iter().collect();
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.redact(&["**.password"], Value::Null), Ok(1));
+        assert_eq!(deep.visitor().next(), Some(&Value::Null));
+    }
+
+    #[test]
+    fn test_redact_invalid_pattern() {
+        let mut json = Value::parse(r#"{"password": "secret"}"#).unwrap();
+        assert!(matches!(json.redact(&["password", "a..b"], Value::Null), Err(PathError::InvalidPath { .. })));
+        assert_eq!(json["password"], Value::from("secret"));
+    }
+}
@@ -0,0 +1,220 @@
+use super::{index::JsonIndexer, index_path::JsonPath, Value};
+use crate::Error;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// [`RefError`] is error of resolving `$ref`. see [`Value::load_bundled`] also.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RefError {
+    #[error("circular reference: {}", chain.join(" -> "))]
+    Cycle { chain: Vec<String> },
+
+    #[error("cannot resolve reference {}", reference)]
+    Unresolvable { reference: String },
+
+    #[error("unsupported reference {}, only local file is supported", reference)]
+    Unsupported { reference: String },
+}
+
+impl Value {
+    /// collect strings that look like reference to other json document, with their paths. a string is reference if
+    /// - it is the value of `"$ref"` key, or
+    /// - it is json pointer fragment such as `"#/definitions/foo"`, or
+    /// - it is uri with scheme such as `"file://path/to/schema.json"` or `"https://example.com/schema.json"`.
+    ///
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let raw_json = r##"{
+    ///     "links": ["https://example.com/schema.json", "not reference"],
+    ///     "properties": {
+    ///         "address": { "$ref": "address.json" },
+    ///         "owner": { "$ref": "#/definitions/user" }
+    ///     }
+    /// }"##;
+    /// let json = Value::parse(raw_json).unwrap();
+    ///
+    /// let refs: Vec<_> = json.collect_refs().into_iter().map(|(path, r)| (path.to_string(), r)).collect();
+    /// assert_eq!(refs, vec![
+    ///     (r#""links">0"#.to_string(), "https://example.com/schema.json"),
+    ///     (r#""properties">"address">"$ref""#.to_string(), "address.json"),
+    ///     (r#""properties">"owner">"$ref""#.to_string(), "#/definitions/user"),
+    /// ]);
+    /// ```
+    pub fn collect_refs(&self) -> Vec<(JsonPath, &str)> {
+        fn collect_refs_recursive<'a>(value: &'a Value, path: &mut JsonPath, refs: &mut Vec<(JsonPath, &'a str)>) {
+            match value {
+                Value::Object(m) => {
+                    for (k, v) in m.iter() {
+                        path.push(JsonIndexer::ObjInd(k.to_string()));
+                        match v {
+                            Value::String(s) if &k[..] == "$ref" => refs.push((path.clone(), s)),
+                            v => collect_refs_recursive(v, path, refs),
+                        }
+                        path.pop();
+                    }
+                }
+                Value::Array(v) => {
+                    for (i, e) in v.iter().enumerate() {
+                        path.push(JsonIndexer::ArrInd(i));
+                        collect_refs_recursive(e, path, refs);
+                        path.pop();
+                    }
+                }
+                Value::String(s) if is_reference(s) => refs.push((path.clone(), s)),
+                _ => (),
+            }
+        }
+        let mut refs = Vec::new();
+        collect_refs_recursive(self, &mut JsonPath::new(), &mut refs);
+        refs
+    }
+}
+
+impl Value {
+    /// load json file specified by path, and replace each object that has `"$ref"` with the referenced value,
+    /// recursively. referenced documents are loaded relative to the referencing document and cached, so each file
+    /// is read once. reference is `"other.json"`, `"other.json#/json/pointer"`, `"#/json/pointer"`, or
+    /// `"file:///absolute/path.json"`. circular reference cause [`Error::Ref`] of [`RefError::Cycle`].
+    /// # examples
+    /// ```no_run
+    /// use dyson::Value;
+    /// // `path/to/root.json`: { "user": { "$ref": "definitions.json#/user" } }
+    /// // `path/to/definitions.json`: { "user": { "type": "object" } }
+    /// let json = Value::load_bundled("path/to/root.json").unwrap();
+    /// assert_eq!(json.to_string(), r#"{"user":{"type":"object"}}"#);
+    /// ```
+    pub fn load_bundled<P: AsRef<Path>>(p: P) -> Result<Value, Error> {
+        let mut bundler = Bundler { documents: HashMap::new(), resolving: Vec::new() };
+        bundler.resolve(&p.as_ref().canonicalize()?, "")
+    }
+}
+
+struct Bundler {
+    documents: HashMap<PathBuf, Value>,
+    resolving: Vec<String>,
+}
+impl Bundler {
+    /// resolve value that is pointed by `pointer` in `file`.
+    fn resolve(&mut self, file: &Path, pointer: &str) -> Result<Value, Error> {
+        let key = format!("{}#{}", file.display(), pointer);
+        if self.resolving.contains(&key) {
+            let mut chain: Vec<_> = self.resolving.iter().skip_while(|r| r != &&key).cloned().collect();
+            chain.push(key);
+            return Err(RefError::Cycle { chain })?;
+        }
+        if !self.documents.contains_key(file) {
+            self.documents.insert(file.to_path_buf(), Value::load(file)?);
+        }
+        let target = self.documents[file].pointer(pointer).ok_or(RefError::Unresolvable { reference: key.clone() })?;
+        let target = target.clone();
+        self.resolving.push(key);
+        let stitched = self.stitch(target, file);
+        self.resolving.pop();
+        stitched
+    }
+
+    /// replace objects that has `"$ref"` in `value` with referenced value. `base` is file that has `value`.
+    fn stitch(&mut self, mut value: Value, base: &Path) -> Result<Value, Error> {
+        let refs: Vec<_> = value
+            .collect_refs()
+            .into_iter()
+            .filter_map(|(path, reference)| match path.split_last() {
+                Some((parent, JsonIndexer::ObjInd(k))) if k == "$ref" => Some((parent, reference.to_string())),
+                _ => None,
+            })
+            .collect();
+        for (parent, reference) in refs {
+            let (file, pointer) = match reference.split_once('#') {
+                Some((file, pointer)) => (file, pointer),
+                None => (&reference[..], ""),
+            };
+            let file = if file.is_empty() {
+                base.to_path_buf()
+            } else if let Some(path) = file.strip_prefix("file://") {
+                PathBuf::from(path).canonicalize()?
+            } else if file.contains("://") {
+                return Err(RefError::Unsupported { reference })?;
+            } else {
+                base.parent().unwrap_or_else(|| Path::new("")).join(file).canonicalize()?
+            };
+            let resolved = self.resolve(&file, pointer)?;
+            if let Some(target) = value.get_mut(&parent) {
+                *target = resolved;
+            }
+        }
+        Ok(value)
+    }
+}
+
+/// check `s` is json pointer fragment or uri with scheme.
+fn is_reference(s: &str) -> bool {
+    if s == "#" || s.starts_with("#/") {
+        true
+    } else if let Some((scheme, _)) = s.split_once("://") {
+        let mut chars = scheme.chars();
+        chars.next().map_or(false, |c| c.is_ascii_alphabetic())
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_reference() {
+        for reference in
+            ["#", "#/", "#/definitions/foo", "file:///etc/schema.json", "http://example.com", "git+ssh://x"]
+        {
+            assert!(is_reference(reference), "{reference:?} must be reference");
+        }
+        for not_reference in ["", "#foo", "plain text", "://no-scheme", "1http://x", "mail to: a@b"] {
+            assert!(!is_reference(not_reference), "{not_reference:?} must not be reference");
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_load_bundled() {
+        let result = || -> anyhow::Result<()> {
+            let dir = tempfile::tempdir()?;
+            std::fs::create_dir(dir.path().join("defs"))?;
+            std::fs::write(
+                dir.path().join("root.json"),
+                r##"{"user": {"$ref": "defs/user.json"}, "ids": [{"$ref": "#/definitions/id"}], "definitions": {"id": 1}}"##,
+            )?;
+            std::fs::write(dir.path().join("defs/user.json"), r##"{"name": {"$ref": "common.json#/string"}}"##)?;
+            std::fs::write(dir.path().join("defs/common.json"), r##"{"string": {"type": "string"}}"##)?;
+            let json = Value::load_bundled(dir.path().join("root.json"))?;
+            assert_eq!(json.to_string(), r#"{"user":{"name":{"type":"string"}},"ids":[1],"definitions":{"id":1}}"#);
+
+            std::fs::write(dir.path().join("a.json"), r##"{"b": {"$ref": "b.json"}}"##)?;
+            std::fs::write(dir.path().join("b.json"), r##"{"a": {"$ref": "a.json#/b"}}"##)?;
+            let err = Value::load_bundled(dir.path().join("a.json")).unwrap_err();
+            assert!(matches!(err, Error::Ref(RefError::Cycle { chain }) if chain.len() == 3));
+
+            std::fs::write(dir.path().join("c.json"), r##"{"c": {"$ref": "#/nothing"}}"##)?;
+            let err = Value::load_bundled(dir.path().join("c.json")).unwrap_err();
+            assert!(matches!(err, Error::Ref(RefError::Unresolvable { .. })));
+            Ok(())
+        };
+        result().unwrap();
+    }
+
+    #[test]
+    fn test_collect_refs() {
+        let json = Value::parse(r##"[{"$ref": "a.json"}, {"$ref": 1}, {"nested": {"$ref": "#"}}, "#/0"]"##).unwrap();
+        let refs: Vec<_> = json.collect_refs().into_iter().map(|(p, r)| (p.to_string(), r)).collect();
+        assert_eq!(
+            refs,
+            vec![("0>\"$ref\"".into(), "a.json"), ("2>\"nested\">\"$ref\"".into(), "#"), ("3".into(), "#/0")]
+        );
+        assert!(Value::parse(r#"{"$refs": "a.json"}"#).unwrap().collect_refs().is_empty());
+    }
+}
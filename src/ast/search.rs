@@ -0,0 +1,178 @@
+use super::{diff::diff_value_lenient, index::JsonIndexer, index_path::JsonPath, visit::DfsEvent, Value};
+
+impl Value {
+    /// check this value or one of its descendants is equal to `value`. order of object keys is ignored.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"users": [{"name": "dyson", "tags": {"a": 1, "b": 2}}]}"#).unwrap();
+    ///
+    /// assert!(json.contains(&Value::from("dyson")));
+    /// assert!(json.contains(&Value::parse(r#"{"b": 2, "a": 1}"#).unwrap()));
+    /// assert!(json.contains(&json));
+    /// assert!(!json.contains(&Value::from("users")));
+    /// ```
+    pub fn contains(&self, value: &Value) -> bool {
+        let mut found = false;
+        self.walk(|event| {
+            if let DfsEvent::Visit(v) = event {
+                found = v.node_type() == value.node_type() && diff_value_lenient(v, value).is_empty();
+            }
+            !found
+        });
+        found
+    }
+
+    /// check this value or one of its descendants is object that has `key`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"users": [{"name": "dyson"}]}"#).unwrap();
+    ///
+    /// assert!(json.contains_key_recursive("name"));
+    /// assert!(!json.contains_key_recursive("dyson"));
+    /// ```
+    pub fn contains_key_recursive(&self, key: &str) -> bool {
+        let mut found = false;
+        self.walk(|event| {
+            if let DfsEvent::Visit(Value::Object(m)) = event {
+                found = m.contains_key(key);
+            }
+            !found
+        });
+        found
+    }
+
+    /// find all values of `key` in this value and its descendants, with their paths in dfs order.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"id": 1, "children": [{"id": 2}, {"name": "leaf", "meta": {"id": 3}}]}"#).unwrap();
+    ///
+    /// let mut ids: Vec<_> = json.find_key("id").into_iter().map(|(path, v)| (path.to_string(), v)).collect();
+    /// ids.sort(); // dfs order depend on order of keys
+    /// assert_eq!(ids, vec![
+    ///     (r#""children">0>"id""#.to_string(), &Value::Integer(2)),
+    ///     (r#""children">1>"meta">"id""#.to_string(), &Value::Integer(3)),
+    ///     (r#""id""#.to_string(), &Value::Integer(1)),
+    /// ]);
+    /// ```
+    pub fn find_key(&self, key: &str) -> Vec<(JsonPath, &Value)> {
+        self.find_all(|path, _| matches!(path.last(), Some(JsonIndexer::ObjInd(k)) if k == key))
+    }
+
+    /// find first value that satisfy `predicate` in dfs order, with its path. `predicate` is called with path and
+    /// value of this value and its descendants, and this value itself is visited first with empty path.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"users": [{"name": "dyson", "age": 1}, {"name": "jq", "age": 12}]}"#).unwrap();
+    ///
+    /// let (path, adult) = json.find(|_, v| v.get("age").and_then(Value::get_integer) >= Some(&10)).unwrap();
+    /// assert_eq!(path.to_string(), r#""users">1"#);
+    /// assert_eq!(adult["name"], Value::from("jq"));
+    /// assert_eq!(json.find(|path, _| path.depth() > 3), None);
+    /// ```
+    pub fn find<F: FnMut(&JsonPath, &Value) -> bool>(&self, mut predicate: F) -> Option<(JsonPath, &Value)> {
+        self.visitor_with_paths().find(|(path, value)| predicate(path, value))
+    }
+
+    /// find all values that satisfy `predicate` in dfs order, with their paths. see [`Value::find`] also.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"a": [1, "two", 3], "b": {"c": 4}}"#).unwrap();
+    ///
+    /// let numbers: Vec<_> = json.find_all(|_, v| v.is_number()).into_iter().map(|(p, v)| (p.to_string(), v)).collect();
+    /// assert_eq!(numbers, vec![
+    ///     (r#""a">0"#.to_string(), &Value::Integer(1)),
+    ///     (r#""a">2"#.to_string(), &Value::Integer(3)),
+    ///     (r#""b">"c""#.to_string(), &Value::Integer(4)),
+    /// ]);
+    /// ```
+    pub fn find_all<F: FnMut(&JsonPath, &Value) -> bool>(&self, mut predicate: F) -> Vec<(JsonPath, &Value)> {
+        self.visitor_with_paths().filter(|(path, value)| predicate(path, value)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let json = Value::parse(r#"{"a": [1, 2.5, {"b": null, "c": [true]}], "d": "s", "e": {}}"#).unwrap();
+        for contained in ["1", "2.5", "null", "true", "[true]", r#"{"c": [true], "b": null}"#, r#""s""#, "{}", "[]"] {
+            let expected = contained != "[]";
+            assert_eq!(json.contains(&Value::parse(contained).unwrap()), expected, "{contained}");
+        }
+        assert!(!json.contains(&Value::Float(1.)));
+        assert!(!json.contains(&Value::parse(r#"{"b": null}"#).unwrap()));
+        assert!(Value::Null.contains(&Value::Null));
+    }
+
+    #[test]
+    fn test_contains_key_recursive() {
+        let json = Value::parse(r#"[{"a": {"b": [{"c": 1}]}}, "d", {"": 0}]"#).unwrap();
+        for key in ["a", "b", "c", ""] {
+            assert!(json.contains_key_recursive(key), "{key}");
+        }
+        for key in ["d", "0", "1"] {
+            assert!(!json.contains_key_recursive(key), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_find_key() {
+        let json = Value::parse(r#"{"k": {"k": [{"k": 1}, {"x": {"k": 2}}]}, "y": "k"}"#).unwrap();
+        let found: Vec<_> = json.find_key("k").into_iter().map(|(path, v)| (path.to_string(), v.to_string())).collect();
+        assert_eq!(
+            found,
+            vec![
+                (r#""k""#.to_string(), r#"{"k":[{"k":1},{"x":{"k":2}}]}"#.to_string()),
+                (r#""k">"k""#.to_string(), r#"[{"k":1},{"x":{"k":2}}]"#.to_string()),
+                (r#""k">"k">0>"k""#.to_string(), "1".to_string()),
+                (r#""k">"k">1>"x">"k""#.to_string(), "2".to_string()),
+            ]
+        );
+        for (path, value) in json.find_key("k") {
+            assert_eq!(&json[&path], value);
+        }
+        assert!(json.find_key("y").len() == 1 && json.find_key("z").is_empty());
+    }
+
+    #[test]
+    fn test_find() {
+        let json = Value::parse(r#"{"a": [1, {"b": 2}, [3]], "c": {"d": {}}, "e": 4}"#).unwrap();
+        let all: Vec<_> = json.find_all(|_, _| true).into_iter().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(
+            all,
+            vec![
+                "",
+                r#""a""#,
+                r#""a">0"#,
+                r#""a">1"#,
+                r#""a">1>"b""#,
+                r#""a">2"#,
+                r#""a">2>0"#,
+                r#""c""#,
+                r#""c">"d""#,
+                r#""e""#
+            ]
+        );
+        for (path, value) in json.find_all(|_, _| true) {
+            assert_eq!(&json[&path], value);
+        }
+
+        let mut visited = 0;
+        let found = json.find(|_, v| {
+            visited += 1;
+            v.is_integer() && v.integer() % 2 == 0
+        });
+        assert_eq!(found, Some((JsonPath::parse(r#""a">1>"b""#).unwrap(), &Value::Integer(2))));
+        assert_eq!(visited, 5);
+        assert_eq!(json.find(|path, _| path.depth() == 0), Some((JsonPath::new(), &json)));
+        assert_eq!(Value::Null.find_all(|_, v| v.is_null()), vec![(JsonPath::new(), &Value::Null)]);
+        assert_eq!(json.find(|_, v| v.is_string()), None);
+    }
+}
@@ -0,0 +1,782 @@
+//! [`serde::Serialize`] and [`serde::Deserialize`] implementation of [`Value`], with `serde` feature.
+//! so [`Value`] can be embedded in other serde-driven structs, and transported over any serde format.
+//! [`Value::RawNumber`] is serialized as integer if it fit `i64` or `u64`, otherwise as float.
+//! integer which overflow `i64` is deserialized into [`Value::RawNumber`] to keep its digits.
+//!
+//! and [`from_value`] and [`to_value`] convert between [`Value`] and user types without stringify.
+//! enum is represented like `serde_json`, unit variant is string and other variant is object with one entry.
+
+use super::{
+    map::{Iter, Key, Map},
+    number::raw_number,
+    Value,
+};
+use ::serde::{
+    de::{
+        self, value::BorrowedStrDeserializer, Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess,
+        SeqAccess, Unexpected, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{
+        self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant,
+        SerializeTuple, SerializeTupleStruct, SerializeTupleVariant, Serializer,
+    },
+};
+use std::fmt;
+use thiserror::Error;
+
+/// [`SerdeError`] is error of conversion between [`Value`] and user types. see [`from_value`] and [`to_value`].
+#[derive(Error, Debug)]
+pub enum SerdeError {
+    #[error("{0}")]
+    Custom(String),
+
+    #[error("key of object must be string, but found {found}")]
+    KeyMustBeString { found: &'static str },
+}
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+impl de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self::Custom(msg.to_string())
+    }
+}
+
+/// deserialize user type `T` from `value`, without stringify it. string of `T` can be borrowed from `value`.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// #[derive(serde::Deserialize, Debug, PartialEq)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+/// let json = Value::parse(r#"{"servers": [{"host": "localhost", "port": 8080}]}"#).unwrap();
+/// let servers: Vec<Server> = dyson::from_value(&json["servers"]).unwrap();
+/// assert_eq!(servers, vec![Server { host: "localhost".to_string(), port: 8080 }]);
+/// ```
+pub fn from_value<'de, T: Deserialize<'de>>(value: &'de Value) -> Result<T, SerdeError> {
+    T::deserialize(value)
+}
+
+/// serialize user type `T` into [`Value`], without stringify it.
+/// # examples
+/// ```
+/// use dyson::Value;
+/// #[derive(serde::Serialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+/// let json = dyson::to_value(&Server { host: "localhost".to_string(), port: 8080 }).unwrap();
+/// assert_eq!(json, Value::parse(r#"{"host": "localhost", "port": 8080}"#).unwrap());
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, SerdeError> {
+    value.serialize(ValueSerializer)
+}
+
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let json = Value::parse(r#"{"key": [1, "two", null]}"#).unwrap();
+/// assert_eq!(serde_json::to_string(&json).unwrap(), r#"{"key":[1,"two",null]}"#);
+/// ```
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Object(object) => {
+                let mut map = serializer.serialize_map(Some(object.len()))?;
+                for (k, v) in object.iter() {
+                    map.serialize_entry(&**k, v)?;
+                }
+                map.end()
+            }
+            Value::Array(array) => {
+                let mut seq = serializer.serialize_seq(Some(array.len()))?;
+                for v in array {
+                    seq.serialize_element(v)?;
+                }
+                seq.end()
+            }
+            Value::Bool(bool) => serializer.serialize_bool(*bool),
+            Value::Null => serializer.serialize_unit(),
+            Value::String(string) => serializer.serialize_str(string),
+            Value::Integer(integer) => serializer.serialize_i64(*integer),
+            Value::Float(float) => serializer.serialize_f64(*float),
+            Value::RawNumber(raw) => match (raw.as_i64(), raw.as_str().parse()) {
+                (Some(integer), _) => serializer.serialize_i64(integer),
+                (None, Ok(unsigned)) => serializer.serialize_u64(unsigned),
+                _ => serializer.serialize_f64(raw.as_f64().unwrap_or(f64::NAN)),
+            },
+        }
+    }
+}
+
+/// # examples
+/// ```
+/// use dyson::Value;
+/// let json: Value = serde_json::from_str(r#"{"key": [1, "two", null]}"#).unwrap();
+/// assert_eq!(json, Value::parse(r#"{"key": [1, "two", null]}"#).unwrap());
+/// ```
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// [`ValueVisitor`] build [`Value`] from any self-describing data.
+struct ValueVisitor;
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any json value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Integer(v))
+    }
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(i64::try_from(v).map(Value::Integer).unwrap_or_else(|_| Value::RawNumber(raw_number(v.to_string()))))
+    }
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Float(v))
+    }
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Deserialize::deserialize(deserializer)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(v) = seq.next_element()? {
+            array.push(v);
+        }
+        Ok(Value::Array(array))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = Map::new();
+        while let Some((k, v)) = map.next_entry::<String, Value>()? {
+            object.insert(k.into(), v);
+        }
+        Ok(Value::Object(Box::new(object)))
+    }
+}
+
+impl<'de> Deserializer<'de> for &'de Value {
+    type Error = SerdeError;
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Value::Object(object) => visitor.visit_map(ObjectAccess { iter: object.iter(), value: None }),
+            Value::Array(array) => visitor.visit_seq(ArrayAccess(array.iter())),
+            Value::Bool(bool) => visitor.visit_bool(*bool),
+            Value::Null => visitor.visit_unit(),
+            Value::String(string) => visitor.visit_borrowed_str(string),
+            Value::Integer(integer) => visitor.visit_i64(*integer),
+            Value::Float(float) => visitor.visit_f64(*float),
+            Value::RawNumber(raw) => match (raw.as_i64(), raw.as_str().parse()) {
+                (Some(integer), _) => visitor.visit_i64(integer),
+                (None, Ok(unsigned)) => visitor.visit_u64(unsigned),
+                _ => visitor.visit_f64(raw.as_f64().unwrap_or(f64::NAN)),
+            },
+        }
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            Value::String(variant) => visitor.visit_enum(VariantRef { variant, value: None }),
+            Value::Object(object) if object.len() == 1 => {
+                let (variant, value) = object.iter().next().expect("object has one entry");
+                visitor.visit_enum(VariantRef { variant, value: Some(value) })
+            }
+            _ => Err(de::Error::invalid_type(Unexpected::Other(self.node_type()), &"string or object with one entry")),
+        }
+    }
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// [`ObjectAccess`] deserialize entries of object, `value` is the value of the last deserialized key.
+struct ObjectAccess<'de> {
+    iter: Iter<'de, Key, Value>,
+    value: Option<&'de Value>,
+}
+impl<'de> MapAccess<'de> for ObjectAccess<'de> {
+    type Error = SerdeError;
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, SerdeError> {
+        match self.iter.next() {
+            Some((k, v)) => {
+                self.value = Some(v);
+                seed.deserialize(KeyRef(k)).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, SerdeError> {
+        seed.deserialize(self.value.take().expect("value should be deserialized after key"))
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// [`KeyRef`] deserialize key of object. key is string, but integer key such as of `BTreeMap<u8, _>` is parsed.
+struct KeyRef<'de>(&'de str);
+macro_rules! deserialize_parsed_key {
+    ($($deserialize:ident => $visit:ident,)*) => {$(
+        fn $deserialize<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+            match self.0.parse() {
+                Ok(integer) => visitor.$visit(integer),
+                Err(_) => visitor.visit_borrowed_str(self.0),
+            }
+        }
+    )*};
+}
+impl<'de> Deserializer<'de> for KeyRef<'de> {
+    type Error = SerdeError;
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_borrowed_str(self.0)
+    }
+    deserialize_parsed_key! {
+        deserialize_i8 => visit_i8, deserialize_i16 => visit_i16, deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64, deserialize_u8 => visit_u8, deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32, deserialize_u64 => visit_u64,
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_enum(VariantRef { variant: self.0, value: None })
+    }
+    forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// [`ArrayAccess`] deserialize elements of array.
+struct ArrayAccess<'de>(std::slice::Iter<'de, Value>);
+impl<'de> SeqAccess<'de> for ArrayAccess<'de> {
+    type Error = SerdeError;
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, SerdeError> {
+        self.0.next().map(|v| seed.deserialize(v)).transpose()
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+/// [`VariantRef`] deserialize enum, `value` is `None` for unit variant represented by string.
+struct VariantRef<'de> {
+    variant: &'de str,
+    value: Option<&'de Value>,
+}
+impl<'de> EnumAccess<'de> for VariantRef<'de> {
+    type Error = SerdeError;
+    type Variant = Self;
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), SerdeError> {
+        Ok((seed.deserialize(BorrowedStrDeserializer::new(self.variant))?, self))
+    }
+}
+impl<'de> VariantAccess<'de> for VariantRef<'de> {
+    type Error = SerdeError;
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        match self.value {
+            None | Some(Value::Null) => Ok(()),
+            Some(value) => Err(de::Error::invalid_type(Unexpected::Other(value.node_type()), &"unit variant")),
+        }
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, SerdeError> {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"newtype variant")),
+        }
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _: usize, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Some(Value::Array(array)) => visitor.visit_seq(ArrayAccess(array.iter())),
+            _ => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"tuple variant")),
+        }
+    }
+    fn struct_variant<V: Visitor<'de>>(self, _: &'static [&'static str], visitor: V) -> Result<V::Value, SerdeError> {
+        match self.value {
+            Some(Value::Object(object)) => visitor.visit_map(ObjectAccess { iter: object.iter(), value: None }),
+            _ => Err(de::Error::invalid_type(Unexpected::UnitVariant, &"struct variant")),
+        }
+    }
+}
+
+/// wrap `value` into object with one entry, that is representation of non-unit enum variant.
+fn variant(variant: &'static str, value: Value) -> Value {
+    let mut object = Map::new();
+    object.insert(variant.into(), value);
+    Value::Object(Box::new(object))
+}
+
+/// [`ValueSerializer`] serialize user type into [`Value`], used by [`to_value`].
+struct ValueSerializer;
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerdeError;
+    type SerializeSeq = ArrayBuilder;
+    type SerializeTuple = ArrayBuilder;
+    type SerializeTupleStruct = ArrayBuilder;
+    type SerializeTupleVariant = ArrayBuilder;
+    type SerializeMap = ObjectBuilder;
+    type SerializeStruct = ObjectBuilder;
+    type SerializeStructVariant = ObjectBuilder;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, SerdeError> {
+        Ok(Value::Bool(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Value, SerdeError> {
+        Ok(Value::Integer(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, SerdeError> {
+        self.serialize_i64(v.into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Value, SerdeError> {
+        ValueVisitor.visit_u64(v)
+    }
+    fn serialize_f32(self, v: f32) -> Result<Value, SerdeError> {
+        self.serialize_f64(v.into())
+    }
+    fn serialize_f64(self, v: f64) -> Result<Value, SerdeError> {
+        Ok(Value::Float(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Value, SerdeError> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Value, SerdeError> {
+        Ok(Value::String(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, SerdeError> {
+        Ok(Value::Array(v.iter().map(|&b| Value::Integer(b.into())).collect()))
+    }
+    fn serialize_none(self) -> Result<Value, SerdeError> {
+        Ok(Value::Null)
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, SerdeError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Value, SerdeError> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, SerdeError> {
+        Ok(Value::Null)
+    }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Value, SerdeError> {
+        Ok(Value::String(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Value, SerdeError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Value, SerdeError> {
+        Ok(variant(name, value.serialize(self)?))
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<ArrayBuilder, SerdeError> {
+        Ok(ArrayBuilder { array: Vec::with_capacity(len.unwrap_or(0)), variant: None })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<ArrayBuilder, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<ArrayBuilder, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<ArrayBuilder, SerdeError> {
+        Ok(ArrayBuilder { array: Vec::with_capacity(len), variant: Some(variant) })
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<ObjectBuilder, SerdeError> {
+        Ok(ObjectBuilder { object: Map::new(), key: None, variant: None })
+    }
+    fn serialize_struct(self, _: &'static str, len: usize) -> Result<ObjectBuilder, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<ObjectBuilder, SerdeError> {
+        Ok(ObjectBuilder { object: Map::new(), key: None, variant: Some(variant) })
+    }
+}
+
+/// [`ArrayBuilder`] collect serialized elements into array. if `variant`, it is wrapped by tuple variant.
+struct ArrayBuilder {
+    array: Vec<Value>,
+    variant: Option<&'static str>,
+}
+impl ArrayBuilder {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.array.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn build(self) -> Value {
+        let array = Value::Array(self.array);
+        match self.variant {
+            Some(name) => variant(name, array),
+            None => array,
+        }
+    }
+}
+impl SerializeSeq for ArrayBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+impl SerializeTuple for ArrayBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+impl SerializeTupleStruct for ArrayBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+impl SerializeTupleVariant for ArrayBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+
+/// [`ObjectBuilder`] collect serialized entries into object. if `variant`, it is wrapped by struct variant.
+struct ObjectBuilder {
+    object: Map<Key, Value>,
+    key: Option<Key>,
+    variant: Option<&'static str>,
+}
+impl ObjectBuilder {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: Key, value: &T) -> Result<(), SerdeError> {
+        self.object.insert(key, value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn build(self) -> Value {
+        let object = Value::Object(Box::new(self.object));
+        match self.variant {
+            Some(name) => variant(name, object),
+            None => object,
+        }
+    }
+}
+impl SerializeMap for ObjectBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self.key.take().expect("value should be serialized after key");
+        self.insert(key, value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+impl SerializeStruct for ObjectBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.insert(key.into(), value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+impl SerializeStructVariant for ObjectBuilder {
+    type Ok = Value;
+    type Error = SerdeError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.insert(key.into(), value)
+    }
+    fn end(self) -> Result<Value, SerdeError> {
+        Ok(self.build())
+    }
+}
+
+/// [`KeySerializer`] serialize key of map into object key. string, char, integer, and unit variant are allowed.
+struct KeySerializer;
+impl KeySerializer {
+    fn reject(found: &'static str) -> Result<Key, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found })
+    }
+}
+impl Serializer for KeySerializer {
+    type Ok = Key;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<Key, SerdeError>;
+    type SerializeTuple = Impossible<Key, SerdeError>;
+    type SerializeTupleStruct = Impossible<Key, SerdeError>;
+    type SerializeTupleVariant = Impossible<Key, SerdeError>;
+    type SerializeMap = Impossible<Key, SerdeError>;
+    type SerializeStruct = Impossible<Key, SerdeError>;
+    type SerializeStructVariant = Impossible<Key, SerdeError>;
+
+    fn serialize_bool(self, _: bool) -> Result<Key, SerdeError> {
+        Self::reject("Bool")
+    }
+    fn serialize_i8(self, v: i8) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_i16(self, v: i16) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_i32(self, v: i32) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_i64(self, v: i64) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_u8(self, v: u8) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_u16(self, v: u16) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_u32(self, v: u32) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_u64(self, v: u64) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_f32(self, _: f32) -> Result<Key, SerdeError> {
+        Self::reject("Float")
+    }
+    fn serialize_f64(self, _: f64) -> Result<Key, SerdeError> {
+        Self::reject("Float")
+    }
+    fn serialize_char(self, v: char) -> Result<Key, SerdeError> {
+        Ok(v.to_string().into())
+    }
+    fn serialize_str(self, v: &str) -> Result<Key, SerdeError> {
+        Ok(v.into())
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Key, SerdeError> {
+        Self::reject("Array")
+    }
+    fn serialize_none(self) -> Result<Key, SerdeError> {
+        Self::reject("Null")
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Key, SerdeError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Key, SerdeError> {
+        Self::reject("Null")
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Key, SerdeError> {
+        Self::reject("Null")
+    }
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Key, SerdeError> {
+        Ok(variant.into())
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Key, SerdeError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Key, SerdeError> {
+        Self::reject("Object")
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Array" })
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Array" })
+    }
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Array" })
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Object" })
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Object" })
+    }
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Object" })
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(SerdeError::KeyMustBeString { found: "Object" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParserOptions;
+
+    #[derive(::serde::Serialize, ::serde::Deserialize, Debug, PartialEq)]
+    struct Event {
+        kind: String,
+        payload: Value,
+    }
+
+    #[test]
+    fn test_serde_embedded() {
+        let raw = r#"{"kind":"created","payload":{"id":1,"ok":true,"score":0.5,"tags":["a",null]}}"#;
+        let event: Event = serde_json::from_str(raw).unwrap();
+        assert_eq!(event.payload["tags"], Value::parse(r#"["a", null]"#).unwrap());
+        assert_eq!(event.payload["score"], Value::Float(0.5));
+        assert_eq!(serde_json::to_string(&event).unwrap(), raw);
+
+        let other: serde_json::Value = serde_json::to_value(&event.payload).unwrap();
+        assert_eq!(other["id"], serde_json::json!(1));
+    }
+
+    #[test]
+    fn test_serde_number() {
+        let json: Value = serde_json::from_str("[18446744073709551615, -1, 1.5]").unwrap();
+        assert_eq!(json[0].get_raw_number().unwrap().as_str(), "18446744073709551615");
+        assert_eq!(json[1], Value::Integer(-1));
+        assert_eq!(serde_json::to_string(&json).unwrap(), "[18446744073709551615,-1,1.5]");
+
+        let raw = Value::parse_with("[1.50, 12]", ParserOptions::new().lossless_number(true)).unwrap();
+        assert_eq!(serde_json::to_string(&raw).unwrap(), "[1.5,12]");
+    }
+
+    #[derive(::serde::Serialize, ::serde::Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Point(i32, i32),
+        Rect { width: u32, height: u32 },
+    }
+
+    #[derive(::serde::Serialize, ::serde::Deserialize, Debug, PartialEq)]
+    struct Config<'a> {
+        name: &'a str,
+        limit: Option<u64>,
+        shapes: Vec<Shape>,
+        labels: std::collections::BTreeMap<u8, char>,
+    }
+
+    #[test]
+    fn test_from_to_value() {
+        let json = Value::parse(
+            r#"{"name": "dyson", "limit": null, "shapes": ["Empty", {"Circle": 1.5}, {"Point": [1, -2]},
+                {"Rect": {"width": 3, "height": 4}}], "labels": {"1": "a", "2": "b"}}"#,
+        )
+        .unwrap();
+        let config: Config = from_value(&json).unwrap();
+        assert_eq!(config.name, "dyson");
+        assert_eq!(config.limit, None);
+        assert_eq!(config.shapes[3], Shape::Rect { width: 3, height: 4 });
+        assert_eq!(config.labels[&2], 'b');
+        assert_eq!(to_value(&config).unwrap(), json);
+        assert_eq!(to_value(&json).unwrap(), json);
+        assert_eq!(to_value(&u64::MAX).unwrap().to_string(), "18446744073709551615");
+    }
+
+    #[test]
+    fn test_from_to_value_error() {
+        let json = Value::parse(r#"{"name": 1, "limit": null, "shapes": [], "labels": {}}"#).unwrap();
+        let err = from_value::<Config>(&json).unwrap_err();
+        assert_eq!(err.to_string(), r#"invalid type: integer `1`, expected a borrowed string"#);
+        assert!(from_value::<Shape>(&Value::parse(r#"{"Circle": 1, "Empty": null}"#).unwrap()).is_err());
+        assert!(from_value::<u8>(&Value::Integer(256)).is_err());
+
+        let map: std::collections::HashMap<_, _> = [(vec![1], 1)].into_iter().collect();
+        assert!(matches!(to_value(&map), Err(SerdeError::KeyMustBeString { found: "Array" })));
+    }
+}
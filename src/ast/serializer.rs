@@ -0,0 +1,390 @@
+//! streaming serializer, it write json tokens into [`Write`] incrementally without building whole string in memory.
+//! layout of output such as separators and indent is decided by [`JsonFormatter`].
+
+use super::{format::FormatOptions, Value};
+use std::io::{self, Write};
+
+/// [`JsonFormatter`] write each token of json into writer `w`, called back by [`Serializer`].
+/// `depth` is nesting depth of the token, so object or array of top level and its end is `0`, and its element is `1`.
+/// default implementations write minified json, so formatter override only what it changes.
+/// # examples
+/// ```
+/// use dyson::{ast::serializer::Serializer, JsonFormatter, Value};
+/// use std::io::{self, Write};
+/// /// write each element in its own line with trailing comma
+/// struct TrailingComma;
+/// impl JsonFormatter for TrailingComma {
+///     fn begin_element<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, _first: bool) -> io::Result<()> {
+///         write!(w, "\n{}", "  ".repeat(depth))
+///     }
+///     fn end_element<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize) -> io::Result<()> {
+///         w.write_all(b",")
+///     }
+///     fn end_array<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, _empty: bool) -> io::Result<()> {
+///         write!(w, "\n{}]", "  ".repeat(depth))
+///     }
+/// }
+/// let mut serializer = Serializer::with_formatter(Vec::new(), TrailingComma);
+/// serializer.value(&Value::parse("[1, [2]]").unwrap()).unwrap();
+/// assert_eq!(String::from_utf8(serializer.into_inner()).unwrap(), "[\n  1,\n  [\n    2,\n  ],\n]");
+/// ```
+pub trait JsonFormatter {
+    /// called before whole `value` is written by [`Serializer::value`], so formatter can look ahead it.
+    fn begin_value(&mut self, _value: &Value) {}
+    /// write start of object.
+    fn begin_object<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize) -> io::Result<()> {
+        w.write_all(b"{")
+    }
+    /// write end of object. `empty` is whether the object has no entry.
+    fn end_object<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize, _empty: bool) -> io::Result<()> {
+        w.write_all(b"}")
+    }
+    /// write start of array.
+    fn begin_array<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize) -> io::Result<()> {
+        w.write_all(b"[")
+    }
+    /// write end of array. `empty` is whether the array has no element.
+    fn end_array<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize, _empty: bool) -> io::Result<()> {
+        w.write_all(b"]")
+    }
+    /// write separator before entry of object or element of array. `first` is whether it is the first one.
+    fn begin_element<W: ?Sized + Write>(&mut self, w: &mut W, _depth: usize, first: bool) -> io::Result<()> {
+        if first {
+            Ok(())
+        } else {
+            w.write_all(b",")
+        }
+    }
+    /// write something after entry of object or element of array.
+    fn end_element<W: ?Sized + Write>(&mut self, _w: &mut W, _depth: usize) -> io::Result<()> {
+        Ok(())
+    }
+    /// write key of object and colon.
+    fn key<W: ?Sized + Write>(&mut self, w: &mut W, key: &str) -> io::Result<()> {
+        w.write_all(FormatOptions::default().quote(key).as_bytes())?;
+        w.write_all(b":")
+    }
+    /// write scalar `value`, that is not object nor array.
+    fn scalar<W: ?Sized + Write>(&mut self, w: &mut W, value: &Value) -> io::Result<()> {
+        write_scalar(w, value, &FormatOptions::default())
+    }
+    /// write something after top level value, such as trailing newline.
+    fn end_document<W: ?Sized + Write>(&mut self, _w: &mut W) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// write scalar `value` with `options`.
+pub(crate) fn write_scalar<W: ?Sized + Write>(w: &mut W, value: &Value, options: &FormatOptions) -> io::Result<()> {
+    match value {
+        Value::Object(_) | Value::Array(_) => unreachable!("collection is not scalar"),
+        Value::Bool(bool) => write!(w, "{bool}"),
+        Value::Null => w.write_all(b"null"),
+        Value::String(string) => w.write_all(options.quote(string).as_bytes()),
+        Value::Integer(integer) => write!(w, "{integer}"),
+        Value::Float(float) => w.write_all(options.float(*float).as_bytes()),
+        Value::RawNumber(raw) => write!(w, "{raw}"),
+    }
+}
+
+/// [`StandardFormatter`] is formatter configured by [`FormatOptions`], used by [`Value::to_string_with`] and
+/// [`Value::stringify_with`].
+#[derive(Debug, Clone, Default)]
+pub struct StandardFormatter {
+    options: FormatOptions,
+    pretty: bool,
+    /// depth of the collection written in one line, see [`FormatOptions::inline_leaves`].
+    inline: Option<usize>,
+    inline_next: bool,
+}
+impl StandardFormatter {
+    /// get formatter without unnecessary space and linefeed.
+    pub fn compact(options: FormatOptions) -> Self {
+        Self { options, ..Default::default() }
+    }
+    /// get formatter with proper indent.
+    pub fn pretty(options: FormatOptions) -> Self {
+        Self { options, pretty: true, ..Default::default() }
+    }
+
+    fn end<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, empty: bool, close: &[u8]) -> io::Result<()> {
+        if self.inline == Some(depth) {
+            self.inline = None;
+        } else if self.pretty {
+            let newline = self.options.line_ending.as_str();
+            w.write_all(if empty { newline.repeat(2) } else { newline.to_string() }.as_bytes())?;
+            w.write_all(self.options.indent_of(depth).as_bytes())?;
+        }
+        w.write_all(close)
+    }
+}
+impl JsonFormatter for StandardFormatter {
+    fn begin_value(&mut self, value: &Value) {
+        let leaf = |v: &Value| !matches!(v, Value::Object(_) | Value::Array(_));
+        let fit = |len| self.options.inline_leaves.map_or(false, |max| len <= max);
+        let inline = match value {
+            Value::Object(object) => fit(object.len()) && object.values().all(leaf),
+            Value::Array(array) => fit(array.len()) && array.iter().all(leaf),
+            _ => false,
+        };
+        self.inline_next = self.pretty && self.inline.is_none() && inline;
+    }
+    fn begin_object<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize) -> io::Result<()> {
+        if std::mem::take(&mut self.inline_next) {
+            self.inline = Some(depth);
+        }
+        w.write_all(b"{")
+    }
+    fn end_object<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, empty: bool) -> io::Result<()> {
+        self.end(w, depth, empty, b"}")
+    }
+    fn begin_array<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize) -> io::Result<()> {
+        if std::mem::take(&mut self.inline_next) {
+            self.inline = Some(depth);
+        }
+        w.write_all(b"[")
+    }
+    fn end_array<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, empty: bool) -> io::Result<()> {
+        self.end(w, depth, empty, b"]")
+    }
+    fn begin_element<W: ?Sized + Write>(&mut self, w: &mut W, depth: usize, first: bool) -> io::Result<()> {
+        if !first {
+            w.write_all(b",")?;
+        }
+        if self.inline.is_some() {
+            w.write_all(if first { b"" } else { b" " })
+        } else if self.pretty {
+            w.write_all(self.options.line_ending.as_str().as_bytes())?;
+            w.write_all(self.options.indent_of(depth).as_bytes())
+        } else {
+            Ok(())
+        }
+    }
+    fn key<W: ?Sized + Write>(&mut self, w: &mut W, key: &str) -> io::Result<()> {
+        w.write_all(self.options.quote(key).as_bytes())?;
+        w.write_all(if self.pretty { b": " } else { b":" })
+    }
+    fn scalar<W: ?Sized + Write>(&mut self, w: &mut W, value: &Value) -> io::Result<()> {
+        write_scalar(w, value, &self.options)
+    }
+    fn end_document<W: ?Sized + Write>(&mut self, w: &mut W) -> io::Result<()> {
+        if self.options.trailing_newline {
+            w.write_all(self.options.line_ending.as_str().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// [`Serializer`] write json into `W` by events such as [`Serializer::begin_object`] and [`Serializer::key`].
+/// comma, colon, linefeed, and indent between tokens are inserted by formatter `F`, see [`JsonFormatter`].
+/// # examples
+/// ```
+/// use dyson::{FormatOptions, Value, ast::serializer::Serializer};
+/// let mut serializer = Serializer::new(Vec::new(), FormatOptions::new());
+/// serializer.begin_array().unwrap();
+/// for i in 0..3 {
+///     serializer.value(&Value::Integer(i)).unwrap();
+/// }
+/// serializer.end_array().unwrap();
+/// assert_eq!(serializer.written(), 7);
+/// assert_eq!(String::from_utf8(serializer.into_inner()).unwrap(), "[0,1,2]");
+/// ```
+pub struct Serializer<W, F = StandardFormatter> {
+    writer: Counter<W>,
+    formatter: F,
+    /// whether each opened object or array already has element.
+    stack: Vec<bool>,
+    after_key: bool,
+}
+impl<W: Write> Serializer<W> {
+    /// get serializer without unnecessary space and linefeed, like [`Value::to_string_with`].
+    pub fn new(writer: W, options: FormatOptions) -> Self {
+        Self::with_formatter(writer, StandardFormatter::compact(options))
+    }
+    /// get serializer with proper indent, like [`Value::stringify_with`].
+    pub fn pretty(writer: W, options: FormatOptions) -> Self {
+        Self::with_formatter(writer, StandardFormatter::pretty(options))
+    }
+}
+impl<W: Write, F: JsonFormatter> Serializer<W, F> {
+    /// get serializer whose output is formatted by `formatter`.
+    pub fn with_formatter(writer: W, formatter: F) -> Self {
+        Self { writer: Counter { inner: writer, written: 0 }, formatter, stack: Vec::new(), after_key: false }
+    }
+
+    /// get the number of bytes written by this serializer.
+    pub fn written(&self) -> usize {
+        self.writer.written
+    }
+    /// get inner writer.
+    pub fn into_inner(self) -> W {
+        self.writer.inner
+    }
+
+    /// write separator before element of current object or array.
+    fn element(&mut self) -> io::Result<()> {
+        if std::mem::take(&mut self.after_key) {
+            return Ok(());
+        }
+        let depth = self.stack.len();
+        match self.stack.last_mut() {
+            Some(has_element) => {
+                let first = !std::mem::replace(has_element, true);
+                self.formatter.begin_element(&mut self.writer, depth, first)
+            }
+            None => Ok(()),
+        }
+    }
+    /// write something after element of current object or array, or after top level value.
+    fn element_end(&mut self) -> io::Result<()> {
+        match self.stack.len() {
+            0 => self.formatter.end_document(&mut self.writer),
+            depth => self.formatter.end_element(&mut self.writer, depth),
+        }
+    }
+
+    /// write start of object.
+    pub fn begin_object(&mut self) -> io::Result<()> {
+        self.element()?;
+        self.formatter.begin_object(&mut self.writer, self.stack.len())?;
+        self.stack.push(false);
+        Ok(())
+    }
+    /// write key of object, its value should be written next.
+    pub fn key(&mut self, key: &str) -> io::Result<()> {
+        self.element()?;
+        self.formatter.key(&mut self.writer, key)?;
+        self.after_key = true;
+        Ok(())
+    }
+    /// write end of object.
+    pub fn end_object(&mut self) -> io::Result<()> {
+        let has_element = self.stack.pop().expect("end without begin");
+        self.formatter.end_object(&mut self.writer, self.stack.len(), !has_element)?;
+        self.element_end()
+    }
+    /// write start of array.
+    pub fn begin_array(&mut self) -> io::Result<()> {
+        self.element()?;
+        self.formatter.begin_array(&mut self.writer, self.stack.len())?;
+        self.stack.push(false);
+        Ok(())
+    }
+    /// write end of array.
+    pub fn end_array(&mut self) -> io::Result<()> {
+        let has_element = self.stack.pop().expect("end without begin");
+        self.formatter.end_array(&mut self.writer, self.stack.len(), !has_element)?;
+        self.element_end()
+    }
+
+    /// write whole `value` recursively.
+    pub fn value(&mut self, value: &Value) -> io::Result<()> {
+        self.formatter.begin_value(value);
+        match value {
+            Value::Object(object) => {
+                self.begin_object()?;
+                for (k, v) in object.iter() {
+                    self.key(k)?;
+                    self.value(v)?;
+                }
+                self.end_object()
+            }
+            Value::Array(array) => {
+                self.begin_array()?;
+                for v in array {
+                    self.value(v)?;
+                }
+                self.end_array()
+            }
+            scalar => {
+                self.element()?;
+                self.formatter.scalar(&mut self.writer, scalar)?;
+                self.element_end()
+            }
+        }
+    }
+    /// flush inner writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// [`Counter`] count the number of bytes written into inner writer.
+struct Counter<W> {
+    inner: W,
+    written: usize,
+}
+impl<W: Write> Write for Counter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.written += written;
+        Ok(written)
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_events() {
+        let mut serializer = Serializer::pretty(Vec::new(), FormatOptions::new().indent(2));
+        serializer.begin_object().unwrap();
+        serializer.key("empty").unwrap();
+        serializer.begin_array().unwrap();
+        serializer.end_array().unwrap();
+        serializer.key("rows").unwrap();
+        serializer.begin_array().unwrap();
+        for i in 0..2 {
+            serializer.value(&Value::parse(format!(r#"{{"id": {i}}}"#)).unwrap()).unwrap();
+        }
+        serializer.end_array().unwrap();
+        serializer.end_object().unwrap();
+        let written = serializer.written();
+        let serialized = String::from_utf8(serializer.into_inner()).unwrap();
+        let json = Value::parse(r#"{"empty": [], "rows": [{"id": 0}, {"id": 1}]}"#).unwrap();
+        assert_eq!(serialized, json.stringify_with(&FormatOptions::new().indent(2)));
+        assert_eq!(written, serialized.len());
+    }
+
+    #[test]
+    fn test_serialize_with_formatter() {
+        use crate::Indent;
+        let json = Value::parse(r#"{"a": [], "b": {"c": [1, {}]}, "d": "e"}"#).unwrap();
+        let serialize = |formatter| {
+            let mut serializer = Serializer::with_formatter(Vec::new(), formatter);
+            serializer.value(&json).unwrap();
+            String::from_utf8(serializer.into_inner()).unwrap()
+        };
+        assert_eq!(serialize(StandardFormatter::compact(FormatOptions::new())), json.to_string());
+        assert_eq!(serialize(StandardFormatter::pretty(FormatOptions::new())), json.stringify());
+
+        let mut indent0 = Serializer::with_formatter(Vec::new(), Indent::<0>);
+        indent0.value(&json).unwrap();
+        assert_eq!(String::from_utf8(indent0.into_inner()).unwrap(), json.to_string());
+        let mut indent1 = Serializer::with_formatter(Vec::new(), Indent::<1>);
+        indent1.value(&json).unwrap();
+        assert_eq!(String::from_utf8(indent1.into_inner()).unwrap(), json.stringify());
+    }
+
+    #[test]
+    fn test_serialize_io_error() {
+        struct Full(usize);
+        impl Write for Full {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                let n = buf.len().min(self.0);
+                self.0 -= n;
+                Ok(n)
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+        let json = Value::parse(r#"{"key": [1, 2, 3]}"#).unwrap();
+        let err = Serializer::new(Full(5), FormatOptions::new()).value(&json).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    }
+}
@@ -0,0 +1,151 @@
+//! set operations of [`Value::Array`]. elements are compared structurally, order of object keys is ignored and
+//! number is compared by its exact value, so `{"a": 1, "b": 2}` and `{"b": 2.0, "a": 1}` are same element, but
+//! `9007199254740992` and `9007199254740993` are different elements even though they are the same as `f64`.
+
+use super::{query::order, Value};
+
+impl Value {
+    /// remove duplicated elements of [`Value::Array`] in place, and keep first occurrence of each element.
+    /// unlike `Vec::dedup`, duplicated elements need not be consecutive.
+    /// # panics
+    /// if value is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let mut json = Value::parse(r#"["b", "a", "b", {"x": 1, "y": 2}, "a", {"y": 2, "x": 1}]"#).unwrap();
+    /// json.dedup();
+    /// assert_eq!(json, Value::parse(r#"["b", "a", {"x": 1, "y": 2}]"#).unwrap());
+    /// ```
+    pub fn dedup(&mut self) {
+        match self {
+            Value::Array(v) => {
+                let mut first = first_occurrences(v).into_iter();
+                v.retain(|_| first.next().unwrap_or(false));
+            }
+            _ => panic!("only Array can be deduplicated, but {}", self.node_type()),
+        }
+    }
+
+    /// get array of elements that are in this array or `other` without duplication. elements of this array come
+    /// first.
+    /// # panics
+    /// if `self` or `other` is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let (a, b) = (Value::parse("[3, 1, 2]").unwrap(), Value::parse("[2, 4, 4, 3]").unwrap());
+    /// assert_eq!(a.union(&b), Value::parse("[3, 1, 2, 4]").unwrap());
+    /// ```
+    pub fn union(&self, other: &Value) -> Value {
+        let mut union: Value = self.iter().chain(other.iter()).cloned().collect();
+        union.dedup();
+        union
+    }
+
+    /// get array of elements of this array that are also in `other` without duplication.
+    /// # panics
+    /// if `self` or `other` is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let (a, b) = (Value::parse("[3, 1, 2, 3]").unwrap(), Value::parse("[2, 4, 3]").unwrap());
+    /// assert_eq!(a.intersection(&b), Value::parse("[3, 2]").unwrap());
+    /// ```
+    pub fn intersection(&self, other: &Value) -> Value {
+        self.retain_by_membership(other, true)
+    }
+
+    /// get array of elements of this array that are not in `other` without duplication.
+    /// # panics
+    /// if `self` or `other` is not `Array`.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let (a, b) = (Value::parse("[3, 1, 2, 1]").unwrap(), Value::parse("[2, 4, 3]").unwrap());
+    /// assert_eq!(a.difference(&b), Value::parse("[1]").unwrap());
+    /// ```
+    pub fn difference(&self, other: &Value) -> Value {
+        self.retain_by_membership(other, false)
+    }
+
+    fn retain_by_membership(&self, other: &Value, member: bool) -> Value {
+        let mut sorted: Vec<_> = other.iter().collect();
+        sorted.sort_by(|a, b| order(a, b));
+        let mut retained: Value =
+            self.iter().filter(|e| sorted.binary_search_by(|s| order(s, e)).is_ok() == member).cloned().collect();
+        retained.dedup();
+        retained
+    }
+}
+
+/// check each element is first occurrence or not. this function's complexity is **O(n log n)**.
+fn first_occurrences(elements: &[Value]) -> Vec<bool> {
+    let mut indices: Vec<_> = (0..elements.len()).collect();
+    // stable sort keep first occurrence before others in the same elements
+    indices.sort_by(|&i, &j| order(&elements[i], &elements[j]));
+    let mut first = vec![true; elements.len()];
+    for w in indices.windows(2) {
+        if order(&elements[w[0]], &elements[w[1]]).is_eq() {
+            first[w[1]] = false;
+        }
+    }
+    first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dedup() {
+        for (json, expected) in [
+            ("[]", "[]"),
+            ("[1, 1, 1]", "[1]"),
+            ("[1, 1.0, 2, 1e0]", "[1, 2]"),
+            (r#"[null, false, null, [1, 2], [2, 1], [1, 2], "", ""]"#, r#"[null, false, [1, 2], [2, 1], ""]"#),
+            (
+                r#"[{"a": [{"b": 1, "c": 2}]}, {"a": [{"c": 2, "b": 1}]}, {"a": []}]"#,
+                r#"[{"a": [{"b": 1, "c": 2}]}, {"a": []}]"#,
+            ),
+        ] {
+            let mut value = Value::parse(json).unwrap();
+            value.dedup();
+            assert_eq!(value, Value::parse(expected).unwrap(), "{json}");
+        }
+    }
+
+    #[test]
+    fn test_set_operation() {
+        let a = Value::parse(r#"["rust", "json", {"id": 1}, "json", null]"#).unwrap();
+        let b = Value::parse(r#"[{"id": 1.0}, "parser", null, "parser"]"#).unwrap();
+        assert_eq!(a.union(&b), Value::parse(r#"["rust", "json", {"id": 1}, null, "parser"]"#).unwrap());
+        assert_eq!(b.union(&a), Value::parse(r#"[{"id": 1.0}, "parser", null, "rust", "json"]"#).unwrap());
+        assert_eq!(a.intersection(&b), Value::parse(r#"[{"id": 1}, null]"#).unwrap());
+        assert_eq!(a.difference(&b), Value::parse(r#"["rust", "json"]"#).unwrap());
+        assert_eq!(b.difference(&a), Value::parse(r#"["parser"]"#).unwrap());
+
+        let empty = Value::Array(Vec::new());
+        assert_eq!(a.union(&empty), a.intersection(&a));
+        assert_eq!(a.intersection(&empty), empty);
+        assert_eq!(empty.difference(&a), empty);
+    }
+
+    #[test]
+    fn test_set_operation_large_integer() {
+        let mut ids =
+            Value::parse("[9007199254740992, 9007199254740993, 9007199254740992.0, 9007199254740993]").unwrap();
+        ids.dedup();
+        assert_eq!(ids, Value::parse("[9007199254740992, 9007199254740993]").unwrap());
+
+        let other = Value::parse("[9007199254740993]").unwrap();
+        assert_eq!(ids.difference(&other), Value::parse("[9007199254740992]").unwrap());
+        assert_eq!(ids.intersection(&other), other);
+        assert_eq!(other.union(&ids), Value::parse("[9007199254740993, 9007199254740992]").unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_operation_unexpected_type() {
+        Value::parse("[]").unwrap().union(&Value::Null);
+    }
+}
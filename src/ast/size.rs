@@ -0,0 +1,100 @@
+//! estimation of memory usage of [`Value`]. it is not exact, because allocator overhead and unused capacity of
+//! map are not counted, but it is enough to find which parts of document dominate memory.
+
+use super::{
+    map::{Key, Map},
+    Value,
+};
+use std::mem::size_of;
+
+impl Value {
+    /// estimate bytes of memory used by this value, that is size of [`Value`] itself and heap allocated by its strings,
+    /// arrays, and objects recursively. this method's complexity is **O(|value|)**.
+    /// with `interned-keys` feature, shared keys are counted in each object, so it may be overestimated.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"small": [1, 2], "large": ["a long string that dominate memory usage"]}"#).unwrap();
+    ///
+    /// let (largest, _) = json.items().max_by_key(|(_, v)| v.deep_size_of()).unwrap();
+    /// assert_eq!(&largest[..], "large");
+    /// assert!(json.deep_size_of() > json["small"].deep_size_of() + json["large"].deep_size_of());
+    /// ```
+    pub fn deep_size_of(&self) -> usize {
+        size_of::<Value>() + self.heap_size_of()
+    }
+
+    /// estimate bytes of heap used by this value. this method does not recurse, so it can be used for deeply nested
+    /// value.
+    fn heap_size_of(&self) -> usize {
+        let (mut size, mut stack) = (0, vec![self]);
+        while let Some(value) = stack.pop() {
+            size += match value {
+                Value::Object(m) => {
+                    let entries = m.len() * (size_of::<Key>() + size_of::<Value>() + ENTRY_OVERHEAD);
+                    let mut keys = 0;
+                    for (k, v) in m.iter() {
+                        keys += key_heap_size_of(k);
+                        stack.push(v);
+                    }
+                    size_of::<Map<Key, Value>>() + entries + keys
+                }
+                Value::Array(v) => {
+                    stack.extend(v);
+                    v.capacity() * size_of::<Value>()
+                }
+                Value::String(s) => s.capacity(),
+                Value::RawNumber(raw) => raw.as_str().len(),
+                Value::Bool(_) | Value::Null | Value::Integer(_) | Value::Float(_) => 0,
+            }
+        }
+        size
+    }
+}
+
+/// each entry of `LinkedHashMap` is allocated as node with two links, and indexed by hash table.
+#[cfg(not(feature = "sorted-map"))]
+const ENTRY_OVERHEAD: usize = 3 * size_of::<usize>() + 1;
+/// each entry of `BTreeMap` is stored in node with edges to child nodes.
+#[cfg(feature = "sorted-map")]
+const ENTRY_OVERHEAD: usize = size_of::<usize>();
+
+#[cfg(not(feature = "interned-keys"))]
+fn key_heap_size_of(key: &Key) -> usize {
+    key.capacity()
+}
+/// `Arc<str>` allocate strong and weak reference counts with its string.
+#[cfg(feature = "interned-keys")]
+fn key_heap_size_of(key: &Key) -> usize {
+    2 * size_of::<usize>() + key.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_size_of() {
+        for scalar in ["null", "true", "1", "1.5"] {
+            assert_eq!(Value::parse(scalar).unwrap().deep_size_of(), size_of::<Value>(), "{scalar}");
+        }
+        let string = Value::String(String::with_capacity(100));
+        assert_eq!(string.deep_size_of(), size_of::<Value>() + 100);
+
+        let array = Value::Array(vec![string, Value::Null]);
+        assert_eq!(array.deep_size_of(), size_of::<Value>() + 2 * size_of::<Value>() + 100);
+
+        let array_size = array.deep_size_of();
+        let object: Value = [("key", array)].into_iter().collect();
+        let object_size = object.deep_size_of();
+        assert!(object_size > array_size + "key".len());
+        let nested: Value = [("key", object), ("other", Value::Null)].into_iter().collect();
+        assert!(nested.deep_size_of() > object_size);
+
+        let mut deep = Value::Null;
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.deep_size_of(), size_of::<Value>() + 100_000 * size_of::<Value>());
+    }
+}
@@ -0,0 +1,130 @@
+//! structural matching of [`Value`] with template. template is json that has the same shape as expected document,
+//! and its string can be wildcard marker that match any value of the type.
+//! - `"$any"` match any value.
+//! - `"$string"`, `"$number"`, `"$bool"`, `"$object"`, and `"$array"` match any value of the type.
+//! - string that start with `"$$"` match literal string that start with `"$"`, such as `"$$any"` match `"$any"`.
+//! - key of object that end with `?` is optional, such as `"nickname?"` match missing `nickname` key.
+//!
+//! other values match only equal value, number is compared by its value so `1` match `1.0`. object must have exactly
+//! the keys of template (except optional keys), and array must have the same length as template.
+
+use super::{index::JsonIndexer, index_path::JsonPath, Value};
+
+impl Value {
+    /// match this value with `template`, and get paths of this value that do not match, in dfs order of template.
+    /// if returned vector is empty, this value match the template. see [module document](self) for template syntax.
+    /// # examples
+    /// ```
+    /// use dyson::{JsonPath, Value};
+    /// let template = Value::parse(r#"{"id": "$number", "name": "$string", "tags": "$array", "email?": "$string"}"#);
+    /// let template = template.unwrap();
+    ///
+    /// let user = Value::parse(r#"{"id": 1, "name": "dyson", "tags": ["json"]}"#).unwrap();
+    /// assert!(user.matches(&template).is_empty());
+    ///
+    /// let user = Value::parse(r#"{"id": "1", "tags": [], "email": null, "admin": true}"#).unwrap();
+    /// let mut mismatches: Vec<_> = user.matches(&template).iter().map(JsonPath::to_dotted).collect();
+    /// mismatches.sort(); // dfs order depend on order of keys
+    /// assert_eq!(mismatches, vec!["admin", "email", "id", "name"]);
+    /// ```
+    pub fn matches(&self, template: &Value) -> Vec<JsonPath> {
+        let mut mismatches = Vec::new();
+        matches_recursive(self, template, &mut JsonPath::new(), &mut mismatches);
+        mismatches
+    }
+}
+
+fn matches_recursive(value: &Value, template: &Value, path: &mut JsonPath, mismatches: &mut Vec<JsonPath>) {
+    match (value, template) {
+        (_, Value::String(marker)) if !match_marker(value, marker) => mismatches.push(path.clone()),
+        (_, Value::String(_)) => (),
+        (Value::Object(m), Value::Object(t)) => {
+            for (tk, tv) in t.iter() {
+                let (key, optional) = tk.strip_suffix('?').map_or((&tk[..], false), |k| (k, true));
+                path.push(JsonIndexer::ObjInd(key.to_string()));
+                match m.get(key) {
+                    Some(v) => matches_recursive(v, tv, path, mismatches),
+                    None if !optional => mismatches.push(path.clone()),
+                    None => (),
+                }
+                path.pop();
+            }
+            for k in m.keys() {
+                if !t.contains_key(&k[..]) && !t.contains_key(&format!("{k}?")[..]) {
+                    path.push(JsonIndexer::ObjInd(k.to_string()));
+                    mismatches.push(path.clone());
+                    path.pop();
+                }
+            }
+        }
+        (Value::Array(v), Value::Array(t)) if v.len() == t.len() => {
+            for (i, (e, te)) in v.iter().zip(t.iter()).enumerate() {
+                path.push(JsonIndexer::ArrInd(i));
+                matches_recursive(e, te, path, mismatches);
+                path.pop();
+            }
+        }
+        (Value::Object(_), _) | (Value::Array(_), _) | (_, Value::Object(_)) | (_, Value::Array(_)) => {
+            mismatches.push(path.clone())
+        }
+        _ if !value.eq_numeric(template) => mismatches.push(path.clone()),
+        _ => (),
+    }
+}
+
+fn match_marker(value: &Value, marker: &str) -> bool {
+    match marker {
+        "$any" => true,
+        "$string" => value.is_string(),
+        "$number" => value.is_number(),
+        "$bool" => value.is_bool(),
+        "$object" => value.is_object(),
+        "$array" => value.is_array(),
+        _ if marker.starts_with("$$") => value.get_string() == Some(&marker[1..]),
+        _ => value.get_string() == Some(marker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_marker() {
+        let value = Value::parse(r#"[{}, [], true, null, "s", 1, 1.5, "$any"]"#).unwrap();
+        for (marker, expected) in [
+            ("$any", vec![true, true, true, true, true, true, true, true]),
+            ("$object", vec![true, false, false, false, false, false, false, false]),
+            ("$array", vec![false, true, false, false, false, false, false, false]),
+            ("$bool", vec![false, false, true, false, false, false, false, false]),
+            ("$string", vec![false, false, false, false, true, false, false, true]),
+            ("$number", vec![false, false, false, false, false, true, true, false]),
+            ("$$any", vec![false, false, false, false, false, false, false, true]),
+            ("s", vec![false, false, false, false, true, false, false, false]),
+        ] {
+            let template = Value::from(marker);
+            assert_eq!(value.iter().map(|v| v.matches(&template).is_empty()).collect::<Vec<_>>(), expected, "{marker}");
+        }
+    }
+
+    #[test]
+    fn test_match_nested_template() {
+        let template = Value::parse(
+            r#"{"users": [{"id": "$number", "role?": "admin"}, "$any"], "total": 2, "next?": "$string", "meta": null}"#,
+        )
+        .unwrap();
+        for (json, expected) in [
+            (r#"{"users": [{"id": 1}, 2], "total": 2.0, "meta": null}"#, vec![]),
+            (r#"{"users": [{"id": 1, "role": "admin"}, {}], "total": 2, "next": "x", "meta": null}"#, vec![]),
+            (r#"{"users": [{"id": 1, "role": "user"}, {}], "total": 3, "meta": null}"#, vec!["total", "users[0].role"]),
+            (r#"{"users": [{"id": 1}], "total": 2, "meta": {}}"#, vec!["meta", "users"]),
+            (r#"{"users": {}, "total": 2, "next": null}"#, vec!["meta", "next", "users"]),
+            (r#"[]"#, vec![""]),
+        ] {
+            let mut mismatches: Vec<_> =
+                Value::parse(json).unwrap().matches(&template).iter().map(JsonPath::to_dotted).collect();
+            mismatches.sort();
+            assert_eq!(mismatches, expected, "{json}");
+        }
+    }
+}
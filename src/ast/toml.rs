@@ -0,0 +1,134 @@
+//! conversion between json and toml, with `toml` feature. toml document is table, so only object can be converted.
+//! datetime of toml is converted into string of json, because json has no datetime.
+
+use super::{index::JsonIndexer, index_path::JsonPath, map::Map, Value};
+use thiserror::Error;
+
+/// [`TomlError`] is error of conversion between json and toml.
+#[derive(Error, Debug)]
+pub enum TomlError {
+    #[error("toml document is table, but root of json is {found}")]
+    NotTable { found: &'static str },
+
+    #[error("toml has no null, but json has null at {path}")]
+    Null { path: JsonPath },
+
+    #[error("{raw} cannot be represented in toml number")]
+    Number { raw: String, path: JsonPath },
+
+    #[error(transparent)]
+    Parse(#[from] ::toml::de::Error),
+
+    #[error(transparent)]
+    Serialize(#[from] ::toml::ser::Error),
+}
+
+impl Value {
+    /// stringify object into toml document. `null`, which toml does not have, cause error.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"name": "dyson", "package": {"keywords": ["json"], "version": "0.1.0"}}"#).unwrap();
+    /// assert_eq!(
+    ///     json.to_toml_string().unwrap(),
+    ///     "name = \"dyson\"\n\n[package]\nkeywords = [\"json\"]\nversion = \"0.1.0\"\n",
+    /// );
+    /// assert!(Value::parse(r#"{"key": null}"#).unwrap().to_toml_string().is_err());
+    /// ```
+    pub fn to_toml_string(&self) -> Result<String, TomlError> {
+        match self {
+            Value::Object(_) => Ok(::toml::to_string(&to_toml(self, &mut JsonPath::new())?)?),
+            _ => Err(TomlError::NotTable { found: self.node_type() }),
+        }
+    }
+
+    /// parse toml document into object. datetime of toml is converted into string.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::from_toml("[server]\nport = 8080\nhosts = [\"a\", \"b\"]").unwrap();
+    /// assert_eq!(json, Value::parse(r#"{"server": {"port": 8080, "hosts": ["a", "b"]}}"#).unwrap());
+    /// ```
+    pub fn from_toml(s: &str) -> Result<Value, TomlError> {
+        Ok(from_toml(s.parse()?))
+    }
+}
+
+fn to_toml(value: &Value, path: &mut JsonPath) -> Result<::toml::Value, TomlError> {
+    Ok(match value {
+        Value::Object(object) => {
+            let mut table = ::toml::Table::new();
+            for (k, v) in object.iter() {
+                path.push(JsonIndexer::ObjInd(k.to_string()));
+                table.insert(k.to_string(), to_toml(v, path)?);
+                path.pop();
+            }
+            ::toml::Value::Table(table)
+        }
+        Value::Array(array) => {
+            let mut converted = Vec::with_capacity(array.len());
+            for (i, v) in array.iter().enumerate() {
+                path.push(JsonIndexer::ArrInd(i));
+                converted.push(to_toml(v, path)?);
+                path.pop();
+            }
+            ::toml::Value::Array(converted)
+        }
+        Value::Bool(bool) => ::toml::Value::Boolean(*bool),
+        Value::Null => return Err(TomlError::Null { path: path.clone() }),
+        Value::String(string) => ::toml::Value::String(string.clone()),
+        Value::Integer(integer) => ::toml::Value::Integer(*integer),
+        Value::Float(float) => ::toml::Value::Float(*float),
+        Value::RawNumber(raw) => {
+            let raw = raw.to_string();
+            match (raw.parse(), raw.parse()) {
+                (Ok(integer), _) => ::toml::Value::Integer(integer),
+                (_, Ok(float)) => ::toml::Value::Float(float),
+                _ => return Err(TomlError::Number { raw, path: path.clone() }),
+            }
+        }
+    })
+}
+
+fn from_toml(value: ::toml::Value) -> Value {
+    match value {
+        ::toml::Value::Table(table) => {
+            Value::Object(Box::new(table.into_iter().map(|(k, v)| (k.into(), from_toml(v))).collect::<Map<_, _>>()))
+        }
+        ::toml::Value::Array(array) => Value::Array(array.into_iter().map(from_toml).collect()),
+        ::toml::Value::Boolean(bool) => Value::Bool(bool),
+        ::toml::Value::String(string) => Value::String(string),
+        ::toml::Value::Integer(integer) => Value::Integer(integer),
+        ::toml::Value::Float(float) => Value::Float(float),
+        ::toml::Value::Datetime(datetime) => Value::String(datetime.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_round_trip() {
+        let json = Value::parse(
+            r#"{"title": "example", "mixed": [1, "two", [3]], "owner": {"name": "tom", "age": 30.5},
+                "servers": [{"ip": "10.0.0.1"}, {"ip": "10.0.0.2"}]}"#,
+        )
+        .unwrap();
+        let toml = json.to_toml_string().unwrap();
+        assert_eq!(Value::from_toml(&toml).unwrap(), json);
+        assert!(toml.contains("[[servers]]"), "{toml}");
+    }
+
+    #[test]
+    fn test_toml_error() {
+        let err = Value::parse(r#"{"a": [1, {"b": null}]}"#).unwrap().to_toml_string().unwrap_err();
+        assert_eq!(err.to_string(), r#"toml has no null, but json has null at "a">1>"b""#);
+        let err = Value::parse("[1, 2]").unwrap().to_toml_string().unwrap_err();
+        assert!(matches!(err, TomlError::NotTable { found: "Array" }));
+        assert!(matches!(Value::from_toml("key = "), Err(TomlError::Parse(_))));
+
+        let datetime = Value::from_toml("date = 1979-05-27T07:32:00Z").unwrap();
+        assert_eq!(datetime["date"], Value::from("1979-05-27T07:32:00Z"));
+    }
+}
@@ -0,0 +1,215 @@
+use super::{format::quote, index::JsonIndexer, index_path::JsonPath, Value};
+use std::collections::BTreeSet;
+
+impl Value {
+    /// remove subtrees until minified serialization (same as [`Value::to_string`]) fits within `max_bytes`, and
+    /// return removed subtrees with their paths at the time of removal. result is always valid json.
+    /// in each step, the deepest subtree whose removal alone is enough is removed. if there is no such subtree,
+    /// the largest child of root is removed. root itself is never removed, so if even empty root does not fit,
+    /// all its children are removed.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let raw_json = r#"{"event": "login", "user": {"id": 1, "avatar": "aGVsbG8gd29ybGQgaGVsbG8gd29ybGQ="}}"#;
+    /// let mut json = Value::parse(raw_json).unwrap();
+    ///
+    /// let dropped = json.fit_within(40);
+    /// assert_eq!(json, Value::parse(r#"{"event": "login", "user": {"id": 1}}"#).unwrap());
+    /// assert_eq!(dropped.len(), 1);
+    /// assert_eq!(dropped[0].0.to_string(), r#""user">"avatar""#);
+    /// ```
+    pub fn fit_within(&mut self, max_bytes: usize) -> Vec<(JsonPath, Value)> {
+        let mut dropped = Vec::new();
+        let mut table = SizeTable::new(self);
+        while table.nodes[0].size > max_bytes {
+            let (path, id) = match table.victim(table.nodes[0].size - max_bytes) {
+                Some(victim) => victim,
+                None => break,
+            };
+            let (parent, last) = path.split_last().expect("victim is not root");
+            let removed = match (&mut self[&parent], last) {
+                (Value::Object(m), JsonIndexer::ObjInd(k)) => m.remove(&k[..]),
+                (Value::Array(a), &JsonIndexer::ArrInd(i)) => Some(a.remove(i)),
+                _ => None,
+            };
+            dropped.push((path, removed.expect("victim exist")));
+            table.remove(id);
+        }
+        dropped
+    }
+}
+
+/// [`SizeTable`] hold minified serialization size of each subtree of a value, so each removal of [`Value::fit_within`]
+/// update only sizes of its ancestors instead of serializing whole value again.
+struct SizeTable {
+    /// nodes in dfs pre-order, so root is `nodes[0]` and each child is after its parent.
+    nodes: Vec<SizeNode>,
+}
+struct SizeNode {
+    size: usize,
+    /// bytes of key and colon if parent is object, otherwise `0`.
+    entry: usize,
+    key: Option<String>,
+    parent: Option<usize>,
+    /// position in children of parent at construction.
+    pos: usize,
+    children: Vec<usize>,
+    /// `(entry + size, pos)` of remaining children, so last one is the largest child.
+    order: BTreeSet<(usize, usize)>,
+    /// fenwick tree of removed children, used to get current index of array element.
+    removed: Vec<usize>,
+}
+impl SizeTable {
+    /// compute size of each subtree bottom-up. this does not recurse, so it can be used for deeply nested value.
+    fn new(root: &Value) -> Self {
+        let mut nodes: Vec<SizeNode> = Vec::new();
+        let mut stack = vec![(root, None, 0, None)];
+        while let Some((value, parent, pos, key)) = stack.pop() {
+            let id = nodes.len();
+            let entry = key.as_deref().map_or(0, |k| quote(k).len() + 1);
+            let size = match value {
+                Value::Object(m) => {
+                    let children = m.iter().enumerate().map(|(i, (k, v))| (v, Some(id), i, Some(k.to_string())));
+                    stack.extend(children.rev());
+                    2
+                }
+                Value::Array(a) => {
+                    stack.extend(a.iter().enumerate().map(|(i, v)| (v, Some(id), i, None)).rev());
+                    2
+                }
+                scalar => scalar.to_string().len(),
+            };
+            if let Some(p) = parent {
+                nodes[p].children.push(id);
+            }
+            let (children, order, removed) = (Vec::new(), BTreeSet::new(), Vec::new());
+            nodes.push(SizeNode { size, entry, key, parent, pos, children, order, removed });
+        }
+        for id in (0..nodes.len()).rev() {
+            let children = std::mem::take(&mut nodes[id].children);
+            if !children.is_empty() {
+                let order: BTreeSet<_> =
+                    children.iter().map(|&c| (nodes[c].entry + nodes[c].size, nodes[c].pos)).collect();
+                let node = &mut nodes[id];
+                node.size += order.iter().map(|&(size, _)| size).sum::<usize>() + children.len() - 1;
+                node.removed = vec![0; children.len() + 1];
+                node.order = order;
+            }
+            nodes[id].children = children;
+        }
+        Self { nodes }
+    }
+
+    /// find path and id of subtree that should be removed to reduce `excess` bytes. in each level, the largest child
+    /// is chosen, and stop at the deepest one whose removal alone is enough. if root has no child, return `None`.
+    fn victim(&self, excess: usize) -> Option<(JsonPath, usize)> {
+        let (mut path, mut current) = (JsonPath::new(), 0);
+        while let Some(&(size, pos)) = self.nodes[current].order.iter().next_back() {
+            if path.depth() > 0 && size < excess {
+                break;
+            }
+            let node = &self.nodes[current];
+            let child = node.children[pos];
+            match &self.nodes[child].key {
+                Some(key) => path.push(JsonIndexer::ObjInd(key.clone())),
+                None => path.push(JsonIndexer::ArrInd(pos - removed_before(&node.removed, pos))),
+            }
+            current = child;
+        }
+        (path.depth() > 0).then(|| (path, current))
+    }
+
+    /// remove subtree `id` from its parent, and update sizes of its ancestors.
+    fn remove(&mut self, id: usize) {
+        let SizeNode { size, entry, pos, parent, .. } = self.nodes[id];
+        let parent = parent.expect("victim is not root");
+        let node = &mut self.nodes[parent];
+        node.order.remove(&(entry + size, pos));
+        mark_removed(&mut node.removed, pos);
+        let delta = entry + size + if node.order.is_empty() { 0 } else { 1 };
+        let mut current = Some(parent);
+        while let Some(id) = current {
+            let SizeNode { size, entry, pos, parent, .. } = self.nodes[id];
+            self.nodes[id].size = size - delta;
+            if let Some(p) = parent {
+                let order = &mut self.nodes[p].order;
+                order.remove(&(entry + size, pos));
+                order.insert((entry + size - delta, pos));
+            }
+            current = parent;
+        }
+    }
+}
+
+/// count removed positions before `pos` by fenwick tree `tree`.
+fn removed_before(tree: &[usize], pos: usize) -> usize {
+    let (mut i, mut count) = (pos, 0);
+    while i > 0 {
+        count += tree[i];
+        i &= i - 1;
+    }
+    count
+}
+/// mark `pos` as removed in fenwick tree `tree`.
+fn mark_removed(tree: &mut [usize], pos: usize) {
+    let mut i = pos + 1;
+    while i < tree.len() {
+        tree[i] += 1;
+        i += i & i.wrapping_neg();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_within() {
+        let raw = r#"{"id": 1, "tags": ["a", "bb", "ccc"], "payload": {"items": [{"blob": "xxxxxxxxxxxxxxxxxxxx"}]}}"#;
+        let original = Value::parse(raw).unwrap();
+
+        let mut json = original.clone();
+        assert!(json.fit_within(1000).is_empty());
+        assert_eq!(json, original);
+
+        let mut json = original.clone();
+        let dropped = json.fit_within(60);
+        assert!(json.to_string().len() <= 60);
+        assert_eq!(json, Value::parse(r#"{"id": 1, "tags": ["a", "bb", "ccc"], "payload": {"items": [{}]}}"#).unwrap());
+        assert_eq!(dropped, vec![(JsonPath::from_dotted("payload.items[0].blob").unwrap(), "x".repeat(20).into())]);
+
+        for max_bytes in 0..original.to_string().len() {
+            let mut json = original.clone();
+            let dropped = json.fit_within(max_bytes);
+            assert!(!dropped.is_empty());
+            assert!(json.to_string().len() <= max_bytes.max(2), "{max_bytes}: {json}");
+            assert_eq!(Value::parse(json.to_string()).unwrap(), json);
+        }
+
+        let mut scalar = Value::from("too long string");
+        assert!(scalar.fit_within(3).is_empty());
+    }
+
+    #[test]
+    fn test_fit_within_large() {
+        let records = |n: i64| -> Value {
+            (0..n)
+                .map(|i| [("id", Value::Integer(i)), ("s", Value::from("xxxxxxxxxx"))].into_iter().collect::<Value>())
+                .collect()
+        };
+        let elapsed = |n: i64| {
+            let mut json = records(n);
+            let max_bytes = json.to_string().len() / 2;
+            let start = std::time::Instant::now();
+            let dropped = json.fit_within(max_bytes);
+            let elapsed = start.elapsed();
+            // each record is smaller than 40 bytes, so only records are dropped until it fits
+            assert!((max_bytes - 40..=max_bytes).contains(&json.to_string().len()));
+            assert!(dropped.iter().all(|(path, _)| path.depth() == 1));
+            elapsed
+        };
+        let (small, large) = (elapsed(5_000), elapsed(40_000));
+        // 8 times larger input take about 64 times longer if it is quadratic
+        assert!(large < small * 24 + std::time::Duration::from_millis(50), "{small:?} -> {large:?}");
+    }
+}
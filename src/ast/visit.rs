@@ -1,23 +1,60 @@
-use super::Value;
+use super::{
+    index::{JsonIndexer, JsonIndexerRef},
+    index_path::JsonPath,
+    map::{IntoIter, Key},
+    Value,
+};
+use std::ops::ControlFlow;
 
+/// iterator of [`Value`] and its descendants in bfs order. see [`Value::bfs`].
+pub struct BfsVisitor<'a> {
+    queue: std::collections::VecDeque<&'a Value>,
+}
 pub struct DfsVisitor<'a> {
     stack: Vec<ValueIterator<'a>>,
     first: Option<&'a Value>,
 }
+/// consuming iterator of leaves of [`Value`]. see [`Value::into_visitor`].
+pub struct IntoDfsVisitor {
+    stack: Vec<IntoValueIterator>,
+    first: Option<Value>,
+}
+enum IntoValueIterator {
+    ObjectIterator(IntoIter<Key, Value>),
+    ArrayIterator(std::vec::IntoIter<Value>),
+}
+/// iterator of [`Value`] and its descendants with their paths. see [`Value::visitor_with_paths`].
+pub struct PathVisitor<'a> {
+    stack: Vec<ValueIterator<'a>>,
+    path: JsonPath,
+    first: Option<&'a Value>,
+}
 enum ValueIterator<'a> {
-    ObjectIterator(linked_hash_map::Iter<'a, String, Value>),
-    ArrayIterator(std::slice::Iter<'a, Value>),
+    ObjectIterator(super::map::Iter<'a, Key, Value>),
+    ArrayIterator(std::iter::Enumerate<std::slice::Iter<'a, Value>>),
 }
+/// [`WalkControl`] is used for skipping descendants while walking. see [`Value::walk_ctl`] also.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WalkControl {
+    Continue,
+    Prune,
+}
+/// [`DfsEvent`] is emitted by [`Value::walk`] and [`Value::walk_ctl`].
+/// - `ForwardEdge(parent, child, indexer)` is emitted before visiting `child`.
+/// - `BackEdge(child, parent, indexer)` is emitted after leaving `child`.
+///
+/// `indexer` is the key or index that `child` lives under in `parent`, that is `parent[indexer] == child`. it borrows
+/// the key from `parent`, so walking do not allocate for each edge.
 #[derive(Debug, PartialEq)]
 pub enum DfsEvent<'a> {
     Visit(&'a Value),
     Leave(&'a Value),
-    ForwardEdge(&'a Value, &'a Value),
-    BackEdge(&'a Value, &'a Value),
+    ForwardEdge(&'a Value, &'a Value, JsonIndexerRef<'a>),
+    BackEdge(&'a Value, &'a Value, JsonIndexerRef<'a>),
 }
 
 impl Value {
-    /// walk json [`Value`] with bfs order. if `f` return true continue walk, return false interrupt walk.
+    /// walk json [`Value`] with dfs order. if `f` return true continue walk, return false interrupt walk.
     /// if complete walk, this method return true, and not complete walk, this method return false.
     /// # examples
     /// ```
@@ -29,80 +66,122 @@ impl Value {
     /// json.walk(|event| match event {
     ///     DfsEvent::Visit(_v) => true,
     ///     DfsEvent::Leave(_v) => true,
-    ///     DfsEvent::ForwardEdge(_parent, _child) => {
+    ///     DfsEvent::ForwardEdge(_parent, _child, _indexer) => {
     ///         depth = depth + 1;
     ///         max_depth = max_depth.max(depth);
     ///         true
     ///     }
-    ///     DfsEvent::BackEdge(_child, _parent) => {
+    ///     DfsEvent::BackEdge(_child, _parent, _indexer) => {
     ///         depth = depth - 1;
     ///         max_depth = max_depth.max(depth);
     ///         true
     ///     }
     /// });
     /// assert_eq!(max_depth, 4);
+    /// assert_eq!(json.max_depth(), max_depth);
     /// ```
     pub fn walk<'a, F: FnMut(DfsEvent<'a>) -> bool>(&'a self, mut f: F) -> bool {
-        let mut fun = || -> Option<()> {
-            let (mut stack, mut iter_stack) = (Vec::new(), Vec::new());
-            match self {
-                Value::Object(m) => {
-                    f(DfsEvent::Visit(self)).then(|| ())?;
-                    stack.push(self);
-                    iter_stack.push(ValueIterator::ObjectIterator(m.iter()));
-                }
-                Value::Array(v) => {
-                    f(DfsEvent::Visit(self)).then(|| ())?;
-                    stack.push(self);
-                    iter_stack.push(ValueIterator::ArrayIterator(v.iter()));
-                }
-                v => {
-                    f(DfsEvent::Visit(v)).then(|| ())?;
-                    f(DfsEvent::Leave(v)).then(|| ())?;
-                }
+        self.walk_ctl(|event| match f(event) {
+            true => ControlFlow::Continue(WalkControl::Continue),
+            false => ControlFlow::Break(()),
+        })
+        .is_continue()
+    }
+
+    /// walk json [`Value`] with dfs order like [`Value::walk`], and `f` control the walk by [`ControlFlow`].
+    /// - `Continue(WalkControl::Continue)` continue walk.
+    /// - `Continue(WalkControl::Prune)` skip descendants. if it is returned for [`DfsEvent::Visit`], children of the
+    ///   value are not visited but the value is left. if it is returned for [`DfsEvent::ForwardEdge`], the child is not
+    ///   visited at all. for other events, it is same as `WalkControl::Continue`.
+    /// - `Break(b)` interrupt walk, and this method return `Break(b)`.
+    /// # examples
+    /// ```
+    /// use dyson::{DfsEvent, Value, WalkControl};
+    /// use std::ops::ControlFlow;
+    /// let json = Value::parse(r#"{"first": [1, 2], "second": [3, 4], "third": [5, "six"]}"#).unwrap();
+    ///
+    /// let mut visited = Vec::new();
+    /// let flow = json.walk_ctl(|event| match event {
+    ///     DfsEvent::Visit(v) if v == &json["second"] => ControlFlow::Continue(WalkControl::Prune),
+    ///     DfsEvent::Visit(Value::String(s)) => ControlFlow::Break(&s[..]),
+    ///     DfsEvent::Visit(Value::Integer(i)) => {
+    ///         visited.push(*i);
+    ///         ControlFlow::Continue(WalkControl::Continue)
+    ///     }
+    ///     _ => ControlFlow::Continue(WalkControl::Continue),
+    /// });
+    /// assert_eq!(visited, [1, 2, 5]);
+    /// assert_eq!(flow, ControlFlow::Break("six"));
+    /// ```
+    pub fn walk_ctl<'a, B, F: FnMut(DfsEvent<'a>) -> ControlFlow<B, WalkControl>>(
+        &'a self,
+        mut f: F,
+    ) -> ControlFlow<B> {
+        let children = ValueIterator::children;
+        let mut stack = Vec::new();
+        match (f(DfsEvent::Visit(self))?, children(self)) {
+            (WalkControl::Continue, Some(iter)) => stack.push((self, iter, None)),
+            _ => {
+                f(DfsEvent::Leave(self))?;
             }
-            while let (Some(last), Some(last_iter)) = (stack.last(), iter_stack.last_mut()) {
-                let next = match last_iter {
-                    ValueIterator::ObjectIterator(oi) => oi.next().map(|(_k, v)| v),
-                    ValueIterator::ArrayIterator(ai) => ai.next(),
-                };
-                match next {
-                    Some(Value::Object(m)) => {
-                        let next_value = next.unwrap();
-                        f(DfsEvent::ForwardEdge(last, next_value)).then(|| ())?;
-                        stack.push(next_value);
-                        iter_stack.push(ValueIterator::ObjectIterator(m.iter()));
-                        f(DfsEvent::Visit(next_value)).then(|| ())?;
-                    }
-                    Some(Value::Array(v)) => {
-                        let next_value = next.unwrap();
-                        f(DfsEvent::ForwardEdge(last, next_value)).then(|| ())?;
-                        stack.push(next_value);
-                        iter_stack.push(ValueIterator::ArrayIterator(v.iter()));
-                        f(DfsEvent::Visit(next_value)).then(|| ())?;
-                    }
-                    Some(v) => {
-                        f(DfsEvent::ForwardEdge(last, v)).then(|| ())?;
-                        f(DfsEvent::Visit(v)).then(|| ())?;
-                        f(DfsEvent::Leave(v)).then(|| ())?;
-                        f(DfsEvent::BackEdge(v, last)).then(|| ())?;
+        }
+        while let Some((parent, last_iter, _)) = stack.last_mut() {
+            let parent = *parent;
+            let next = match last_iter {
+                ValueIterator::ObjectIterator(oi) => oi.next().map(|(k, v)| (JsonIndexerRef::ObjInd(k), v)),
+                ValueIterator::ArrayIterator(ai) => ai.next().map(|(i, v)| (JsonIndexerRef::ArrInd(i), v)),
+            };
+            match next {
+                Some((indexer, child)) => {
+                    if let WalkControl::Prune = f(DfsEvent::ForwardEdge(parent, child, indexer))? {
+                        continue;
                     }
-                    None => {
-                        iter_stack.pop();
-                        if let Some(v) = stack.pop() {
-                            f(DfsEvent::Leave(v)).then(|| ())?;
-                            let parent = stack.last().copied();
-                            parent.and_then(|p| f(DfsEvent::BackEdge(v, p)).then(|| ()))?;
+                    match (f(DfsEvent::Visit(child))?, children(child)) {
+                        (WalkControl::Continue, Some(iter)) => stack.push((child, iter, Some(indexer))),
+                        _ => {
+                            f(DfsEvent::Leave(child))?;
+                            f(DfsEvent::BackEdge(child, parent, indexer))?;
                         }
                     }
                 }
+                None => {
+                    let indexer = stack.pop().and_then(|(_, _, indexer)| indexer);
+                    f(DfsEvent::Leave(parent))?;
+                    if let (Some(&(grandparent, _, _)), Some(indexer)) = (stack.last(), indexer) {
+                        f(DfsEvent::BackEdge(parent, grandparent, indexer))?;
+                    }
+                }
             }
-            Some(())
-        };
-        fun().is_some()
+        }
+        ControlFlow::Continue(())
     }
 
-    /// get json visitor it will visit [`Value`] with bfs order.
+    /// get max depth, that is number of edges from this value to its deepest descendant. scalar value, `{}` and
+    /// `[]` have depth 0. this method does not recurse, so it can be used for deeply nested value.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{ "key": [ 1, "two", 3, { "foo": { "bar": "baz" } } ] }"#).unwrap();
+    /// assert_eq!(json.max_depth(), 4);
+    /// assert_eq!(json["key"][0].max_depth(), 0);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        let (mut depth, mut max_depth) = (0, 0);
+        self.walk(|event| {
+            match event {
+                DfsEvent::ForwardEdge(_, _, _) => {
+                    depth += 1;
+                    max_depth = max_depth.max(depth);
+                }
+                DfsEvent::BackEdge(_, _, _) => depth -= 1,
+                DfsEvent::Visit(_) | DfsEvent::Leave(_) => (),
+            }
+            true
+        });
+        max_depth
+    }
+
+    /// get json visitor it will visit leaves of [`Value`] with dfs order. see [`Value::bfs`] for bfs order.
     /// # examples
     /// ```
     /// use dyson::Value;
@@ -114,13 +193,88 @@ impl Value {
     ///     assert_eq!(visited, &expected);
     /// }
     /// ```
-    pub fn visitor(&self) -> DfsVisitor {
+    pub fn visitor(&self) -> DfsVisitor<'_> {
         match self {
             Value::Object(m) => DfsVisitor { stack: vec![ValueIterator::ObjectIterator(m.iter())], first: None },
-            Value::Array(v) => DfsVisitor { stack: vec![ValueIterator::ArrayIterator(v.iter())], first: None },
+            Value::Array(v) => {
+                DfsVisitor { stack: vec![ValueIterator::ArrayIterator(v.iter().enumerate())], first: None }
+            }
             v => DfsVisitor { stack: vec![], first: Some(v) },
         }
     }
+
+    /// get json visitor it will visit [`Value`] and all of its descendants, including objects and arrays, with bfs
+    /// order, that is level order. this value itself is visited first.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"a": [1, {"b": 2}], "c": 3}"#).unwrap();
+    ///
+    /// let visited: Vec<_> = json.bfs().collect();
+    /// assert_eq!(visited, vec![&json, &json["a"], &json["c"], &json["a"][0], &json["a"][1], &json["a"][1]["b"]]);
+    /// ```
+    pub fn bfs(&self) -> BfsVisitor<'_> {
+        BfsVisitor { queue: std::iter::once(self).collect() }
+    }
+
+    /// consume [`Value`] and get json visitor it will visit owned leaves with dfs order like [`Value::visitor`]. each
+    /// leaf is moved out of the tree without cloning, and this visitor does not recurse.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let raw_json = r#"{ "key": [ 1, "two", 3, { "foo": { "bar": "baz" } } ] }"#;
+    /// let json = Value::parse(raw_json).unwrap();
+    ///
+    /// let visited: Vec<_> = json.into_visitor().collect();
+    /// assert_eq!(visited, vec![Value::from(1), Value::from("two"), Value::from(3), Value::from("baz")]);
+    /// ```
+    pub fn into_visitor(self) -> IntoDfsVisitor {
+        IntoDfsVisitor { stack: Vec::new(), first: Some(self) }
+    }
+
+    /// get json visitor it will visit [`Value`] and all of its descendants, including objects and arrays, with their
+    /// paths in dfs order. this value itself is visited first with empty path.
+    /// # examples
+    /// ```
+    /// use dyson::Value;
+    /// let json = Value::parse(r#"{"key": [1, {"foo": "bar"}]}"#).unwrap();
+    ///
+    /// let visited: Vec<_> = json.visitor_with_paths().map(|(path, v)| (path.to_string(), v)).collect();
+    /// assert_eq!(visited, vec![
+    ///     ("".to_string(), &json),
+    ///     (r#""key""#.to_string(), &json["key"]),
+    ///     (r#""key">0"#.to_string(), &Value::Integer(1)),
+    ///     (r#""key">1"#.to_string(), &json["key"][1]),
+    ///     (r#""key">1>"foo""#.to_string(), &Value::from("bar")),
+    /// ]);
+    /// ```
+    pub fn visitor_with_paths(&self) -> PathVisitor<'_> {
+        PathVisitor { stack: Vec::new(), path: JsonPath::new(), first: Some(self) }
+    }
+}
+
+impl<'a> ValueIterator<'a> {
+    fn children(value: &'a Value) -> Option<Self> {
+        match value {
+            Value::Object(m) => Some(ValueIterator::ObjectIterator(m.iter())),
+            Value::Array(v) => Some(ValueIterator::ArrayIterator(v.iter().enumerate())),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Iterator for BfsVisitor<'a> {
+    type Item = &'a Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.queue.pop_front()?;
+        match next {
+            Value::Object(m) => self.queue.extend(m.values()),
+            Value::Array(v) => self.queue.extend(v.iter()),
+            _ => (),
+        }
+        Some(next)
+    }
 }
 
 impl<'a> Iterator for DfsVisitor<'a> {
@@ -133,11 +287,11 @@ impl<'a> Iterator for DfsVisitor<'a> {
             while let Some(last) = self.stack.last_mut() {
                 let next = match last {
                     ValueIterator::ObjectIterator(oi) => oi.next().map(|(_k, v)| v),
-                    ValueIterator::ArrayIterator(ai) => ai.next(),
+                    ValueIterator::ArrayIterator(ai) => ai.next().map(|(_i, v)| v),
                 };
                 match next {
                     Some(Value::Object(m)) => self.stack.push(ValueIterator::ObjectIterator(m.iter())),
-                    Some(Value::Array(v)) => self.stack.push(ValueIterator::ArrayIterator(v.iter())),
+                    Some(Value::Array(v)) => self.stack.push(ValueIterator::ArrayIterator(v.iter().enumerate())),
                     Some(v) => return Some(v),
                     None => {
                         self.stack.pop();
@@ -149,6 +303,74 @@ impl<'a> Iterator for DfsVisitor<'a> {
     }
 }
 
+impl IntoValueIterator {
+    fn children(value: &mut Value) -> Option<Self> {
+        match value {
+            Value::Object(m) => Some(IntoValueIterator::ObjectIterator(std::mem::take(&mut **m).into_iter())),
+            Value::Array(v) => Some(IntoValueIterator::ArrayIterator(std::mem::take(v).into_iter())),
+            _ => None,
+        }
+    }
+}
+
+impl Iterator for IntoDfsVisitor {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let next = match self.first.take() {
+                Some(first) => Some(first),
+                None => match self.stack.last_mut()? {
+                    IntoValueIterator::ObjectIterator(oi) => oi.next().map(|(_k, v)| v),
+                    IntoValueIterator::ArrayIterator(ai) => ai.next(),
+                },
+            };
+            match next {
+                Some(mut v) => match IntoValueIterator::children(&mut v) {
+                    Some(children) => self.stack.push(children),
+                    None => return Some(v),
+                },
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for PathVisitor<'a> {
+    type Item = (JsonPath, &'a Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first.take() {
+            self.stack.extend(ValueIterator::children(first));
+            return Some((self.path.clone(), first));
+        }
+        while let Some(last) = self.stack.last_mut() {
+            let next = match last {
+                ValueIterator::ObjectIterator(oi) => oi.next().map(|(k, v)| (JsonIndexer::ObjInd(k.to_string()), v)),
+                ValueIterator::ArrayIterator(ai) => ai.next().map(|(i, v)| (JsonIndexer::ArrInd(i), v)),
+            };
+            match next {
+                Some((indexer, v)) => {
+                    let mut path = self.path.clone();
+                    path.push(indexer);
+                    if let Some(children) = ValueIterator::children(v) {
+                        self.stack.push(children);
+                        self.path = path.clone();
+                    }
+                    return Some((path, v));
+                }
+                None => {
+                    self.stack.pop();
+                    self.path.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -164,15 +386,15 @@ mod tests {
         assert!(json.walk(|event| match event {
             DfsEvent::Visit(v) => assert_eq!(v, &Value::String("rust".into())) == (),
             DfsEvent::Leave(v) => assert_eq!(v, &Value::String("rust".into())) == (),
-            DfsEvent::ForwardEdge(_, _) => unreachable!("one element json has no edge"),
-            DfsEvent::BackEdge(_, _) => unreachable!("one element json has no edge"),
+            DfsEvent::ForwardEdge(_, _, _) => unreachable!("one element json has no edge"),
+            DfsEvent::BackEdge(_, _, _) => unreachable!("one element json has no edge"),
         }));
 
         assert!(!json.walk(|event| match event {
             DfsEvent::Visit(_) => false,
             DfsEvent::Leave(_) => unreachable!("when visit first node, return false"),
-            DfsEvent::ForwardEdge(_, _) => unreachable!("one element json has no edge"),
-            DfsEvent::BackEdge(_, _) => unreachable!("one element json has no edge"),
+            DfsEvent::ForwardEdge(_, _, _) => unreachable!("one element json has no edge"),
+            DfsEvent::BackEdge(_, _, _) => unreachable!("one element json has no edge"),
         }));
     }
 
@@ -185,37 +407,200 @@ mod tests {
         println!("{}", json.walk(|event| events.push(event) == ()));
         let mut iter = events.iter();
         assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json)));
-        assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json, &json["key"])));
+        assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json, &json["key"], JsonIndexerRef::ObjInd("key"))));
         assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json["key"])));
         {
-            assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][0])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][0], JsonIndexerRef::ArrInd(0)))
+            );
             assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json["key"][0])));
             assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json["key"][0])));
-            assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"][0], &json["key"])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::BackEdge(&json["key"][0], &json["key"], JsonIndexerRef::ArrInd(0)))
+            );
 
-            assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][1])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][1], JsonIndexerRef::ArrInd(1)))
+            );
             assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json["key"][1])));
             assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json["key"][1])));
-            assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"][1], &json["key"])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::BackEdge(&json["key"][1], &json["key"], JsonIndexerRef::ArrInd(1)))
+            );
 
-            assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][2])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::ForwardEdge(&json["key"], &json["key"][2], JsonIndexerRef::ArrInd(2)))
+            );
             assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json["key"][2])));
             {
-                assert_eq!(iter.next(), Some(&DfsEvent::ForwardEdge(&json["key"][2], &json["key"][2]["foo"])));
+                assert_eq!(
+                    iter.next(),
+                    Some(&DfsEvent::ForwardEdge(
+                        &json["key"][2],
+                        &json["key"][2]["foo"],
+                        JsonIndexerRef::ObjInd("foo")
+                    ))
+                );
                 assert_eq!(iter.next(), Some(&DfsEvent::Visit(&json["key"][2]["foo"])));
                 assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json["key"][2]["foo"])));
-                assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"][2]["foo"], &json["key"][2])));
+                assert_eq!(
+                    iter.next(),
+                    Some(&DfsEvent::BackEdge(&json["key"][2]["foo"], &json["key"][2], JsonIndexerRef::ObjInd("foo")))
+                );
             }
             assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json["key"][2])));
-            assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"][2], &json["key"])));
+            assert_eq!(
+                iter.next(),
+                Some(&DfsEvent::BackEdge(&json["key"][2], &json["key"], JsonIndexerRef::ArrInd(2)))
+            );
         }
         assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json["key"])));
-        assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"], &json)));
+        assert_eq!(iter.next(), Some(&DfsEvent::BackEdge(&json["key"], &json, JsonIndexerRef::ObjInd("key"))));
 
         assert_eq!(iter.next(), Some(&DfsEvent::Leave(&json)));
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_walk_edge_indexer() {
+        let json = Value::parse(r#"{"a": [1, {"b": [true, null]}], "": {"c": []}, "d": "e"}"#).unwrap();
+        let (mut forward, mut back) = (0, 0);
+        json.walk(|event| {
+            match event {
+                DfsEvent::ForwardEdge(parent, child, indexer) => {
+                    forward += 1;
+                    assert_eq!(&parent[indexer], child);
+                }
+                DfsEvent::BackEdge(child, parent, indexer) => {
+                    back += 1;
+                    assert_eq!(&parent[indexer], child);
+                }
+                DfsEvent::Visit(_) | DfsEvent::Leave(_) => (),
+            }
+            true
+        });
+        assert_eq!((forward, back), (9, 9));
+    }
+
+    #[test]
+    fn test_visitor_with_paths() {
+        let json = Value::parse(r#"[{"a": [], "b": {"c": [null, 1]}}, "d", {}]"#).unwrap();
+        let visited: Vec<_> = json.visitor_with_paths().collect();
+        assert_eq!(visited.len(), 9);
+        for (path, value) in &visited {
+            assert_eq!(&&json[path], value);
+        }
+        let leaves: Vec<_> = visited.iter().filter(|(_, v)| !v.is_object() && !v.is_array()).map(|(_, v)| *v).collect();
+        assert_eq!(leaves, json.visitor().collect::<Vec<_>>());
+        assert_eq!(Value::Null.visitor_with_paths().collect::<Vec<_>>(), vec![(JsonPath::new(), &Value::Null)]);
+
+        let mut deep = Value::Null;
+        for _ in 0..1_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        let (path, value) = deep.visitor_with_paths().last().unwrap();
+        assert_eq!((path.depth(), value), (1_000, &Value::Null));
+    }
+
+    #[test]
+    fn test_into_visitor() {
+        for json in ["1", "{}", "[[], {}]", r#"{"a": [1, {"b": [true, null]}], "": {"c": []}, "d": "e"}"#] {
+            let value = Value::parse(json).unwrap();
+            let expected: Vec<_> = value.visitor().cloned().collect();
+            assert_eq!(value.into_visitor().collect::<Vec<_>>(), expected, "{json}");
+        }
+
+        let mut deep = Value::Null;
+        for _ in 0..100_000 {
+            deep = Value::Object(Box::new([("a".into(), Value::Array(vec![deep]))].into_iter().collect()));
+        }
+        assert_eq!(deep.into_visitor().collect::<Vec<_>>(), vec![Value::Null]);
+    }
+
+    #[test]
+    fn test_bfs() {
+        let json = Value::parse(r#"[{"a": [[1]], "b": 2}, [3, [4]], 5]"#).unwrap();
+        let visited: Vec<_> = json.bfs().map(|v| v.to_string()).collect();
+        let levels = [
+            vec![r#"[{"a":[[1]],"b":2},[3,[4]],5]"#],
+            vec![r#"{"a":[[1]],"b":2}"#, "[3,[4]]", "5"],
+            vec!["[[1]]", "2", "3", "[4]"],
+            vec!["[1]", "4"],
+            vec!["1"],
+        ];
+        assert_eq!(visited, levels.concat());
+        assert_eq!(json.bfs().count(), json.visitor_with_paths().count());
+        assert_eq!(Value::Null.bfs().collect::<Vec<_>>(), vec![&Value::Null]);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        for (json, depth) in [("1", 0), ("{}", 0), ("[]", 0), ("[1, 2]", 1), (r#"[[], {"a": [[true]]}, [1]]"#, 4)] {
+            assert_eq!(Value::parse(json).unwrap().max_depth(), depth, "{json}");
+        }
+        let mut deep = Value::Null;
+        for _ in 0..100_000 {
+            deep = Value::Array(vec![deep]);
+        }
+        assert_eq!(deep.max_depth(), 100_000);
+    }
+
+    #[test]
+    fn test_walk_ctl() {
+        let json = Value::parse(r#"{"a": [1, {"b": 2}], "c": {"d": [3]}, "e": 4}"#).unwrap();
+        let mut events = Vec::new();
+        let flow = json.walk_ctl(|event| {
+            let control = match event {
+                DfsEvent::Visit(v) if v == &json["a"][1] => WalkControl::Prune,
+                DfsEvent::ForwardEdge(_, v, _) if v == &json["c"] => WalkControl::Prune,
+                _ => WalkControl::Continue,
+            };
+            events.push(event);
+            ControlFlow::<(), _>::Continue(control)
+        });
+        assert_eq!(flow, ControlFlow::Continue(()));
+        let expected = vec![
+            DfsEvent::Visit(&json),
+            DfsEvent::ForwardEdge(&json, &json["a"], JsonIndexerRef::ObjInd("a")),
+            DfsEvent::Visit(&json["a"]),
+            DfsEvent::ForwardEdge(&json["a"], &json["a"][0], JsonIndexerRef::ArrInd(0)),
+            DfsEvent::Visit(&json["a"][0]),
+            DfsEvent::Leave(&json["a"][0]),
+            DfsEvent::BackEdge(&json["a"][0], &json["a"], JsonIndexerRef::ArrInd(0)),
+            DfsEvent::ForwardEdge(&json["a"], &json["a"][1], JsonIndexerRef::ArrInd(1)),
+            DfsEvent::Visit(&json["a"][1]),
+            DfsEvent::Leave(&json["a"][1]),
+            DfsEvent::BackEdge(&json["a"][1], &json["a"], JsonIndexerRef::ArrInd(1)),
+            DfsEvent::Leave(&json["a"]),
+            DfsEvent::BackEdge(&json["a"], &json, JsonIndexerRef::ObjInd("a")),
+            DfsEvent::ForwardEdge(&json, &json["c"], JsonIndexerRef::ObjInd("c")),
+            DfsEvent::ForwardEdge(&json, &json["e"], JsonIndexerRef::ObjInd("e")),
+            DfsEvent::Visit(&json["e"]),
+            DfsEvent::Leave(&json["e"]),
+            DfsEvent::BackEdge(&json["e"], &json, JsonIndexerRef::ObjInd("e")),
+            DfsEvent::Leave(&json),
+        ];
+        assert_eq!(events, expected);
+
+        let mut visited = 0;
+        let flow = json.walk_ctl(|event| match event {
+            DfsEvent::Visit(Value::Integer(i)) if *i == 2 => ControlFlow::Break(*i),
+            DfsEvent::Visit(_) => {
+                visited += 1;
+                ControlFlow::Continue(WalkControl::Continue)
+            }
+            _ => ControlFlow::Continue(WalkControl::Continue),
+        });
+        assert_eq!((flow, visited), (ControlFlow::Break(2), 4));
+        assert!(json.walk(|_| true));
+        assert!(Value::Null.walk_ctl(|_| ControlFlow::<(), _>::Continue(WalkControl::Prune)).is_continue());
+    }
+
     #[test]
     fn test_visit_json() {
         #[derive(Hash, PartialEq, Eq, Debug)]
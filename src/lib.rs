@@ -55,13 +55,36 @@
 //! ```
 //! more, see [`Value`] also.
 
+#[macro_use]
+mod macros;
 pub mod ast;
 pub mod syntax;
 
-pub use ast::index::{JsonIndexer, Ranger};
-pub use ast::index_path::JsonPath;
-pub use ast::io::Indent;
-pub use ast::visit::DfsEvent;
+pub use ast::builder::ValueBuilder;
+pub use ast::cache::DocumentCache;
+pub use ast::edit::SetOptions;
+pub use ast::format::{ExponentStyle, FormatOptions, LineEnding};
+pub use ast::index::{JsonIndexer, JsonIndexerRef, Ranger};
+pub use ast::index_path::{JsonPath, PathError, StaticIndexer, StaticPath};
+pub use ast::into::ConvertError;
+pub use ast::io::{Indent, JsonFormatter};
+pub use ast::map::{Key, Map};
+pub use ast::number::{Number, RawNumber};
+pub use ast::patch::{diff_as_patch, Patch, PatchError};
+pub use ast::query::QueryError;
+pub use ast::refs::RefError;
+#[cfg(feature = "serde")]
+pub use ast::serde::{from_value, to_value, SerdeError};
+#[cfg(feature = "toml")]
+pub use ast::toml::TomlError;
+pub use ast::visit::{DfsEvent, WalkControl};
 pub use ast::Value;
+pub use syntax::cst::Document;
+pub use syntax::error::{CstError, Error, ParseError, Position};
+pub use syntax::parser::{ParseReport, ParseWarning, ParserOptions};
+pub use syntax::span::Span;
+pub use syntax::validate::extract;
 
-pub use ast::diff::{diff_value, diff_value_detail};
+pub use ast::diff::{
+    diff_dirs, diff_value, diff_value_detail, diff_value_lenient, diff_value_lenient_numeric, FileDiff,
+};
@@ -0,0 +1,109 @@
+/// construct [`Value`](crate::Value) from json-like literal, like `serde_json::json!`.
+/// value which is not literal of json, such as variable or expression, is converted by [`From`].
+/// key of object is converted into [`Key`](crate::Key) by [`From`], so it can be also variable or expression.
+/// # examples
+/// ```
+/// use dyson::{dyson, Value};
+/// let version = 0.1;
+/// let json = dyson!({
+///     "language": "rust",
+///     "version": version,
+///     "keyword": [1, "two", null, {"nested": true}],
+///     "empty": {},
+/// });
+/// assert_eq!(
+///     json,
+///     Value::parse(r#"{"language": "rust", "version": 0.1, "keyword": [1, "two", null, {"nested": true}], "empty": {}}"#)
+///         .unwrap()
+/// );
+/// ```
+#[macro_export]
+macro_rules! dyson {
+    // munch tokens of array element until `,`
+    (@array [$($elements:expr,)*] ()) => {
+        ::std::vec![$($elements,)*]
+    };
+    (@array [$($elements:expr,)*] ($($value:tt)+)) => {
+        ::std::vec![$($elements,)* $crate::dyson!($($value)+)]
+    };
+    (@array [$($elements:expr,)*] ($($value:tt)+) , $($rest:tt)*) => {
+        $crate::dyson!(@array [$($elements,)* $crate::dyson!($($value)+),] () $($rest)*)
+    };
+    (@array [$($elements:expr,)*] ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::dyson!(@array [$($elements,)*] ($($value)* $next) $($rest)*)
+    };
+
+    // munch tokens of object key until `:`, and tokens of object value until `,`
+    (@key $object:ident ()) => {};
+    (@key $object:ident ($($key:tt)+) : $($rest:tt)*) => {
+        $crate::dyson!(@value $object ($($key)+) () $($rest)*)
+    };
+    (@key $object:ident ($($key:tt)+)) => {
+        ::std::compile_error!("expected `:` after key of object")
+    };
+    (@key $object:ident ($($key:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::dyson!(@key $object ($($key)* $next) $($rest)*)
+    };
+    (@value $object:ident ($($key:tt)+) ($($value:tt)+) , $($rest:tt)*) => {
+        $object.insert(<$crate::Key>::from($($key)+), $crate::dyson!($($value)+));
+        $crate::dyson!(@key $object () $($rest)*)
+    };
+    (@value $object:ident ($($key:tt)+) ($($value:tt)+)) => {
+        $object.insert(<$crate::Key>::from($($key)+), $crate::dyson!($($value)+));
+    };
+    (@value $object:ident ($($key:tt)+) ()) => {
+        ::std::compile_error!("expected value after `:` of object")
+    };
+    (@value $object:ident ($($key:tt)+) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::dyson!(@value $object ($($key)+) ($($value)* $next) $($rest)*)
+    };
+
+    (null) => {
+        $crate::Value::Null
+    };
+    ([$($tt:tt)*]) => {
+        $crate::Value::Array($crate::dyson!(@array [] () $($tt)*))
+    };
+    ({}) => {
+        $crate::Value::Object(::std::boxed::Box::default())
+    };
+    ({$($tt:tt)+}) => {{
+        let mut object = $crate::Map::new();
+        $crate::dyson!(@key object () $($tt)+);
+        $crate::Value::Object(::std::boxed::Box::new(object))
+    }};
+    ($other:expr) => {
+        $crate::Value::from($other)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn test_dyson_macro() {
+        assert_eq!(dyson!(null), Value::Null);
+        assert_eq!(dyson!(true), Value::Bool(true));
+        assert_eq!(dyson!(-1), Value::Integer(-1));
+        assert_eq!(dyson!("str"), Value::from("str"));
+        assert_eq!(dyson!([]), Value::Array(Vec::new()));
+        assert_eq!(dyson!({}), Value::parse("{}").unwrap());
+
+        let (key, values) = ("computed".to_string(), vec![Value::Integer(1)]);
+        let json = dyson!({
+            "array": [1, 2.5, "three", null, [false], {}],
+            key: values.len() as i64 + 1,
+            "nested": {"deep": {"deeper": [{"a": null}]}},
+            "expression": Value::from(values),
+        });
+        assert_eq!(
+            json,
+            Value::parse(
+                r#"{"array": [1, 2.5, "three", null, [false], {}], "computed": 2,
+                    "nested": {"deep": {"deeper": [{"a": null}]}}, "expression": [1]}"#
+            )
+            .unwrap()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::bail;
 use clap::{App, Args, Parser, Subcommand};
-use dyson::{diff_value_detail, Indent, Value};
+use dyson::{diff_dirs, diff_value_detail, Indent, Value};
 use std::io::{stdin, stdout};
 
 #[derive(Parser)]
@@ -16,6 +16,9 @@ enum Action {
 
     /// compare two json
     Compare(CompareArg),
+
+    /// compare two json, or two directories of json
+    Diff(DiffArg),
     // Edit { edit: Vec<String> },
 }
 
@@ -24,6 +27,7 @@ fn main() -> anyhow::Result<()> {
     match cli.action {
         Action::Format(arg) => format(arg),
         Action::Compare(arg) => compare(arg),
+        Action::Diff(arg) => diff(arg),
         // Action::Edit { edit } => todo!(),
     }
 }
@@ -85,3 +89,29 @@ fn compare(arg: CompareArg) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[derive(Debug, Args)]
+struct DiffArg {
+    /// input json file (or directory) path
+    path1: String,
+
+    /// input json file (or directory) path2
+    path2: String,
+
+    /// compare `*.json` files in two directories recursively, and print only changed files
+    #[clap(long = "dir")]
+    dir: bool,
+}
+fn diff(arg: DiffArg) -> anyhow::Result<()> {
+    if arg.dir {
+        for diff in diff_dirs(arg.path1, arg.path2)? {
+            println!("{}", diff);
+        }
+    } else {
+        let (json1, json2) = (Value::load(arg.path1)?, Value::load(arg.path2)?);
+        for diff in diff_value_detail(&json1, &json2) {
+            println!("{}", diff);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,419 @@
+use super::{
+    error::CstError,
+    span::{position, Span},
+};
+use crate::ast::{index::JsonIndexer, index_path::JsonPath, into::ConvertError, Value};
+use std::ops::Range;
+
+/// kind of [`Token`] of concrete syntax tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Comma,
+    String,
+    Number,
+    /// `true`, `false`, or `null`.
+    Literal,
+    Whitespace,
+    /// `// line comment` or `/* block comment */`.
+    Comment,
+}
+impl TokenKind {
+    /// trivia is token that do not affect json value, such as whitespace and comment.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, TokenKind::Whitespace | TokenKind::Comment)
+    }
+}
+
+/// [`Token`] of concrete syntax tree. `span` is range in source of [`Document`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NodeKind {
+    Object(Vec<(String, Node)>),
+    Array(Vec<Node>),
+    Scalar(Value),
+}
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    kind: NodeKind,
+    span: Range<usize>,
+}
+
+/// [`Document`] is concrete syntax tree of json. it keep all of source text such as whitespace and comment,
+/// so parsed document can be written back exactly, and can be edited with keeping its format.
+/// # examples
+/// ```
+/// use dyson::{Document, JsonIndexer, JsonPath, Value};
+/// let raw_json = r#"{
+///     // version of this config
+///     "version": 0.1,
+///     "keyword": [ "rust", "json" ] /* trailing comment */
+/// }"#;
+/// let mut document = Document::parse(raw_json).unwrap();
+/// assert_eq!(document.to_string(), raw_json);
+/// assert_eq!(document.value()["version"], Value::Float(0.1));
+///
+/// let path: JsonPath = vec![JsonIndexer::ObjInd("version".to_string())].into_iter().collect();
+/// document.replace(&path, &Value::Float(0.2)).unwrap();
+/// assert_eq!(document.to_string(), raw_json.replace("0.1", "0.2"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document {
+    source: String,
+    tokens: Vec<Token>,
+    root: Node,
+}
+
+impl Document {
+    /// max nesting depth of object and array that [`Document::parse`] accept. concrete syntax tree is walked
+    /// recursively, so deeper document is rejected instead of overflowing stack.
+    pub const MAX_DEPTH: usize = 128;
+
+    /// parse json that may contain comments into concrete syntax tree.
+    /// # errors
+    /// if `source` is not valid json with comments, or it is nested deeper than [`Document::MAX_DEPTH`], error has
+    /// its position.
+    pub fn parse<S: Into<String>>(source: S) -> Result<Self, CstError> {
+        let source = source.into();
+        let tokens = tokenize(&source)?;
+        let mut cursor = Cursor { source: &source, tokens: &tokens, index: 0, depth: 0 };
+        let root = cursor.parse_node()?;
+        if let Some(token) = cursor.peek() {
+            let (start, end) = (token.span.start, position(&source, source.len()));
+            return Err(CstError::FoundSurplus { start, end })?;
+        }
+        Ok(Self { root, tokens, source })
+    }
+
+    /// get all tokens include trivia. concatenation of these tokens is equal to source.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// get json value of this document. trivia such as comment is discarded.
+    pub fn value(&self) -> Value {
+        fn value_recursive(node: &Node) -> Value {
+            match &node.kind {
+                NodeKind::Object(entries) => entries.iter().map(|(k, v)| (k.clone(), value_recursive(v))).collect(),
+                NodeKind::Array(elements) => elements.iter().map(value_recursive).collect(),
+                NodeKind::Scalar(value) => value.clone(),
+            }
+        }
+        value_recursive(&self.root)
+    }
+
+    /// get span of the value specified by path. the span do not include surrounding trivia.
+    /// # examples
+    /// ```
+    /// use dyson::{Document, JsonPath};
+    /// let document = Document::parse("{\n  \"key\": [1, /* two */ 2]\n}").unwrap();
+    /// let span = document.span(&JsonPath::from_dotted("key[1]").unwrap()).unwrap();
+    /// assert_eq!((span.start, document.slice(&span)), ((1, 23), "2"));
+    /// ```
+    pub fn span(&self, path: &JsonPath) -> Option<Span> {
+        let node = path.iter().try_fold(&self.root, |node, indexer| match (&node.kind, indexer) {
+            (NodeKind::Object(entries), JsonIndexer::ObjInd(key)) => {
+                entries.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            (NodeKind::Array(elements), &JsonIndexer::ArrInd(i)) => elements.get(i),
+            _ => None,
+        })?;
+        Some(Span::new(&self.source, node.span.clone()))
+    }
+
+    /// get source text of `span`.
+    /// # panics
+    /// if `span` is out of source of this document.
+    pub fn slice(&self, span: &Span) -> &str {
+        &self.source[span.byte_range.clone()]
+    }
+
+    /// replace the value specified by path with `value`. only the text of the value is rewritten,
+    /// so whitespace and comments around it are kept.
+    /// # errors
+    /// if the path do not exist in this document.
+    pub fn replace(&mut self, path: &JsonPath, value: &Value) -> Result<(), CstError> {
+        let span = self.span(path).ok_or_else(|| CstError::NoSuchPath { path: path.clone() })?;
+        let mut source = String::with_capacity(self.source.len());
+        source.push_str(&self.source[..span.byte_range.start]);
+        source.push_str(&value.to_string());
+        source.push_str(&self.source[span.byte_range.end..]);
+        *self = Self::parse(source)?;
+        Ok(())
+    }
+}
+
+impl AsRef<str> for Document {
+    fn as_ref(&self) -> &str {
+        &self.source
+    }
+}
+
+impl std::fmt::Display for Document {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, CstError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    let mut cursor = (0, 0);
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '{' => TokenKind::LeftBrace,
+            '}' => TokenKind::RightBrace,
+            '[' => TokenKind::LeftBracket,
+            ']' => TokenKind::RightBracket,
+            ':' => TokenKind::Colon,
+            ',' => TokenKind::Comma,
+            ' ' | '\n' | '\r' | '\t' => {
+                while chars.next_if(|&(_, c)| matches!(c, ' ' | '\n' | '\r' | '\t')).is_some() {}
+                TokenKind::Whitespace
+            }
+            '/' => match chars.next() {
+                Some((_, '/')) => {
+                    while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+                    TokenKind::Comment
+                }
+                Some((_, '*')) => {
+                    let mut prev = ' ';
+                    loop {
+                        match chars.next() {
+                            Some((_, '/')) if prev == '*' => break TokenKind::Comment,
+                            Some((_, c)) => prev = c,
+                            None => Err(CstError::UnterminatedComment { pos: position(source, start) })?,
+                        }
+                    }
+                }
+                _ => Err(CstError::UnexpectedChar { found: c, pos: position(source, start) })?,
+            },
+            '"' => {
+                let mut escaped = false;
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) if !escaped => break TokenKind::String,
+                        Some((_, '\n')) | None => Err(CstError::UnterminatedString { pos: position(source, start) })?,
+                        Some((_, c)) => escaped = !escaped && c == '\\',
+                    }
+                }
+            }
+            '-' | '0'..='9' => {
+                while chars.next_if(|&(_, c)| matches!(c, '0'..='9' | '+' | '-' | '.' | 'e' | 'E')).is_some() {}
+                TokenKind::Number
+            }
+            'a'..='z' => {
+                while chars.next_if(|&(_, c)| c.is_ascii_alphanumeric()).is_some() {}
+                TokenKind::Literal
+            }
+            c => Err(CstError::UnexpectedChar { found: c, pos: position(source, start) })?,
+        };
+        let end = chars.peek().map_or(source.len(), |&(i, _)| i);
+        let start_pos = cursor;
+        // tokens are contiguous, so position is computed incrementally from previous token
+        for c in source[start..end].chars() {
+            cursor = if c == '\n' { (cursor.0 + 1, 0) } else { (cursor.0, cursor.1 + 1) };
+        }
+        tokens.push(Token { kind, span: Span { start: start_pos, end: cursor, byte_range: start..end } });
+    }
+    Ok(tokens)
+}
+
+struct Cursor<'a> {
+    source: &'a str,
+    tokens: &'a [Token],
+    index: usize,
+    /// nesting depth of object and array that is being parsed.
+    depth: usize,
+}
+impl<'a> Cursor<'a> {
+    /// peek next non trivia token.
+    fn peek(&mut self) -> Option<&'a Token> {
+        while self.tokens.get(self.index).map_or(false, |t| t.kind.is_trivia()) {
+            self.index += 1;
+        }
+        self.tokens.get(self.index)
+    }
+
+    /// read next non trivia token.
+    fn next(&mut self) -> Option<&'a Token> {
+        let token = self.peek();
+        self.index += 1;
+        token
+    }
+
+    /// read next non trivia token, it is expected `kind`.
+    fn expect(&mut self, kind: TokenKind) -> Result<&'a Token, CstError> {
+        match self.next() {
+            Some(token) if token.kind == kind => Ok(token),
+            Some(token) => Err(CstError::UnexpectedToken {
+                expected: kind,
+                found: self.source[token.span.byte_range.clone()].to_string(),
+                pos: token.span.start,
+            })?,
+            None => Err(CstError::UnexpectedEof { expected: kind, pos: position(self.source, self.source.len()) })?,
+        }
+    }
+
+    /// parse scalar token by [`Value::parse`], and report error with position in whole source.
+    fn scalar(&self, token: &Token) -> Result<Value, CstError> {
+        Value::parse(&self.source[token.span.byte_range.clone()])
+            .map_err(|e| CstError::InvalidScalar { message: e.to_string(), pos: token.span.start })
+    }
+
+    fn parse_node(&mut self) -> Result<Node, CstError> {
+        let token = self.next().ok_or_else(|| CstError::UnexpectedEof {
+            expected: TokenKind::LeftBrace,
+            pos: position(self.source, self.source.len()),
+        })?;
+        let start = token.span.byte_range.start;
+        if matches!(token.kind, TokenKind::LeftBrace | TokenKind::LeftBracket) {
+            self.depth += 1;
+            if self.depth > Document::MAX_DEPTH {
+                Err(CstError::DepthLimitExceeded { limit: Document::MAX_DEPTH, pos: token.span.start })?;
+            }
+        }
+        let kind = match token.kind {
+            TokenKind::LeftBrace => {
+                let mut entries = Vec::new();
+                while self.peek().map(|t| t.kind) != Some(TokenKind::RightBrace) {
+                    let key_token = self.expect(TokenKind::String)?;
+                    let key = self.scalar(key_token)?.try_into().map_err(|e: ConvertError| {
+                        CstError::InvalidScalar { message: e.to_string(), pos: key_token.span.start }
+                    })?;
+                    self.expect(TokenKind::Colon)?;
+                    entries.push((key, self.parse_node()?));
+                    if self.peek().map(|t| t.kind) != Some(TokenKind::Comma) {
+                        break;
+                    }
+                    let comma = self.expect(TokenKind::Comma)?;
+                    if self.peek().map(|t| t.kind) == Some(TokenKind::RightBrace) {
+                        Err(CstError::TrailingComma { pos: comma.span.start })?;
+                    }
+                }
+                self.expect(TokenKind::RightBrace)?;
+                self.depth -= 1;
+                NodeKind::Object(entries)
+            }
+            TokenKind::LeftBracket => {
+                let mut elements = Vec::new();
+                while self.peek().map(|t| t.kind) != Some(TokenKind::RightBracket) {
+                    elements.push(self.parse_node()?);
+                    if self.peek().map(|t| t.kind) != Some(TokenKind::Comma) {
+                        break;
+                    }
+                    let comma = self.expect(TokenKind::Comma)?;
+                    if self.peek().map(|t| t.kind) == Some(TokenKind::RightBracket) {
+                        Err(CstError::TrailingComma { pos: comma.span.start })?;
+                    }
+                }
+                self.expect(TokenKind::RightBracket)?;
+                self.depth -= 1;
+                NodeKind::Array(elements)
+            }
+            TokenKind::String | TokenKind::Number | TokenKind::Literal => NodeKind::Scalar(self.scalar(token)?),
+            _ => Err(CstError::UnexpectedToken {
+                expected: TokenKind::LeftBrace,
+                found: self.source[token.span.byte_range.clone()].to_string(),
+                pos: token.span.start,
+            })?,
+        };
+        let end = self.tokens[self.index - 1].span.byte_range.end;
+        Ok(Node { kind, span: start..end })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let raw_json = [
+            r#"// header comment"#,
+            r#"{"#,
+            r#"  "language" :  "rust", // trailing comment"#,
+            r#"	"version": 1.230e2,"#,
+            r#"  /* block"#,
+            r#"     comment */ "keyword": ["rust",  "json" , "parser"],"#,
+            r#"  "escaped": "quote \" and \\ and \/","#,
+            r#"  "empty": {  }"#,
+            r#"}"#,
+            r#""#,
+        ]
+        .join("\r\n");
+        let document = Document::parse(&raw_json[..]).unwrap();
+        assert_eq!(document.to_string(), raw_json);
+        assert_eq!(document.tokens().iter().map(|t| document.slice(&t.span)).collect::<String>(), raw_json);
+        assert_eq!(
+            document.value(),
+            Value::parse(
+                r#"{"language": "rust", "version": 123.0, "keyword": ["rust", "json", "parser"],
+                "escaped": "quote \" and \\ and /", "empty": {}}"#
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_replace_keep_format() {
+        let raw_json = "{\n  \"a\": [1, /* two */ 2, 3], // comment\n  \"b\": null\n}";
+        let mut document = Document::parse(raw_json).unwrap();
+
+        let path: JsonPath = vec![JsonIndexer::ObjInd("a".to_string()), JsonIndexer::ArrInd(1)].into_iter().collect();
+        document.replace(&path, &Value::String("two".to_string())).unwrap();
+        assert_eq!(document.to_string(), "{\n  \"a\": [1, /* two */ \"two\", 3], // comment\n  \"b\": null\n}");
+
+        let path: JsonPath = vec![JsonIndexer::ObjInd("b".to_string())].into_iter().collect();
+        document.replace(&path, &Value::parse(r#"{"c": [true]}"#).unwrap()).unwrap();
+        assert_eq!(
+            document.to_string(),
+            "{\n  \"a\": [1, /* two */ \"two\", 3], // comment\n  \"b\": {\"c\":[true]}\n}"
+        );
+        assert_eq!(document.value()["b"]["c"][0], Value::Bool(true));
+
+        let path: JsonPath = vec![JsonIndexer::ObjInd("nothing".to_string())].into_iter().collect();
+        assert!(matches!(document.replace(&path, &Value::Null), Err(CstError::NoSuchPath { .. })));
+    }
+
+    #[test]
+    fn test_span_and_slice() {
+        let raw_json = "{\r\n  \"あ\": [1, \"two\"]\r\n}";
+        let document = Document::parse(raw_json).unwrap();
+        let path: JsonPath = vec![JsonIndexer::ObjInd("あ".to_string()), JsonIndexer::ArrInd(1)].into_iter().collect();
+        let span = document.span(&path).unwrap();
+        assert_eq!((span.start, span.end, document.slice(&span)), ((1, 11), (1, 16), "\"two\""));
+        assert!(document.tokens().iter().all(|t| Span::new(raw_json, t.span.byte_range.clone()) == t.span));
+        assert_eq!(document.as_ref(), raw_json);
+    }
+
+    #[test]
+    fn test_invalid_document() {
+        let err = Document::parse("{\"a\": 1,}").unwrap_err();
+        assert!(err.to_string().contains("trailing comma"));
+        let err = Document::parse("[1, 2] /* not closed").unwrap_err();
+        assert!(err.to_string().contains("comment"));
+        let err = Document::parse("[1, 2]\n3").unwrap_err();
+        assert!(err.to_string().contains("line 2 (col 1)"));
+        let err = Document::parse("[1, nul]").unwrap_err();
+        assert!(err.to_string().contains("line 1 (col 5)"));
+        let err = Document::parse("{\"a\" 1}").unwrap_err();
+        assert!(err.to_string().contains("Colon"));
+
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(Document::parse(nested(Document::MAX_DEPTH)).is_ok());
+        let err = Document::parse(nested(Document::MAX_DEPTH + 1)).unwrap_err();
+        assert!(matches!(err, CstError::DepthLimitExceeded { limit: Document::MAX_DEPTH, pos: (0, 128) }));
+        let err = Document::parse("[".repeat(100_000)).unwrap_err();
+        assert!(matches!(err, CstError::DepthLimitExceeded { .. }));
+    }
+}
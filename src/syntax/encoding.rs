@@ -0,0 +1,161 @@
+//! detect encoding of json input and decode it. encoding is detected by BOM, or by pattern of null bytes in the first
+//! 4 bytes like RFC 4627, because json text start with two ASCII characters.
+
+use std::io;
+
+/// [`Encoding`] is encoding of json input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+impl Encoding {
+    /// detect encoding from first bytes of input, and return it with byte length of BOM.
+    pub(crate) fn detect(head: &[u8]) -> (Self, usize) {
+        match head {
+            [0xEF, 0xBB, 0xBF, ..] => (Encoding::Utf8, 3),
+            [0x00, 0x00, 0xFE, 0xFF, ..] => (Encoding::Utf32Be, 4),
+            [0xFF, 0xFE, 0x00, 0x00, ..] => (Encoding::Utf32Le, 4),
+            [0xFE, 0xFF, ..] => (Encoding::Utf16Be, 2),
+            [0xFF, 0xFE, ..] => (Encoding::Utf16Le, 2),
+            [0x00, 0x00, 0x00, _, ..] => (Encoding::Utf32Be, 0),
+            [_, 0x00, 0x00, 0x00, ..] => (Encoding::Utf32Le, 0),
+            [0x00, _, 0x00, _, ..] | [0x00, _] => (Encoding::Utf16Be, 0),
+            [_, 0x00, _, 0x00, ..] | [_, 0x00] => (Encoding::Utf16Le, 0),
+            _ => (Encoding::Utf8, 0),
+        }
+    }
+}
+
+/// decode chars of `bytes` by encoding detected from its first 4 bytes, rest of `bytes` is decoded lazily. BOM is
+/// removed. if `bytes` is not valid in the encoding, decoded iterator return error of [`io::ErrorKind::InvalidData`].
+pub(crate) fn decode<'a, I>(mut bytes: I) -> io::Result<impl 'a + Iterator<Item = io::Result<char>>>
+where
+    I: 'a + Iterator<Item = io::Result<u8>>,
+{
+    let mut head = Vec::with_capacity(4);
+    while head.len() < 4 {
+        match bytes.next() {
+            Some(b) => head.push(b?),
+            None => break,
+        }
+    }
+    let (encoding, bom) = Encoding::detect(&head);
+    let mut bytes = head.into_iter().skip(bom).map(Ok).chain(bytes);
+    Ok(std::iter::from_fn(move || match encoding {
+        Encoding::Utf8 => read_utf8(&mut bytes).transpose(),
+        _ => read_wide(&mut bytes, encoding).transpose(),
+    }))
+}
+
+/// read one UTF-16 or UTF-32 char of `encoding` from `bytes`.
+fn read_wide<I: Iterator<Item = io::Result<u8>>>(bytes: &mut I, encoding: Encoding) -> io::Result<Option<char>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("stream did not contain valid {encoding}"));
+    let mut unit = || -> io::Result<Option<u32>> {
+        let (width, little) = match encoding {
+            Encoding::Utf16Le => (2, true),
+            Encoding::Utf16Be => (2, false),
+            Encoding::Utf32Le => (4, true),
+            _ => (4, false),
+        };
+        let mut buf = [0; 4];
+        match bytes.next() {
+            Some(b) => buf[0] = b?,
+            None => return Ok(None),
+        }
+        for b in buf.iter_mut().take(width).skip(1) {
+            *b = bytes.next().ok_or_else(invalid)??;
+        }
+        let unit = buf[..width].iter().enumerate().fold(0, |u, (i, &b)| match little {
+            true => u | u32::from(b) << (8 * i),
+            false => u << 8 | u32::from(b),
+        });
+        Ok(Some(unit))
+    };
+    let lead = match unit()? {
+        Some(lead) => lead,
+        None => return Ok(None),
+    };
+    let code = match (encoding, lead) {
+        (Encoding::Utf16Le | Encoding::Utf16Be, 0xD800..=0xDBFF) => match unit()? {
+            Some(trail @ 0xDC00..=0xDFFF) => 0x10000 + ((lead - 0xD800) << 10) + (trail - 0xDC00),
+            _ => return Err(invalid()),
+        },
+        _ => lead,
+    };
+    char::from_u32(code).map(Some).ok_or_else(invalid)
+}
+
+/// read one UTF-8 char from `bytes`. if `bytes` is not valid UTF-8, return error of [`io::ErrorKind::InvalidData`].
+pub(crate) fn read_utf8<I: Iterator<Item = io::Result<u8>>>(bytes: &mut I) -> io::Result<Option<char>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8");
+    let lead = match bytes.next() {
+        Some(b) => b?,
+        None => return Ok(None),
+    };
+    let width = match lead {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return Err(invalid()),
+    };
+    let mut buf = [lead, 0, 0, 0];
+    for b in buf.iter_mut().take(width).skip(1) {
+        *b = bytes.next().ok_or_else(invalid)??;
+    }
+    let decoded = std::str::from_utf8(&buf[..width]).ok().and_then(|s| s.chars().next());
+    decoded.map(Some).ok_or_else(invalid)
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::Utf8 => write!(f, "UTF-8"),
+            Encoding::Utf16Le => write!(f, "UTF-16LE"),
+            Encoding::Utf16Be => write!(f, "UTF-16BE"),
+            Encoding::Utf32Le => write!(f, "UTF-32LE"),
+            Encoding::Utf32Be => write!(f, "UTF-32BE"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Encoding::detect(b"\xEF\xBB\xBF{}"), (Encoding::Utf8, 3));
+        assert_eq!(Encoding::detect(b"\xFF\xFE{\x00}\x00"), (Encoding::Utf16Le, 2));
+        assert_eq!(Encoding::detect(b"\xFE\xFF\x00{\x00}"), (Encoding::Utf16Be, 2));
+        assert_eq!(Encoding::detect(b"\xFF\xFE\x00\x00{\x00\x00\x00"), (Encoding::Utf32Le, 4));
+        assert_eq!(Encoding::detect(b"\x00\x00\xFE\xFF\x00\x00\x00{"), (Encoding::Utf32Be, 4));
+        assert_eq!(Encoding::detect(b"{\x00}\x00"), (Encoding::Utf16Le, 0));
+        assert_eq!(Encoding::detect(b"\x001"), (Encoding::Utf16Be, 0));
+        assert_eq!(Encoding::detect(b"1\x00\x00\x00"), (Encoding::Utf32Le, 0));
+        assert_eq!(Encoding::detect(b"\x00\x00\x001"), (Encoding::Utf32Be, 0));
+        assert_eq!(Encoding::detect(b"{}"), (Encoding::Utf8, 0));
+        assert_eq!(Encoding::detect(b""), (Encoding::Utf8, 0));
+    }
+
+    #[test]
+    fn test_decode() {
+        let decode = |bytes: Vec<u8>| decode(bytes.into_iter().map(Ok))?.collect::<io::Result<String>>();
+        let text = "[\"あ😀\"]";
+        let utf16le: Vec<_> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let utf32be: Vec<_> = text.chars().flat_map(|c| (c as u32).to_be_bytes()).collect();
+        assert_eq!(decode(utf16le.clone()).unwrap(), text);
+        assert_eq!(decode(utf32be).unwrap(), text);
+        assert_eq!(decode([&b"\xEF\xBB\xBF"[..], text.as_bytes()].concat()).unwrap(), text);
+
+        let err = decode(utf16le[..utf16le.len() - 1].to_vec()).unwrap_err();
+        assert_eq!(err.to_string(), "stream did not contain valid UTF-16LE");
+        // unpaired surrogate
+        let err = decode(b"\xFE\xFF\xD8\x00\x00]".to_vec()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
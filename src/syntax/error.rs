@@ -1,4 +1,9 @@
-use super::token::{SequentialToken, SingleToken, StringToken};
+use super::{
+    cst::TokenKind,
+    span::Span,
+    token::{ImmediateToken, MainToken, NumberToken, SequentialToken, SingleToken, StringToken},
+};
+use crate::ast::{index_path::JsonPath, into::ConvertError, refs::RefError};
 use thiserror::Error;
 
 pub type Position = (usize, usize);
@@ -16,6 +21,102 @@ pub(crate) fn join_token<'a, I: IntoIterator<Item = &'a T>, T: 'a + SingleToken>
     }
 }
 
+/// [`Error`] is error of public api of dyson, such as [`crate::Value::parse`] and [`crate::Value::load`].
+/// # examples
+/// ```
+/// use dyson::{Error, Value};
+/// match Value::parse(r#"{"key": [1, 2,]}"#) {
+///     Err(Error::Syntax { span, .. }) => assert_eq!((span.start, span.byte_range), ((0, 13), 13..13)),
+///     _ => unreachable!("trailing comma is syntax error"),
+/// }
+/// ```
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// failed to read or write.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// input is not valid json at `span`.
+    #[error("{message}")]
+    Syntax { message: String, span: Span },
+
+    /// parsing is aborted at `span` by limits of [`crate::ParserOptions`].
+    #[error("{error}")]
+    Limit { error: ParseError, span: Span },
+
+    /// failed to convert value into rust type.
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+
+    /// failed to resolve `$ref` by [`crate::Value::load_bundled`].
+    #[error(transparent)]
+    Ref(#[from] RefError),
+
+    /// other error.
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+impl Error {
+    /// get span of the error in input, if it has.
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            Error::Syntax { span, .. } | Error::Limit { span, .. } => Some(span),
+            _ => None,
+        }
+    }
+
+    /// convert internal error of parsing into public error. `offset` convert position into byte offset of input.
+    pub(crate) fn located<F: Fn(Position) -> usize>(e: anyhow::Error, offset: F) -> Self {
+        let span = |(start, end): (Position, Position)| Span { start, end, byte_range: offset(start)..offset(end) };
+        macro_rules! syntax {
+            ($($t:ty),*) => {
+                $(if let Some(spanned) = e.downcast_ref::<$t>() {
+                    return Error::Syntax { message: e.to_string(), span: span(spanned.span()) };
+                })*
+            };
+        }
+        syntax!(
+            SingleTokenError<MainToken>,
+            SingleTokenError<ImmediateToken>,
+            SingleTokenError<StringToken>,
+            SingleTokenError<NumberToken>,
+            SequentialTokenError<ImmediateToken>,
+            SequentialTokenError<StringToken>,
+            ParseTokenError,
+            StructureError,
+            ParseValueError<MainToken>,
+            ParseStringError,
+            ParseNumberError,
+            ValidateError
+        );
+        match e.downcast::<ParseError>() {
+            Ok(error) => {
+                let span = span(error.span());
+                Error::Limit { error, span }
+            }
+            Err(e) => e.into(),
+        }
+    }
+}
+/// error without position. error of parsing should be converted by [`Error::located`] to get its span.
+impl From<anyhow::Error> for Error {
+    fn from(e: anyhow::Error) -> Self {
+        match e.downcast::<std::io::Error>() {
+            Ok(io) => Error::Io(io),
+            Err(e) => match e.downcast::<ConvertError>() {
+                Ok(convert) => Error::Convert(convert),
+                Err(e) => Error::Other(e),
+            },
+        }
+    }
+}
+
+/// span of error, start and end position.
+pub(crate) trait Spanned {
+    fn span(&self) -> (Position, Position);
+}
+
 #[derive(Error, Debug)]
 pub enum SingleTokenError<T: SingleToken> {
     #[error("{}: expected {}, but found {:?}", postr(pos), join_token(expected, " or "), found)]
@@ -50,6 +151,25 @@ pub enum StructureError {
 
     #[error("{} - {}: found surplus token previous EOF", postr(start), postr(end))]
     FoundSurplus { start: Position, end: Position },
+
+    #[error("{} - {}: unexpected EOF, skipped value is not closed", postr(start), postr(end))]
+    UnexpectedEof { start: Position, end: Position },
+}
+
+#[derive(Error, Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum ParseError {
+    #[error("{}: nesting depth exceeds the limit {}", postr(pos), limit)]
+    DepthLimitExceeded { limit: usize, pos: Position },
+
+    #[error("{}: number of values exceeds the limit {}", postr(pos), limit)]
+    NodeLimitExceeded { limit: usize, pos: Position },
+
+    #[error("{}: length of string exceeds the limit {} bytes", postr(pos), limit)]
+    StringLengthExceeded { limit: usize, pos: Position },
+
+    #[error("{}: number of entries exceeds the limit {}", postr(pos), limit)]
+    EntriesLimitExceeded { limit: usize, pos: Position },
 }
 
 #[derive(Error, Debug)]
@@ -81,6 +201,9 @@ pub enum ParseStringError {
 
     #[error("{} - {}: unexpected escape sequence \"\\{}\"", postr(start), postr(end), escape)]
     UnexpectedEscapeSequence { escape: StringToken, start: Position, end: Position },
+
+    #[error("{}: unescaped control character U+{:04X} is not allowed in string literal", postr(pos), *control as u32)]
+    UnescapedControl { control: char, pos: Position },
 }
 
 #[derive(Error, Debug)]
@@ -96,11 +219,175 @@ pub enum ParseNumberError {
 
     #[error("{}: empty digits is not allowed", postr(pos))]
     EmptyDigits { pos: Position },
+
+    #[error("{}: leading zeros are not allowed", postr(pos))]
+    LeadingZeros { pos: Position },
+
+    #[error("{} - {}: number has more than {} digits", postr(start), postr(end), limit)]
+    TooManyDigits { limit: usize, start: Position, end: Position },
+}
+
+#[derive(Error, Debug)]
+pub enum ValidateError {
+    #[error("{}: expected {}, but found {:?}", postr(pos), expected, found)]
+    UnexpectedChar { expected: &'static str, found: char, pos: Position },
+
+    #[error("{}: expected {}, but found EOF", postr(pos), expected)]
+    UnexpectedEof { expected: &'static str, pos: Position },
+
+    #[error("{}: stream did not contain valid UTF-8", postr(pos))]
+    InvalidUtf8 { pos: Position },
+
+    #[error("{}: escape sequence cannot be converted into unicode", postr(pos))]
+    InvalidUnicode { pos: Position },
+
+    #[error("{}: leading zeros are not allowed", postr(pos))]
+    LeadingZeros { pos: Position },
+
+    #[error("{}: number maybe valid, but cannot be converted into `i64`", postr(pos))]
+    IntegerOverflow { pos: Position },
+}
+
+/// [`CstError`] is error of [`crate::Document`], such as invalid source or missing path.
+#[derive(Error, Debug)]
+pub enum CstError {
+    #[error("{}: unexpected character {:?}", postr(pos), found)]
+    UnexpectedChar { found: char, pos: Position },
+
+    #[error("{}: cannot close block comment", postr(pos))]
+    UnterminatedComment { pos: Position },
+
+    #[error("{}: cannot close string literal", postr(pos))]
+    UnterminatedString { pos: Position },
+
+    #[error("{}: expected {:?}, but found \"{}\"", postr(pos), expected, found)]
+    UnexpectedToken { expected: TokenKind, found: String, pos: Position },
+
+    #[error("{}: expected {:?}, but found EOF", postr(pos), expected)]
+    UnexpectedEof { expected: TokenKind, pos: Position },
+
+    #[error("{}: {}", postr(pos), message)]
+    InvalidScalar { message: String, pos: Position },
+
+    #[error("{}: trailing comma is not allowed in json", postr(pos))]
+    TrailingComma { pos: Position },
+
+    #[error("{} - {}: found surplus token previous EOF", postr(start), postr(end))]
+    FoundSurplus { start: Position, end: Position },
+
+    #[error("no such path: {}", path)]
+    NoSuchPath { path: JsonPath },
+
+    #[error("{}: nesting is deeper than {}", postr(pos), limit)]
+    DepthLimitExceeded { limit: usize, pos: Position },
+}
+
+impl<T: SingleToken> Spanned for SingleTokenError<T> {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedToken { pos, .. } | Self::UnexpectedEof { pos, .. } => (*pos, *pos),
+        }
+    }
+}
+impl<T: SequentialToken> Spanned for SequentialTokenError<T> {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedToken { start, end, .. } | Self::UnexpectedEof { start, end, .. } => (*start, *end),
+        }
+    }
+}
+impl Spanned for ParseTokenError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedWhiteSpace { start, end, .. } | Self::UnexpectedEof { start, end, .. } => (*start, *end),
+        }
+    }
+}
+impl Spanned for StructureError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::TrailingComma { pos } => (*pos, *pos),
+            Self::FoundSurplus { start, end } | Self::UnexpectedEof { start, end } => (*start, *end),
+        }
+    }
+}
+impl Spanned for ParseError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::DepthLimitExceeded { pos, .. }
+            | Self::NodeLimitExceeded { pos, .. }
+            | Self::StringLengthExceeded { pos, .. }
+            | Self::EntriesLimitExceeded { pos, .. } => (*pos, *pos),
+        }
+    }
+}
+impl<T: SingleToken> Spanned for ParseValueError<T> {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::CannotStartParseValue { pos, .. } | Self::UnexpectedEof { pos, .. } => (*pos, *pos),
+        }
+    }
+}
+impl Spanned for ParseStringError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedLinefeed { start, end, .. }
+            | Self::UnexpectedEof { start, end, .. }
+            | Self::UnsupportedEscapeSequence { start, end, .. }
+            | Self::CannotConvertUnicode { start, end, .. }
+            | Self::UnexpectedEscapeSequence { start, end, .. } => (*start, *end),
+            Self::UnescapedControl { pos, .. } => (*pos, *pos),
+        }
+    }
+}
+impl Spanned for ParseNumberError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedEof { start, end, .. }
+            | Self::CannotConvertI64 { start, end, .. }
+            | Self::CannotConvertF64 { start, end, .. }
+            | Self::TooManyDigits { start, end, .. } => (*start, *end),
+            Self::EmptyDigits { pos } | Self::LeadingZeros { pos } => (*pos, *pos),
+        }
+    }
+}
+impl Spanned for ValidateError {
+    fn span(&self) -> (Position, Position) {
+        match self {
+            Self::UnexpectedChar { pos, .. }
+            | Self::UnexpectedEof { pos, .. }
+            | Self::InvalidUtf8 { pos }
+            | Self::InvalidUnicode { pos }
+            | Self::LeadingZeros { pos }
+            | Self::IntegerOverflow { pos } => (*pos, *pos),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ast::Value;
+    use super::*;
+    use crate::{ast::Value, syntax::parser::ParserOptions};
+
+    #[test]
+    fn test_public_error() {
+        let err = Value::parse("[1, tru]").unwrap_err();
+        assert!(matches!(err, Error::Syntax { .. }));
+        assert_eq!(err.span(), Some(&Span { start: (0, 4), end: (0, 8), byte_range: 4..8 }));
+        assert_eq!(err.to_string(), anyhow::Error::from(err).to_string());
+
+        let err = Value::parse("[1] 2").unwrap_err();
+        assert!(matches!(err, Error::Syntax { span: Span { start: (0, 4), .. }, .. }));
+        let err = Value::parse("{\r\n  \"あ\": tru\r\n}").unwrap_err();
+        assert_eq!(err.span().map(|s| s.byte_range.clone()), Some(12..15));
+
+        let err = Value::parse_with("[[1]]", ParserOptions::new().max_depth(1)).unwrap_err();
+        assert!(matches!(err, Error::Limit { error: ParseError::DepthLimitExceeded { limit: 1, .. }, .. }));
+        assert_eq!(err.span().map(|s| s.byte_range.clone()), Some(1..1));
+
+        let err = Value::load("path/to/not/exist.json").unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == std::io::ErrorKind::NotFound));
+    }
 
     #[test]
     fn test_parse_empty() {
@@ -187,6 +474,19 @@ mod tests {
         let overflow = "999999999999999999999999999999999999999999999999999999999999";
         let err = Value::parse(overflow).unwrap_err();
         assert!(err.to_string().contains("maybe valid number"));
+
+        let options = ParserOptions::new().max_number_digits(32);
+        let err = Value::parse_with(format!("[{}]", "9".repeat(1_000_000)), options.clone()).unwrap_err();
+        assert!(err.to_string().contains("more than 32 digits"));
+        assert_eq!(err.span().map(|s| s.byte_range.clone()), Some(1..33));
+        let err = Value::parse_with(format!("0.{}e{}", "1".repeat(20), "1".repeat(12)), options.clone()).unwrap_err();
+        assert!(err.to_string().contains("line 1 (col 1) - line 1 (col 35)"));
+        assert!(Value::parse_with(format!("0.{}e{}", "1".repeat(20), "1".repeat(11)), options).is_ok());
+
+        let options = ParserOptions::new().max_number_digits(3).leading_zeros(true);
+        for (number, ok) in [("012", true), ("-012", true), ("0123", false), ("-0123", false)] {
+            assert_eq!(Value::parse_with(number, options.clone()).is_ok(), ok, "{number}");
+        }
     }
 
     #[test]
@@ -213,6 +513,43 @@ mod tests {
         assert!(err.to_string().contains("surplus"));
     }
 
+    #[test]
+    fn test_adversarial_input() {
+        let json = r#"{"key": [1, -2.5e-3, "t\"wo\u00f9", true, false, null, {"nested": [[], {}]}], "\u0041": 0}"#;
+        assert!(Value::parse(json).is_ok());
+        for (end, _) in json.char_indices() {
+            assert!(Value::parse(&json[..end]).is_err(), "prefix {:?} must be error", &json[..end]);
+        }
+
+        let adversarial = [
+            "\"\\u",
+            "\"\\uùñí©\"",
+            "\"\\u+123\"",
+            "\"\\ud800\"",
+            "\"\\udfff\\ud800\"",
+            "\"\\u12\"",
+            "\"\\",
+            "-",
+            "-e",
+            "1e",
+            "1e+",
+            "1.",
+            "0.e1",
+            "tru",
+            "nul\u{0}",
+            "[,]",
+            "{,}",
+            "{\"a\"}",
+            "{\"a\":}",
+            "\u{feff}[]",
+            "[\u{0}]",
+            "\u{10ffff}",
+        ];
+        for input in adversarial {
+            assert!(Value::parse(input).is_err(), "{:?} must be error", input);
+        }
+    }
+
     #[test]
     fn test_invalid_value() {
         let rs = "invalid json";
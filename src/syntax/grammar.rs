@@ -0,0 +1,136 @@
+//! public facade to parse each production of json grammar, such as only `string` or only `number`.
+//! whitespace around the production is allowed, but any other surplus content is error.
+//! for whole json value, [`crate::Value::parse`] is entry point.
+
+use super::{
+    error::{Error, StructureError},
+    lexer::Lexer,
+    parser::{Parser, ParserOptions},
+};
+use crate::ast::{
+    map::{Key, Map},
+    Value,
+};
+
+/// parse `s` as one production by `production` of parser with `options`.
+fn parse_production<F>(s: &str, options: ParserOptions, production: F) -> Result<Value, Error>
+where
+    F: FnOnce(&Parser, &mut Lexer) -> anyhow::Result<Value>,
+{
+    let (parser, mut lexer) = (Parser::with_options(options), Lexer::from_text(s));
+    let parsed = match lexer.skip_whitespace() {
+        Some(_) => production(&parser, &mut lexer),
+        None => parser.parse_value(&mut lexer), // report unexpected eof
+    };
+    let parsed = parsed.and_then(|value| match lexer.skip_whitespace() {
+        Some(&(p, _)) => Err(StructureError::FoundSurplus { start: p, end: lexer.eof() })?,
+        None => Ok(value),
+    });
+    parsed.map_err(|e| Error::located(e, |pos| lexer.byte_offset(pos)))
+}
+
+/// parse `value` of json, same as [`crate::Value::parse_with`] for `&str`.
+pub fn parse_value(s: &str, options: ParserOptions) -> Result<Value, Error> {
+    parse_production(s, options, |parser, lexer| parser.parse_value(lexer))
+}
+
+/// parse `object` of json such as `{"key": "value"}`.
+/// # examples
+/// ```
+/// use dyson::syntax;
+/// let object = syntax::parse_object(r#"{"key": [1, 2]}"#).unwrap();
+/// assert_eq!(object.len(), 1);
+/// assert!(syntax::parse_object("[1, 2]").is_err());
+/// ```
+pub fn parse_object(s: &str) -> Result<Map<Key, Value>, Error> {
+    let object = parse_production(s, ParserOptions::default(), |parser, lexer| parser.parse_object(lexer))?;
+    Ok(object.try_into().expect("object production should return object"))
+}
+
+/// parse `array` of json such as `[1, 2, 3]`.
+/// # examples
+/// ```
+/// use dyson::{syntax, Value};
+/// assert_eq!(syntax::parse_array("[1, 2]").unwrap(), vec![Value::Integer(1), Value::Integer(2)]);
+/// ```
+pub fn parse_array(s: &str) -> Result<Vec<Value>, Error> {
+    let array = parse_production(s, ParserOptions::default(), |parser, lexer| parser.parse_array(lexer))?;
+    Ok(array.try_into().expect("array production should return array"))
+}
+
+/// parse `string` literal of json, and return unescaped string.
+/// # examples
+/// ```
+/// use dyson::syntax;
+/// assert_eq!(syntax::parse_string(r#""tab\tandあ""#).unwrap(), "tab\tandあ");
+/// assert!(syntax::parse_string("no quotation").is_err());
+/// ```
+pub fn parse_string(s: &str) -> Result<String, Error> {
+    let string = parse_production(s, ParserOptions::default(), |parser, lexer| parser.parse_string(lexer))?;
+    Ok(string.try_into().expect("string production should return string"))
+}
+
+/// parse `number` of json. the result is [`Value::Integer`] or [`Value::Float`],
+/// or [`Value::RawNumber`] if `lossless_number` of `options` is enabled.
+/// # examples
+/// ```
+/// use dyson::{syntax, ParserOptions, Value};
+/// assert_eq!(syntax::parse_number("-12", ParserOptions::default()).unwrap(), Value::Integer(-12));
+/// assert_eq!(syntax::parse_number("1.5e1", ParserOptions::default()).unwrap(), Value::Float(15.0));
+/// assert!(syntax::parse_number("012", ParserOptions::default()).is_err());
+/// ```
+pub fn parse_number(s: &str, options: ParserOptions) -> Result<Value, Error> {
+    parse_production(s, options, |parser, lexer| parser.parse_number(lexer))
+}
+
+/// parse `bool` of json, that is `true` or `false`.
+/// # examples
+/// ```
+/// use dyson::syntax;
+/// assert_eq!(syntax::parse_bool(" false ").unwrap(), false);
+/// ```
+pub fn parse_bool(s: &str) -> Result<bool, Error> {
+    let tf = parse_production(s, ParserOptions::default(), |parser, lexer| parser.parse_bool(lexer))?;
+    Ok(tf.try_into().expect("bool production should return bool"))
+}
+
+/// parse `null` of json.
+/// # examples
+/// ```
+/// use dyson::syntax;
+/// assert!(syntax::parse_null("null").is_ok());
+/// assert!(syntax::parse_null("nil").is_err());
+/// ```
+pub fn parse_null(s: &str) -> Result<(), Error> {
+    parse_production(s, ParserOptions::default(), |parser, lexer| parser.parse_null(lexer)).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_productions() {
+        let object = parse_object(" {\"a\": {\"b\": null}} ").unwrap();
+        assert_eq!(object["a"], Value::parse(r#"{"b": null}"#).unwrap());
+        assert_eq!(parse_array("[]").unwrap(), Vec::new());
+        assert_eq!(parse_string("\"\"").unwrap(), "");
+        assert!(parse_bool("true").unwrap());
+        assert_eq!(parse_value("[true]", ParserOptions::default()).unwrap(), Value::Array(vec![Value::Bool(true)]));
+        let lossless = ParserOptions::new().lossless_number(true);
+        assert_eq!(parse_number("1.10", lossless).unwrap().to_string(), "1.10");
+    }
+
+    #[test]
+    fn test_parse_productions_error() {
+        let err = parse_string("\"a\" \"b\"").unwrap_err();
+        assert_eq!(err.span().map(|s| s.byte_range.clone()), Some(4..7));
+        let err = parse_number("1 2", ParserOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("surplus"), "{err}");
+        let err = parse_bool("  ").unwrap_err();
+        assert!(err.to_string().contains("EOF"), "{err}");
+        assert!(parse_array("{}").is_err());
+        assert!(parse_object("").is_err());
+        assert!(parse_null("null,").is_err());
+    }
+}
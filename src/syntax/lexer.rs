@@ -1,34 +1,178 @@
 use super::{
-    error::{ParseTokenError, SequentialTokenError, SingleTokenError},
+    encoding,
+    error::{ParseTokenError, Position, SequentialTokenError, SingleTokenError},
     rawjson::RawJson,
+    span::{self, position},
     token::{MainToken, SequentialToken, SingleToken},
 };
+use std::{cell::RefCell, collections::VecDeque, io, rc::Rc};
 
+#[derive(Clone)]
 pub struct Lexer<'a> {
-    pub(crate) json: &'a RawJson,
+    source: Source<'a>,
     curr: Option<((usize, usize), char)>,
 }
+/// source of [`Lexer`]. `Text` is read without line splitting, `offset` is byte offset of next char of `curr`.
+/// `Stream` is read from caller-provided iterator, `index` is index of next char of `curr` in the stream.
+#[derive(Clone)]
+enum Source<'a> {
+    Rows(&'a RawJson),
+    Text { text: &'a str, offset: usize, eof: Position },
+    Stream { stream: Rc<RefCell<Stream<'a>>>, index: usize },
+}
+
+/// chars read from caller-provided iterator. read chars are kept only while a clone of [`Lexer`] may read them again,
+/// so memory usage is bounded by rows and non-ASCII chars for error span, not by input size.
+struct Stream<'a> {
+    chars: Box<dyn 'a + Iterator<Item = io::Result<char>>>,
+    buffer: VecDeque<char>,
+    /// index of first char of `buffer` in the stream.
+    base: usize,
+    /// position and byte offset of next char of the stream.
+    pos: Position,
+    offset: usize,
+    /// position and byte offset at each row start and after each non-ASCII char, chars between them are ASCII.
+    anchors: Vec<(Position, usize)>,
+    error: Option<io::Error>,
+}
+impl<'a> Stream<'a> {
+    /// get `index`-th char of the stream. `index` must not be less than `base`.
+    fn get(&mut self, index: usize) -> Option<char> {
+        while self.base + self.buffer.len() <= index && self.error.is_none() {
+            let c = match self.chars.next()? {
+                Ok(c) => c,
+                Err(e) => {
+                    self.error = Some(e);
+                    return None;
+                }
+            };
+            self.buffer.push_back(c);
+            self.offset += c.len_utf8();
+            self.pos = if c == '\n' { (self.pos.0 + 1, 0) } else { (self.pos.0, self.pos.1 + 1) };
+            if c == '\n' || !c.is_ascii() {
+                self.anchors.push((self.pos, self.offset));
+            }
+        }
+        self.buffer.get(index - self.base).copied()
+    }
+
+    /// forget chars before `index`, they will never be read.
+    fn forget(&mut self, index: usize) {
+        let forgotten = index.saturating_sub(self.base).min(self.buffer.len());
+        self.buffer.drain(..forgotten);
+        self.base += forgotten;
+    }
+
+    /// convert position into byte offset of read chars.
+    fn byte_offset(&self, pos: Position) -> usize {
+        let anchor = self.anchors.partition_point(|&(p, _)| p <= pos).saturating_sub(1);
+        match self.anchors.get(anchor) {
+            Some(&((row, col), offset)) if row == pos.0 && pos <= self.pos => offset + (pos.1 - col),
+            _ => self.offset,
+        }
+    }
+}
 impl<'a> Iterator for Lexer<'a> {
     type Item = ((usize, usize), char);
     fn next(&mut self) -> Option<Self::Item> {
         let ((row, col), curr) = self.curr?;
-        if col + 1 < self.json[row].len() {
-            self.curr = Some(((row, col + 1), self.json[row][col + 1]));
-        } else if row + 1 < self.json.rows() {
-            self.curr = Some(((row + 1, 0), self.json[row + 1][0]));
-        } else {
-            self.curr = None;
-        }
+        self.curr = match &mut self.source {
+            Source::Rows(json) if col + 1 < json[row].len() => Some(((row, col + 1), json[row][col + 1])),
+            Source::Rows(json) if row + 1 < json.rows() => Some(((row + 1, 0), json[row + 1][0])),
+            Source::Rows(_) => None,
+            Source::Text { text, offset, .. } => text[*offset..].chars().next().map(|c| {
+                *offset += c.len_utf8();
+                (if curr == '\n' { (row + 1, 0) } else { (row, col + 1) }, c)
+            }),
+            Source::Stream { stream, index } => {
+                let mut s = stream.borrow_mut();
+                if Rc::strong_count(stream) == 1 {
+                    // no clone of this lexer, so read chars are never read again
+                    s.forget(*index);
+                }
+                s.get(*index).map(|c| {
+                    *index += 1;
+                    (if curr == '\n' { (row + 1, 0) } else { (row, col + 1) }, c)
+                })
+            }
+        };
         Some(((row, col), curr))
     }
 }
 
 impl<'a> Lexer<'a> {
-    /// read next token without skip whitespace. this method's complexity is **O(1)**.
-    /// if next token is eof, return None.
+    /// get lexer of raw json. first call of `peek` or `next` return its first char.
     pub fn new(json: &'a RawJson) -> Self {
         let curr = json.get(0, 0).map(|&c| ((0, 0), c));
-        Self { json, curr }
+        Self { source: Source::Rows(json), curr }
+    }
+
+    /// get lexer of `text`, without splitting it into lines. each `'\n'` start next row, and `'\r'` is kept.
+    pub fn from_text(text: &'a str) -> Self {
+        let curr = text.chars().next().map(|c| ((0, 0), c));
+        let offset = curr.map_or(0, |(_, c)| c.len_utf8());
+        Self { source: Source::Text { text, offset, eof: position(text, text.len()) }, curr }
+    }
+
+    /// get lexer of chars of caller-provided iterator, without collecting them. see [`Lexer::from_text`] also.
+    pub fn from_chars<I: 'a + Iterator<Item = char>>(chars: I) -> Self {
+        Self::from_stream(Box::new(chars.map(Ok)))
+    }
+
+    /// get lexer of bytes of caller-provided iterator such as [`std::io::Read::bytes`]. bytes are decoded as UTF-8,
+    /// and io error or invalid UTF-8 stop the lexer. the error can be gotten by [`Lexer::take_error`].
+    pub fn from_bytes<I: 'a + Iterator<Item = io::Result<u8>>>(mut bytes: I) -> Self {
+        Self::from_stream(Box::new(std::iter::from_fn(move || encoding::read_utf8(&mut bytes).transpose())))
+    }
+
+    /// get lexer of bytes of caller-provided iterator, whose encoding is detected by BOM or by null bytes of its first
+    /// 4 bytes. UTF-16 and UTF-32 are decoded lazily like UTF-8 of [`Lexer::from_bytes`], and BOM is skipped.
+    /// # errors
+    /// if reading first bytes fails, the io error is returned.
+    pub fn from_encoded_bytes<I: 'a + Iterator<Item = io::Result<u8>>>(bytes: I) -> io::Result<Self> {
+        Ok(Self::from_stream(Box::new(encoding::decode(bytes)?)))
+    }
+
+    fn from_stream(chars: Box<dyn 'a + Iterator<Item = io::Result<char>>>) -> Self {
+        let (buffer, anchors) = (VecDeque::new(), vec![((0, 0), 0)]);
+        let mut stream = Stream { chars, buffer, base: 0, pos: (0, 0), offset: 0, anchors, error: None };
+        let curr = stream.get(0).map(|c| ((0, 0), c));
+        Self { source: Source::Stream { stream: Rc::new(RefCell::new(stream)), index: 1 }, curr }
+    }
+
+    /// return eof position. this method's complexity is **O(1)**, but if the lexer read stream, rest of the stream
+    /// is read to get eof position.
+    pub fn eof(&self) -> Position {
+        match &self.source {
+            Source::Rows(json) => json.eof(),
+            &Source::Text { eof, .. } => eof,
+            Source::Stream { stream, index } => {
+                let mut s = stream.borrow_mut();
+                let mut rest = *index;
+                while s.get(rest).is_some() {
+                    rest += 1;
+                }
+                s.pos
+            }
+        }
+    }
+
+    /// convert position into byte offset of input. if the lexer read stream, it is byte offset of UTF-8 encoding of
+    /// read chars, and position after read chars is regarded as current offset.
+    pub fn byte_offset(&self, pos: Position) -> usize {
+        match &self.source {
+            Source::Rows(json) => json.byte_offset(pos),
+            Source::Text { text, .. } => span::offset(text, pos),
+            Source::Stream { stream, .. } => stream.borrow().byte_offset(pos),
+        }
+    }
+
+    /// take io error that stop reading stream.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        match &self.source {
+            Source::Stream { stream, .. } => stream.borrow_mut().error.take(),
+            _ => None,
+        }
     }
 
     /// peek next token without skip whitespace. this method's complexity is **O(1)**.
@@ -61,10 +205,11 @@ impl<'a> Lexer<'a> {
             if T::tokenize(c) != token {
                 Err(SingleTokenError::UnexpectedToken { expected: vec![token], found: T::tokenize(c), pos })?
             } else {
-                self.next().ok_or_else(|| unreachable!("previous peek ensure this next success"))
+                self.next();
+                Ok((pos, c))
             }
         } else {
-            Err(SingleTokenError::UnexpectedEof { expected: vec![token], pos: self.json.eof() })?
+            Err(SingleTokenError::UnexpectedEof { expected: vec![token], pos: self.eof() })?
         }
     }
 
@@ -76,21 +221,22 @@ impl<'a> Lexer<'a> {
         }
         let &(start, _) = self.peek().ok_or_else(|| ParseTokenError::UnexpectedEof {
             found: "".into(),
-            start: self.json.eof(),
-            end: self.json.eof(),
+            start: self.eof(),
+            end: self.eof(),
         })?;
-        let mut result = String::new();
+        let (mut result, mut count) = (String::new(), 0);
         for (p, c) in self.take(n) {
             if MainToken::tokenize(c) == MainToken::Whitespace {
                 return Err(ParseTokenError::UnexpectedWhiteSpace { found: result, start, end: p })?;
             } else {
-                result.push(c)
+                result.push(c);
+                count += 1;
             }
         }
-        if result.len() == n {
+        if count == n {
             Ok((result, self.peek().cloned()))
         } else {
-            Err(ParseTokenError::UnexpectedEof { found: result, start, end: self.json.eof() })?
+            Err(ParseTokenError::UnexpectedEof { found: result, start, end: self.eof() })?
         }
     }
 
@@ -105,11 +251,11 @@ impl<'a> Lexer<'a> {
             if T::confirm(&ts) == token {
                 Ok(nexted)
             } else {
-                let end = nexted.map(|(p, _)| p).unwrap_or_else(|| self.json.eof());
+                let end = nexted.map(|(p, _)| p).unwrap_or_else(|| self.eof());
                 Err(SequentialTokenError::UnexpectedToken { expected: vec![token], found: ts, start, end })?
             }
         } else {
-            let eof = self.json.eof();
+            let eof = self.eof();
             Err(SequentialTokenError::UnexpectedEof { expected: vec![token], start: eof, end: eof })?
         }
     }
@@ -172,6 +318,52 @@ mod tests {
     }
 
     #[test]
+    fn test_text_read() {
+        let mut lexer = Lexer::from_text("{\r\n\"あ\"}");
+        let read: Vec<_> = lexer.by_ref().collect();
+        assert_eq!(
+            read,
+            vec![
+                ((0, 0), '{'),
+                ((0, 1), '\r'),
+                ((0, 2), '\n'),
+                ((1, 0), '"'),
+                ((1, 1), 'あ'),
+                ((1, 2), '"'),
+                ((1, 3), '}')
+            ]
+        );
+        assert_eq!((lexer.next(), lexer.eof()), (None, (1, 4)));
+        assert_eq!(Lexer::from_text("").peek(), None);
+    }
+
+    #[test]
+    fn test_stream_read() {
+        let text = "{\r\n\"あ\"}";
+        let read: Vec<_> = Lexer::from_chars(text.chars()).collect();
+        assert_eq!(read, Lexer::from_text(text).collect::<Vec<_>>());
+        let read: Vec<_> = Lexer::from_bytes(text.bytes().map(Ok)).collect();
+        assert_eq!(read, Lexer::from_text(text).collect::<Vec<_>>());
+
+        // clone of lexer read the same chars again
+        let mut lexer = Lexer::from_chars("[true, false]".chars());
+        lexer.next();
+        let checkpoint = lexer.clone();
+        assert_eq!(lexer.lex_n_chars(4).unwrap().0, "true");
+        lexer = checkpoint;
+        assert_eq!(lexer.lex_n_chars(5).unwrap().0, "true,");
+        assert_eq!(lexer.next(), Some(((0, 6), ' ')));
+        assert_eq!(lexer.eof(), (0, 13));
+
+        let lexer = Lexer::from_chars("\"あ\",\n\"い\"".chars());
+        let read: Vec<_> = lexer.clone().collect();
+        for (i, &(pos, _)) in read.iter().enumerate() {
+            assert_eq!(lexer.byte_offset(pos), read[..i].iter().map(|(_, c)| c.len_utf8()).sum::<usize>());
+        }
+    }
+
+    #[test]
+    #[allow(clippy::useless_vec)]
     fn test_skip_whitespace() {
         let json = vec!["{", "    \"a\": 1", "}"].into_iter().collect();
         let expected = vec!['{', '"', 'a', '"', ':', '1', '}'];
@@ -226,5 +418,11 @@ mod tests {
         assert_eq!(lexer.skip_whitespace(), Some(&((0, 8), 'f')));
         let lex_5_chars = lexer.lex_n_chars(5).unwrap_err();
         assert!(lex_5_chars.to_string().contains("fal"));
+
+        let json = "ùñí©ø∂é".into();
+        let mut lexer = Lexer::new(&json);
+        let (lex_4_chars, nexted) = lexer.lex_n_chars(4).unwrap();
+        assert_eq!(lex_4_chars, "ùñí©");
+        assert_eq!(nexted, Some(((0, 4), 'ø')));
     }
 }
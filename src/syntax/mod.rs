@@ -1,5 +1,12 @@
+pub mod cst;
+pub(crate) mod encoding;
 pub(crate) mod error;
+pub(crate) mod grammar;
 pub(crate) mod lexer;
 pub(crate) mod parser;
 pub mod rawjson;
+pub mod span;
 pub(crate) mod token;
+pub(crate) mod validate;
+
+pub use grammar::{parse_array, parse_bool, parse_null, parse_number, parse_object, parse_string, parse_value};
@@ -1,29 +1,413 @@
 use super::{
     error::{
-        ParseNumberError, ParseStringError, ParseValueError, Position, SequentialTokenError, SingleTokenError,
-        StructureError,
+        postr, ParseError, ParseNumberError, ParseStringError, ParseValueError, Position, SequentialTokenError,
+        SingleTokenError, StructureError,
     },
     lexer::{Lexer, SkipWs},
     token::{ImmediateToken, MainToken, NumberToken, SingleToken, StringToken},
 };
-use crate::ast::Value;
+use crate::ast::{
+    index::JsonIndexer,
+    index_path::JsonPath,
+    map::{Interner, Map},
+    number::raw_number,
+    Value,
+};
 use anyhow::Context as _;
-use linked_hash_map::LinkedHashMap;
+use std::cell::{Cell, RefCell};
+
+/// [`ParserOptions`] configure behavior of parser. see [`Value::parse_with`] also.
+/// # examples
+/// ```
+/// use dyson::{ParserOptions, Value};
+/// let raw_json = "[\"tab\tseparated\"]";
+/// assert!(Value::parse(raw_json).is_ok());
+/// assert!(Value::parse_with(raw_json, ParserOptions::new().strict(true)).is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    strict: bool,
+    max_depth: Option<usize>,
+    max_nodes: Option<usize>,
+    max_string_length: Option<usize>,
+    max_entries: Option<usize>,
+    max_number_digits: Option<usize>,
+    lossless_number: bool,
+    projection: Option<Vec<JsonPath>>,
+    numeric_keys: bool,
+    leading_zeros: bool,
+    negative_zero: bool,
+    relaxed_numbers: bool,
+    tolerant: bool,
+}
+impl ParserOptions {
+    /// get default options, it is same as [`Value::parse`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// if `strict`, reject unescaped control characters (U+0000 - U+001F) in string, like RFC 8259.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// abort parsing if nesting of object and array exceeds `max_depth`. by default, depth is not limited.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let raw_json = "[[[]]]";
+    /// assert!(Value::parse_with(raw_json, ParserOptions::new().max_depth(3)).is_ok());
+    /// assert!(Value::parse_with(raw_json, ParserOptions::new().max_depth(2)).is_err());
+    /// ```
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// abort parsing if total number of values in the document exceeds `max_nodes`. by default, it is not limited.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let raw_json = r#"{"key": [1, 2, 3]}"#;
+    /// assert!(Value::parse_with(raw_json, ParserOptions::new().max_nodes(5)).is_ok());
+    /// assert!(Value::parse_with(raw_json, ParserOptions::new().max_nodes(4)).is_err());
+    /// ```
+    pub fn max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = Some(max_nodes);
+        self
+    }
+
+    /// abort parsing if byte length of a string (include object key) exceeds `max_string_length`.
+    /// by default, it is not limited.
+    pub fn max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = Some(max_string_length);
+        self
+    }
+
+    /// abort parsing if number of entries of an object or an array exceeds `max_entries`.
+    /// by default, it is not limited.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// reject number that has more than `max_number_digits` digits in total of its integer, fraction, and exponent
+    /// part. pathological number like a million digits is rejected while reading it. by default, it is not limited.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let options = ParserOptions::new().max_number_digits(8);
+    /// assert_eq!(Value::parse_with("-1234.5e-67", options.clone()).unwrap(), Value::Float(-1234.5e-67));
+    /// let err = Value::parse_with("123456789", options).unwrap_err();
+    /// assert!(err.to_string().contains("more than 8 digits"));
+    /// ```
+    pub fn max_number_digits(mut self, max_number_digits: usize) -> Self {
+        self.max_number_digits = Some(max_number_digits);
+        self
+    }
+
+    /// if `lossless_number`, parse number into [`Value::RawNumber`] that keep its source text instead of
+    /// [`Value::Integer`] or [`Value::Float`], so it can be written back exactly. see [`crate::ast::number::RawNumber`].
+    pub fn lossless_number(mut self, lossless_number: bool) -> Self {
+        self.lossless_number = lossless_number;
+        self
+    }
+
+    /// materialize only values on or under `paths`, other values are skipped without allocation.
+    /// skipped object entries are omitted, and skipped array elements are replaced with [`Value::Null`] to keep index.
+    /// see [`Value::parse_projection`] also.
+    pub fn projection(mut self, paths: &[JsonPath]) -> Self {
+        self.projection = Some(paths.to_vec());
+        self
+    }
+
+    /// if `numeric_keys`, accept unquoted number as object key (like `{1: "one"}`) and convert it into string key.
+    /// each conversion is reported as [`ParseWarning::NumericKey`], see [`Value::parse_report`].
+    pub fn numeric_keys(mut self, numeric_keys: bool) -> Self {
+        self.numeric_keys = numeric_keys;
+        self
+    }
+
+    /// if `leading_zeros`, accept number with leading zeros like `012` (it is parsed as `12`, also with
+    /// `lossless_number`).
+    /// by default, it is rejected like RFC 8259.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// assert!(Value::parse("012").is_err());
+    /// assert_eq!(Value::parse_with("012", ParserOptions::new().leading_zeros(true)).unwrap(), Value::Integer(12));
+    /// ```
+    pub fn leading_zeros(mut self, leading_zeros: bool) -> Self {
+        self.leading_zeros = leading_zeros;
+        self
+    }
+
+    /// if `negative_zero`, integer `-0` is parsed into `Value::Float(-0.0)` to keep its sign.
+    /// by default, it is parsed into `Value::Integer(0)`.
+    pub fn negative_zero(mut self, negative_zero: bool) -> Self {
+        self.negative_zero = negative_zero;
+        self
+    }
+
+    /// if `relaxed_numbers`, accept number with leading plus sign like `+123` or `+1.5e3`.
+    /// the sign is dropped, so it is parsed as same as the number without it.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// assert!(Value::parse("+123").is_err());
+    /// let relaxed = ParserOptions::new().relaxed_numbers(true);
+    /// assert_eq!(Value::parse_with("[+123, +1.5e3]", relaxed).unwrap().to_string(), "[123,1500]");
+    /// ```
+    pub fn relaxed_numbers(mut self, relaxed_numbers: bool) -> Self {
+        self.relaxed_numbers = relaxed_numbers;
+        self
+    }
+
+    /// if `tolerant`, value of object or array that cannot be parsed is replaced with [`Value::Null`] instead of
+    /// failing whole document, and it is reported as [`ParseWarning::Replaced`]. see [`Value::parse_report`].
+    /// broken structure of object or array itself (such as missing closing bracket) make its parent replaced.
+    /// # examples
+    /// ```
+    /// use dyson::{ParserOptions, Value};
+    /// let raw_json = r#"[{"id": 1}, {"id": tru}, {"id": 3}]"#;
+    /// let report = Value::parse_report(raw_json, ParserOptions::new().tolerant(true)).unwrap();
+    /// assert_eq!(report.value.to_string(), r#"[{"id":1},{"id":null},{"id":3}]"#);
+    /// assert_eq!(report.warnings.len(), 1);
+    /// ```
+    pub fn tolerant(mut self, tolerant: bool) -> Self {
+        self.tolerant = tolerant;
+        self
+    }
+}
+
+/// [`ParseWarning`] is a non-fatal problem found while lenient parsing. see [`ParseReport`] also.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// unquoted number `key` is used as object key, and it is converted into string.
+    NumericKey { key: String, pos: Position },
+    /// value that start at `pos` cannot be parsed because of `error`, and it is replaced with null.
+    Replaced { error: String, pos: Position },
+}
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseWarning::NumericKey { key, pos } => {
+                write!(f, "{}: unquoted numeric key {} is converted into string", postr(pos), key)
+            }
+            ParseWarning::Replaced { error, pos } => write!(f, "{}: replaced with null, {}", postr(pos), error),
+        }
+    }
+}
+
+/// [`ParseReport`] is result of parsing with warnings. see [`Value::parse_report`] also.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseReport {
+    pub value: Value,
+    pub warnings: Vec<ParseWarning>,
+}
+
+type Reviver<'r> = Box<dyn FnMut(&JsonPath, Value) -> Value + 'r>;
 
-pub struct Parser {}
+/// [`DepthGuard`] leave nested object or array when it is dropped. see [`Parser::enter`].
+struct DepthGuard<'a>(&'a Cell<usize>);
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get().saturating_sub(1));
+    }
+}
+
+pub struct Parser<'r> {
+    options: ParserOptions,
+    depth: Cell<usize>,
+    nodes: Cell<usize>,
+    path: RefCell<JsonPath>,
+    warnings: RefCell<Vec<ParseWarning>>,
+    keys: RefCell<Interner>,
+    reviver: Option<RefCell<Reviver<'r>>>,
+}
+impl Default for Parser<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-impl Parser {
+impl<'r> Parser<'r> {
     /// get new parser to parse raw json
     pub fn new() -> Self {
         // TODO trailing comma, allow comment
-        Self {}
+        Self::with_options(ParserOptions::default())
+    }
+
+    /// get new parser to parse raw json with given options
+    pub fn with_options(options: ParserOptions) -> Self {
+        Self {
+            options,
+            depth: Cell::new(0),
+            nodes: Cell::new(0),
+            path: RefCell::new(JsonPath::new()),
+            warnings: RefCell::new(Vec::new()),
+            keys: RefCell::new(Interner::default()),
+            reviver: None,
+        }
+    }
+
+    /// set `reviver` that is called with path and value of each completed node, children first.
+    /// returned value is used instead of parsed value. see [`Value::parse_with_reviver`] also.
+    pub fn reviver<F: FnMut(&JsonPath, Value) -> Value + 'r>(mut self, reviver: F) -> Self {
+        self.reviver = Some(RefCell::new(Box::new(reviver)));
+        self
+    }
+
+    /// enter nested object or array that start at `pos`. it is left when returned guard is dropped, so depth is
+    /// restored even if parsing the nested value fails.
+    fn enter(&self, pos: Position) -> anyhow::Result<DepthGuard<'_>> {
+        let depth = self.depth.get() + 1;
+        match self.options.max_depth {
+            Some(limit) if depth > limit => Err(ParseError::DepthLimitExceeded { limit, pos })?,
+            _ => {
+                self.depth.set(depth);
+                Ok(DepthGuard(&self.depth))
+            }
+        }
+    }
+
+    /// count a value that start at `pos`.
+    fn count_node(&self, pos: Position) -> anyhow::Result<()> {
+        let nodes = self.nodes.get() + 1;
+        match self.options.max_nodes {
+            Some(limit) if nodes > limit => Err(ParseError::NodeLimitExceeded { limit, pos })?,
+            _ => {
+                self.nodes.set(nodes);
+                Ok(())
+            }
+        }
+    }
+
+    /// check number of entries of an object or an array that start at `pos`.
+    fn check_entries(&self, entries: usize, pos: Position) -> anyhow::Result<()> {
+        match self.options.max_entries {
+            Some(limit) if entries > limit => Err(ParseError::EntriesLimitExceeded { limit, pos })?,
+            _ => Ok(()),
+        }
+    }
+
+    /// take warnings reported while parsing.
+    pub fn take_warnings(&self) -> Vec<ParseWarning> {
+        self.warnings.take()
+    }
+
+    /// check value at current path should be materialized. it is true if the path is an ancestor or
+    /// a descendant of (or same as) some projected path.
+    fn is_projected(&self) -> bool {
+        let path = self.path.borrow();
+        self.options
+            .projection
+            .as_ref()
+            .map_or(true, |paths| paths.iter().any(|p| p.starts_with(&path) || path.starts_with(p)))
+    }
+
+    /// parse `value` of json under `indexer`, or skip it if it is not projected.
+    fn parse_child(&self, lexer: &mut Lexer, indexer: JsonIndexer) -> anyhow::Result<Option<Value>> {
+        self.path.borrow_mut().push(indexer);
+        let result = if !self.is_projected() {
+            self.skip_value(lexer).map(|()| None)
+        } else if self.options.tolerant {
+            self.parse_or_replace(lexer).map(Some)
+        } else {
+            self.parse_value(lexer).map(Some)
+        };
+        self.path.borrow_mut().pop();
+        result
+    }
+
+    /// parse `value` of json, or skip it and get null if it cannot be parsed. limits error is not recovered.
+    fn parse_or_replace(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
+        let checkpoint = lexer.clone();
+        self.parse_value(lexer).or_else(|e| {
+            if e.downcast_ref::<ParseError>().is_some() {
+                return Err(e);
+            }
+            *lexer = checkpoint;
+            let eof = lexer.eof();
+            let pos = lexer.skip_whitespace().map_or(eof, |&(p, _)| p);
+            // skip until `,` or closing bracket of parent, unclosed bracket is regarded as closed by mismatched one
+            let (mut nest, mut in_string, mut escaped) = (Vec::new(), false, false);
+            while let Some(&(_, c)) = lexer.peek() {
+                if in_string {
+                    in_string = escaped || (c != '"' && c != '\n');
+                    escaped = !escaped && c == '\\';
+                } else {
+                    match MainToken::tokenize(c) {
+                        MainToken::Quotation => in_string = true,
+                        MainToken::LeftBrace => nest.push(MainToken::RightBrace),
+                        MainToken::LeftBracket => nest.push(MainToken::RightBracket),
+                        close @ (MainToken::RightBrace | MainToken::RightBracket) => match nest.last() {
+                            Some(expected) if expected == &close => drop(nest.pop()),
+                            _ => break,
+                        },
+                        MainToken::Comma if nest.is_empty() => break,
+                        _ => (),
+                    }
+                }
+                lexer.next();
+            }
+            self.warnings.borrow_mut().push(ParseWarning::Replaced { error: e.to_string(), pos });
+            Ok(Value::Null)
+        })
+    }
+
+    /// skip `value` of json without materializing. object, array and string are skipped only by matching
+    /// brackets and quotations, so syntax errors inside them may not be detected.
+    fn skip_value(&self, lexer: &mut Lexer) -> anyhow::Result<()> {
+        let &(start, c) = match lexer.skip_whitespace() {
+            Some(next) => next,
+            None => return self.parse_value(lexer).map(drop),
+        };
+        if !matches!(MainToken::tokenize(c), MainToken::LeftBrace | MainToken::LeftBracket | MainToken::Quotation) {
+            return self.parse_value(lexer).map(drop);
+        }
+        let (mut nest, mut in_string, mut escaped) = (0usize, false, false);
+        for (_, c) in lexer.by_ref() {
+            if in_string {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    in_string = false;
+                }
+            } else {
+                match MainToken::tokenize(c) {
+                    MainToken::Quotation => in_string = true,
+                    MainToken::LeftBrace | MainToken::LeftBracket => nest += 1,
+                    MainToken::RightBrace | MainToken::RightBracket => nest -= 1,
+                    _ => (),
+                }
+            }
+            if nest == 0 && !in_string {
+                return Ok(());
+            }
+        }
+        let eof = lexer.eof();
+        Err(StructureError::UnexpectedEof { start, end: eof })?
     }
 
     /// parse `value` of json. the following ebnf is not precise.<br>
     /// `value` := `object` | `array` | `bool` | `null` | `string` | `number`;
     pub fn parse_value(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
+        let value = self.parse_value_node(lexer)?;
+        match &self.reviver {
+            Some(reviver) => Ok((reviver.borrow_mut())(&self.path.borrow(), value)),
+            None => Ok(value),
+        }
+    }
+
+    /// parse `value` of json without calling reviver.
+    fn parse_value_node(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
         let examples = || vec![MainToken::LeftBrace, MainToken::Undecided('t'), MainToken::Digit('0')];
         if let Some(&(pos, c)) = lexer.skip_whitespace() {
+            self.count_node(pos)?;
             let tokenized = MainToken::tokenize(c);
             if matches!(tokenized, MainToken::LeftBrace) {
                 self.parse_object(lexer)
@@ -35,13 +419,15 @@ impl Parser {
                 self.parse_null(lexer)
             } else if matches!(tokenized, MainToken::Quotation) {
                 self.parse_string(lexer)
-            } else if matches!(tokenized, MainToken::Minus | MainToken::Digit(_)) {
+            } else if matches!(tokenized, MainToken::Minus | MainToken::Digit(_))
+                || self.options.relaxed_numbers && matches!(tokenized, MainToken::Plus)
+            {
                 self.parse_number(lexer)
             } else {
                 Err(ParseValueError::CannotStartParseValue { examples: examples(), found: tokenized, pos })?
             }
         } else {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             Err(ParseValueError::UnexpectedEof { examples: examples(), pos: eof })?
         }
     }
@@ -49,14 +435,25 @@ impl Parser {
     /// parse `object` of json. the following ebnf is not precise.<br>
     /// `object` := "{" { `string` ":" `value` \[ "," \] }  "}"
     pub fn parse_object(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
-        let mut object = LinkedHashMap::new();
-        let (_, _left_brace) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::LeftBrace)?;
+        let mut object = Map::new();
+        let (pos, _left_brace) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::LeftBrace)?;
+        let _depth = self.enter(pos)?;
         while !lexer.is_next::<_, SkipWs<true>>(MainToken::RightBrace) {
-            if lexer.is_next::<_, SkipWs<true>>(MainToken::Quotation) {
-                let key = self.parse_string(lexer)?;
+            let numeric_key = self.options.numeric_keys
+                && matches!(lexer.skip_whitespace(), Some(&(_, c)) if matches!(MainToken::tokenize(c), MainToken::Minus | MainToken::Digit(_)));
+            if numeric_key || lexer.is_next::<_, SkipWs<true>>(MainToken::Quotation) {
+                let key: String = if numeric_key {
+                    let (pos, key) = self.lex_number(lexer)?;
+                    self.warnings.borrow_mut().push(ParseWarning::NumericKey { key: key.clone(), pos });
+                    key
+                } else {
+                    self.parse_string(lexer)?.try_into()?
+                };
                 lexer.lex_1_char::<_, SkipWs<true>>(MainToken::Colon)?;
-                let value = self.parse_value(lexer)?;
-                object.insert(key.into(), value);
+                if let Some(value) = self.parse_child(lexer, JsonIndexer::ObjInd(key.clone()))? {
+                    object.insert(self.keys.borrow_mut().intern(key), value);
+                    self.check_entries(object.len(), pos)?;
+                }
 
                 if let Ok((p, _comma)) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::Comma) {
                     if lexer.is_next::<_, SkipWs<true>>(MainToken::RightBrace) {
@@ -68,17 +465,19 @@ impl Parser {
             }
         }
         lexer.lex_1_char::<_, SkipWs<true>>(MainToken::RightBrace)?;
-        Ok(Value::Object(object))
+        Ok(Value::Object(Box::new(object)))
     }
 
     /// parse `array` of json. the following ebnf is not precise.<br>
     /// `array` := "\[" { `value` \[ "," \] }  "\]"
     pub fn parse_array(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
         let mut array = Vec::new();
-        let (_, _left_bracket) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::LeftBracket)?;
+        let (pos, _left_bracket) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::LeftBracket)?;
+        let _depth = self.enter(pos)?;
         while !lexer.is_next::<_, SkipWs<true>>(MainToken::RightBracket) {
-            let value = self.parse_value(lexer)?;
-            array.push(value);
+            let value = self.parse_child(lexer, JsonIndexer::ArrInd(array.len()))?;
+            array.push(value.unwrap_or(Value::Null));
+            self.check_entries(array.len(), pos)?;
 
             if let Ok((p, _comma)) = lexer.lex_1_char::<_, SkipWs<true>>(MainToken::Comma) {
                 if lexer.is_next::<_, SkipWs<true>>(MainToken::RightBracket) {
@@ -97,7 +496,7 @@ impl Parser {
     pub fn parse_bool(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
         let expected = || vec![ImmediateToken::True, ImmediateToken::False];
         let &(pos, tf) = lexer.peek().ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             SequentialTokenError::UnexpectedEof { expected: expected(), start: eof, end: eof }
         })?;
         match ImmediateToken::tokenize(tf) {
@@ -118,7 +517,7 @@ impl Parser {
     pub fn parse_null(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
         let expected = || vec![ImmediateToken::Null];
         let &(pos, n) = lexer.peek().ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             SequentialTokenError::UnexpectedEof { expected: expected(), start: eof, end: eof }
         })?;
         match ImmediateToken::tokenize(n) {
@@ -137,17 +536,23 @@ impl Parser {
         let (start, _quotation) = lexer.lex_1_char::<_, SkipWs<false>>(StringToken::Quotation)?;
         while !lexer.is_next::<_, SkipWs<false>>(StringToken::Quotation) {
             let &(p, c) = lexer.peek().ok_or_else(|| {
-                let eof = lexer.json.eof();
+                let eof = lexer.eof();
                 ParseStringError::UnexpectedEof { comp: string.clone(), start, end: eof }
             })?;
             if c == '\n' {
                 return Err(ParseStringError::UnexpectedLinefeed { comp: string, start, end: p })?;
+            } else if self.options.strict && ('\u{0000}'..='\u{001F}').contains(&c) {
+                return Err(ParseStringError::UnescapedControl { control: c, pos: p })?;
             } else if lexer.is_next::<_, SkipWs<false>>(StringToken::ReverseSolidus) {
                 string.push(self.parse_escape_sequence(lexer)?);
             } else {
                 string.push(c);
                 lexer.next();
             }
+            match self.options.max_string_length {
+                Some(limit) if string.len() > limit => Err(ParseError::StringLengthExceeded { limit, pos: start })?,
+                _ => (),
+            }
         }
         lexer.lex_1_char::<_, SkipWs<false>>(StringToken::Quotation)?;
         Ok(Value::String(string))
@@ -158,7 +563,7 @@ impl Parser {
     pub fn parse_escape_sequence(&self, lexer: &mut Lexer) -> anyhow::Result<char> {
         let (start, reverse_solidus) = lexer.lex_1_char::<_, SkipWs<false>>(StringToken::ReverseSolidus)?;
         let (p, escaped) = lexer.next().ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             ParseStringError::UnexpectedEof { comp: reverse_solidus.to_string(), start, end: eof }
         })?;
         let tokenized = StringToken::tokenize(escaped);
@@ -182,62 +587,94 @@ impl Parser {
     pub fn parse_unicode(&self, lexer: &mut Lexer, start: Position) -> anyhow::Result<char> {
         let (hex4, nexted) = lexer.lex_n_chars(4)?;
         let (p, _) = nexted.ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             ParseStringError::UnexpectedEof { comp: hex4.clone(), start, end: eof }
         })?;
-        let uc = char::from_u32(u32::from_str_radix(&hex4, 16)?);
+        let hex = hex4.chars().all(|c| c.is_ascii_hexdigit()).then(|| u32::from_str_radix(&hex4, 16).ok()).flatten();
+        let uc = hex.and_then(char::from_u32);
         Ok(uc.ok_or(ParseStringError::CannotConvertUnicode { uc: hex4, start, end: p })?)
     }
 
     /// parse `number` of json. the following ebnf is not precise.<br>
     /// `number` := \[ "-" \] `digits` \[ \[ `fraction_part` \] \[`exponent_part` \] \]
     pub fn parse_number(&self, lexer: &mut Lexer) -> anyhow::Result<Value> {
+        let (start, number) = self.lex_number(lexer)?;
+        if self.options.lossless_number {
+            // number with leading zeros is not valid json, so keep it as `12` instead of `012`
+            let (sign, digits) = number.split_at(number.starts_with('-') as usize);
+            let zeros = digits.len() - digits.trim_start_matches('0').len();
+            let zeros = match digits[zeros..].chars().next() {
+                Some(c) if c.is_ascii_digit() => zeros,
+                _ => zeros.saturating_sub(1),
+            };
+            Ok(Value::RawNumber(raw_number(format!("{}{}", sign, &digits[zeros..]))))
+        } else if number.contains(['.', 'e', 'E']) || self.options.negative_zero && number.trim_matches('0') == "-" {
+            let end = || lexer.peek().map_or_else(|| lexer.eof(), |&(p, _)| p);
+            Ok(Value::Float(number.parse().with_context(|| ParseNumberError::CannotConvertF64 {
+                num: number,
+                start,
+                end: end(),
+            })?))
+        } else {
+            let end = || lexer.peek().map_or_else(|| lexer.eof(), |&(p, _)| p);
+            Ok(Value::Integer(number.parse().with_context(|| ParseNumberError::CannotConvertI64 {
+                num: number,
+                start,
+                end: end(),
+            })?))
+        }
+    }
+
+    /// read `number` of json as its source text, and return it with start position.
+    fn lex_number(&self, lexer: &mut Lexer) -> anyhow::Result<(Position, String)> {
         let mut number = String::new();
         let &(start, _) = lexer.peek().ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             ParseNumberError::UnexpectedEof { num: number.clone(), start: eof, end: eof }
         })?;
         if let Ok((_c, minus)) = lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Minus) {
             number.push(minus);
+        } else if self.options.relaxed_numbers {
+            // leading plus is not part of number
+            lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Plus).ok();
         }
-        if let Ok((_, zero)) = lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Zero) {
+        let lexed = |number: &str| number.bytes().filter(u8::is_ascii_digit).count();
+        if let Ok((pos, zero)) = lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Zero) {
             number.push(zero);
-        } else {
-            number.push_str(&self.parse_digits(lexer, start)?);
-        }
-
-        let &(_, c) = lexer.peek().unwrap_or(&(lexer.json.eof(), '\0'));
-        if matches!(NumberToken::tokenize(c), NumberToken::Dot | NumberToken::Exponent) {
-            if lexer.is_next::<_, SkipWs<false>>(NumberToken::Dot) {
-                number.push_str(&self.parse_fraction(lexer, start)?);
-            }
-            if lexer.is_next::<_, SkipWs<false>>(NumberToken::Exponent) {
-                number.push_str(&self.parse_exponent(lexer, start)?);
+            if let Some(&(_, c)) = lexer.peek() {
+                if matches!(NumberToken::tokenize(c), NumberToken::Zero | NumberToken::OneNine(_)) {
+                    if !self.options.leading_zeros {
+                        return Err(ParseNumberError::LeadingZeros { pos })?;
+                    }
+                    number.push_str(&self.parse_digits(lexer, start, lexed(&number))?);
+                }
             }
-            let &(end, _) = lexer.peek().unwrap_or(&(lexer.json.eof(), '\0'));
-            Ok(Value::Float(number.parse().with_context(|| ParseNumberError::CannotConvertF64 {
-                num: number,
-                start,
-                end,
-            })?))
         } else {
-            let eof = lexer.json.eof();
-            let &(end, _) = lexer.peek().unwrap_or(&(eof, '\0'));
-            Ok(Value::Integer(number.parse().with_context(|| ParseNumberError::CannotConvertI64 {
-                num: number,
-                start,
-                end,
-            })?))
+            number.push_str(&self.parse_digits(lexer, start, 0)?);
+        }
+        if lexer.is_next::<_, SkipWs<false>>(NumberToken::Dot) {
+            number.push_str(&self.parse_fraction(lexer, start, lexed(&number))?);
         }
+        if lexer.is_next::<_, SkipWs<false>>(NumberToken::Exponent) {
+            number.push_str(&self.parse_exponent(lexer, start, lexed(&number))?);
+        }
+        Ok((start, number))
     }
 
     /// parse `digits` of json. the following ebnf is not precise.<br>
-    /// `digits` := { "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" }
-    fn parse_digits(&self, lexer: &mut Lexer, start: Position) -> anyhow::Result<String> {
+    /// `digits` := { "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" }<br>
+    /// `lexed` is the number of digits already read in the number, it is used to limit total digits.
+    fn parse_digits(&self, lexer: &mut Lexer, start: Position, lexed: usize) -> anyhow::Result<String> {
         let mut digits = String::new();
-        while let Some(&(_, c)) = lexer.peek() {
-            if matches!(NumberToken::tokenize(c), NumberToken::Zero | NumberToken::OneNine(_)) {
-                let (_, digit) = lexer.next().unwrap_or_else(|| unreachable!("previous peek ensure this next success"));
+        while let Some(&(pos, digit)) = lexer.peek() {
+            if matches!(NumberToken::tokenize(digit), NumberToken::Zero | NumberToken::OneNine(_)) {
+                match self.options.max_number_digits {
+                    Some(limit) if lexed + digits.len() >= limit => {
+                        return Err(ParseNumberError::TooManyDigits { limit, start, end: pos })?
+                    }
+                    _ => (),
+                }
+                lexer.next();
                 digits.push(digit)
             } else if digits.is_empty() {
                 return Err(ParseNumberError::EmptyDigits { pos: start })?;
@@ -254,28 +691,28 @@ impl Parser {
 
     /// parse `fraction_part` of json. the following ebnf is not precise.<br>
     /// `fraction_part` := "." `digits`
-    pub fn parse_fraction(&self, lexer: &mut Lexer, start: Position) -> anyhow::Result<String> {
+    pub fn parse_fraction(&self, lexer: &mut Lexer, start: Position, lexed: usize) -> anyhow::Result<String> {
         let mut fraction_component = String::new();
         let (_, dot) = lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Dot)?;
         fraction_component.push(dot);
-        fraction_component.push_str(&self.parse_digits(lexer, start)?);
+        fraction_component.push_str(&self.parse_digits(lexer, start, lexed)?);
         Ok(fraction_component)
     }
 
     /// parse `exponent_part` of json. the following ebnf is not precise.<br>
     /// `exponent_part` := ("E" | "e") \[ "+" | "-" \] `digits`
-    pub fn parse_exponent(&self, lexer: &mut Lexer, start: Position) -> anyhow::Result<String> {
+    pub fn parse_exponent(&self, lexer: &mut Lexer, start: Position, lexed: usize) -> anyhow::Result<String> {
         let mut exponent_component = String::new();
         let (_, exponent) = lexer.lex_1_char::<_, SkipWs<false>>(NumberToken::Exponent)?;
         exponent_component.push(exponent);
         let &(pos, sign_or_digits) = lexer.peek().ok_or_else(|| {
-            let eof = lexer.json.eof();
+            let eof = lexer.eof();
             ParseNumberError::UnexpectedEof { num: exponent_component.clone(), start, end: eof }
         })?;
         match NumberToken::tokenize(sign_or_digits) {
             NumberToken::Plus | NumberToken::Minus => {
-                let (_, sign) = lexer.next().unwrap_or_else(|| unreachable!("previous peek ensure this next success"));
-                exponent_component.push(sign)
+                lexer.next();
+                exponent_component.push(sign_or_digits)
             }
             NumberToken::Zero | NumberToken::OneNine(_) => (),
             sd => {
@@ -284,7 +721,7 @@ impl Parser {
                 return Err(SingleTokenError::UnexpectedToken { expected, found: sd, pos })?;
             }
         }
-        exponent_component.push_str(&self.parse_digits(lexer, start)?);
+        exponent_component.push_str(&self.parse_digits(lexer, start, lexed)?);
         Ok(exponent_component)
     }
 }
@@ -298,8 +735,8 @@ mod tests {
         let empty = "{}".into();
         let (mut lexer, parser) = (Lexer::new(&empty), Parser::new());
         let object = parser.parse_object(&mut lexer);
-        if let Value::Object(m) = object.unwrap() {
-            assert_eq!(m, LinkedHashMap::new());
+        if let Value::Object(m) = &object.unwrap() {
+            assert_eq!(**m, Map::new());
         } else {
             unreachable!("\"{{}}\" must be parsed as empty object");
         }
@@ -312,8 +749,8 @@ mod tests {
         let empty = "[\r\n \t \n  ]".into();
         let (mut lexer, parser) = (Lexer::new(&empty), Parser::new());
         let array = parser.parse_array(&mut lexer);
-        if let Value::Array(v) = array.unwrap() {
-            assert_eq!(v, Vec::new());
+        if let Value::Array(v) = &array.unwrap() {
+            assert_eq!(v, &Vec::new());
         } else {
             unreachable!("\"[]\" must be parsed as empty array");
         }
@@ -321,6 +758,50 @@ mod tests {
         assert_eq!(lexer.next(), None);
     }
 
+    #[test]
+    fn test_parse_max_depth() {
+        let nested = r#"{"a": [1, {"b": [2]}], "c": [3]}"#.into();
+        let parser = Parser::with_options(ParserOptions::new().max_depth(4));
+        assert!(parser.parse_value(&mut Lexer::new(&nested)).is_ok());
+        let parser = Parser::with_options(ParserOptions::new().max_depth(3));
+        let err = parser.parse_value(&mut Lexer::new(&nested)).unwrap_err();
+        assert!(err.to_string().contains("depth"));
+        assert!(err.to_string().contains("line 1 (col 17)"));
+
+        let deep = "[".repeat(100_000).into();
+        let parser = Parser::with_options(ParserOptions::new().max_depth(128));
+        let err = parser.parse_value(&mut Lexer::new(&deep)).unwrap_err();
+        assert!(err.to_string().contains("128"));
+
+        let parser = Parser::with_options(ParserOptions::new().max_depth(2));
+        for _ in 0..3 {
+            assert!(parser.parse_value(&mut Lexer::new(&"[[1,]]".into())).is_err());
+        }
+        assert!(parser.parse_value(&mut Lexer::new(&"[[1]]".into())).is_ok());
+    }
+
+    #[test]
+    fn test_parse_resource_limits() {
+        let json = r#"{"key": ["one", "two", "three"], "value": {"four": 4}}"#.into();
+        let parser = Parser::with_options(ParserOptions::new().max_nodes(7).max_string_length(5).max_entries(3));
+        assert!(parser.parse_value(&mut Lexer::new(&json)).is_ok());
+
+        let parser = Parser::with_options(ParserOptions::new().max_nodes(6));
+        let err = parser.parse_value(&mut Lexer::new(&json)).unwrap_err();
+        assert!(err.to_string().contains("number of values exceeds the limit 6"));
+        assert!(err.to_string().contains("line 1 (col 52)"));
+
+        let parser = Parser::with_options(ParserOptions::new().max_string_length(4));
+        let err = parser.parse_value(&mut Lexer::new(&json)).unwrap_err();
+        assert!(err.to_string().contains("length of string exceeds the limit 4"));
+        assert!(err.to_string().contains("line 1 (col 24)"));
+
+        let parser = Parser::with_options(ParserOptions::new().max_entries(2));
+        let err = parser.parse_value(&mut Lexer::new(&json)).unwrap_err();
+        assert!(err.to_string().contains("number of entries exceeds the limit 2"));
+        assert!(err.to_string().contains("line 1 (col 9)"));
+    }
+
     #[test]
     fn test_parse_bool() {
         let parser = Parser::new();
@@ -398,6 +879,24 @@ mod tests {
         assert_eq!(lexer.next(), None);
     }
 
+    #[test]
+    fn test_parse_string_strict() {
+        let control = "\"Ru\tst\u{1}\"".into();
+        let (mut lexer, parser) = (Lexer::new(&control), Parser::new());
+        let lenient = parser.parse_string(&mut lexer).unwrap();
+        assert_eq!(lenient, Value::String("Ru\tst\u{1}".to_string()));
+
+        let (mut lexer, parser) = (Lexer::new(&control), Parser::with_options(ParserOptions::new().strict(true)));
+        let err = parser.parse_string(&mut lexer).unwrap_err();
+        assert!(err.to_string().contains("U+0009"));
+        assert!(err.to_string().contains("line 1 (col 4)"));
+
+        let escaped = r#""Ru\tst""#.into();
+        let (mut lexer, parser) = (Lexer::new(&escaped), Parser::with_options(ParserOptions::new().strict(true)));
+        let strict = parser.parse_string(&mut lexer).unwrap();
+        assert_eq!(strict, Value::String("Ru\tst".to_string()));
+    }
+
     #[test]
     fn test_parse_number() {
         let hundred = "100".into();
@@ -428,4 +927,115 @@ mod tests {
         assert_eq!(lexer.next(), Some(((0, 5), '\n')));
         assert_eq!(lexer.next(), None);
     }
+
+    #[test]
+    fn test_parse_projection() {
+        let raw = r#"{"id": 7, "skip": {"s": "}]\"[{", "n": [1, [2, {}]]}, "list": [{"a": 1, "b": 2}, "x", {"a": 3}]}"#;
+        let path = |indexers: Vec<JsonIndexer>| indexers.into_iter().collect::<JsonPath>();
+        let paths = [
+            path(vec![JsonIndexer::ObjInd("id".to_string())]),
+            path(vec![JsonIndexer::ObjInd("list".to_string()), JsonIndexer::ArrInd(2)]),
+        ];
+        let json = Value::parse_projection(raw, &paths).unwrap();
+        assert_eq!(json.to_string(), r#"{"id":7,"list":[null,null,{"a":3}]}"#);
+
+        let json = Value::parse_projection(raw, &[JsonPath::new()]).unwrap();
+        assert_eq!(json, Value::parse(raw).unwrap());
+
+        let unclosed = r#"{"id": 7, "skip": {"s": [1, 2}"#;
+        let err = Value::parse_projection(unclosed, &paths[..1]).unwrap_err();
+        assert!(err.to_string().contains("skipped value is not closed"));
+        assert!(Value::parse_projection(r#"{"id": 7, "skip": tru}"#, &paths[..1]).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_parse_numeric_keys() {
+        let raw = r#"{1: "one", -2.5e1: "minus", "3": "three"}"#;
+        assert!(Value::parse(raw).is_err());
+        let report = Value::parse_report(raw, ParserOptions::new().numeric_keys(true)).unwrap();
+        assert_eq!(report.value.to_string(), r#"{"1":"one","-2.5e1":"minus","3":"three"}"#);
+        assert_eq!(
+            report.warnings,
+            vec![
+                ParseWarning::NumericKey { key: "1".to_string(), pos: (0, 1) },
+                ParseWarning::NumericKey { key: "-2.5e1".to_string(), pos: (0, 11) },
+            ]
+        );
+        assert_eq!(report.warnings[0].to_string(), "line 1 (col 2): unquoted numeric key 1 is converted into string");
+
+        let report = Value::parse_report(r#"{"1": 1}"#, ParserOptions::new().numeric_keys(true)).unwrap();
+        assert!(report.warnings.is_empty());
+        assert!(Value::parse_with("{1x: 1}", ParserOptions::new().numeric_keys(true)).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sorted-map", ignore = "depend on insertion order")]
+    fn test_parse_with_reviver() {
+        let raw = r#"{"created": "2022-01-01", "tags": ["A", "b"], "count": 1}"#;
+        let mut visited = Vec::new();
+        let json = Value::parse_with_reviver(raw, |path, value| {
+            visited.push(path.to_string());
+            match &value {
+                Value::String(s) => Value::String(s.to_lowercase()),
+                Value::Integer(i) => Value::Integer(i * 10),
+                _ => value,
+            }
+        })
+        .unwrap();
+        assert_eq!(json.to_string(), r#"{"created":"2022-01-01","tags":["a","b"],"count":10}"#);
+        assert_eq!(visited, vec![r#""created""#, r#""tags">0"#, r#""tags">1"#, r#""tags""#, r#""count""#, ""]);
+    }
+
+    #[test]
+    fn test_parse_number_options() {
+        let err = Value::parse("[1, 012]").unwrap_err();
+        assert_eq!(err.to_string(), "line 1 (col 5): leading zeros are not allowed");
+        let leading_zeros = ParserOptions::new().leading_zeros(true);
+        assert_eq!(Value::parse_with("[012, -00.5, 0e1]", leading_zeros).unwrap().to_string(), "[12,-0.5,0]");
+
+        assert_eq!(Value::parse("-0").unwrap(), Value::Integer(0));
+        let negative_zero = Value::parse_with("-0", ParserOptions::new().negative_zero(true)).unwrap();
+        assert!(matches!(negative_zero, Value::Float(f) if f == 0.0 && f.is_sign_negative()));
+        assert_eq!(Value::parse_with("0", ParserOptions::new().negative_zero(true)).unwrap(), Value::Integer(0));
+
+        assert!(Value::parse("[+1]").is_err());
+        let relaxed = || ParserOptions::new().relaxed_numbers(true);
+        let json = Value::parse_with("[+123, +1.5e3, -4, +0.25, 5]", relaxed()).unwrap();
+        assert_eq!(json, Value::parse("[123, 1.5e3, -4, 0.25, 5]").unwrap());
+        for invalid in ["+", "++1", "+-1", "-+1", "+.5", "+012"] {
+            assert!(Value::parse_with(invalid, relaxed()).is_err(), "{invalid:?} must be invalid");
+        }
+        let lossless = relaxed().lossless_number(true);
+        assert_eq!(Value::parse_with("+1.50", lossless).unwrap().to_string(), "1.50");
+
+        let lossless = ParserOptions::new().leading_zeros(true).lossless_number(true);
+        let json = Value::parse_with("[012, -00.50, 000, -0, 0e1, 00e1]", lossless).unwrap();
+        assert_eq!(json.to_string(), "[12,-0.50,0,-0,0e1,0e1]");
+        assert!(Value::parse(json.to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_tolerant() {
+        let tolerant = || ParserOptions::new().tolerant(true);
+        let raw = r#"{"a": [1, tru, "s\"]", 4], "b": {"c": 012, "d": "ok"}, "e": {"x": [1, 2}, "f": 1e}"#;
+        let report = Value::parse_report(raw, tolerant()).unwrap();
+        assert_eq!(
+            report.value.to_string(),
+            r#"{"a":[1,null,"s\"]",4],"b":{"c":null,"d":"ok"},"e":{"x":null},"f":null}"#
+        );
+        let positions: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|w| match w {
+                ParseWarning::Replaced { pos, .. } => *pos,
+                w => unreachable!("unexpected warning {w}"),
+            })
+            .collect();
+        assert_eq!(positions, vec![(0, 10), (0, 38), (0, 66), (0, 79)]);
+
+        assert!(Value::parse_report("[1, tru", tolerant()).is_err());
+        assert!(Value::parse_report("tru", tolerant()).is_err());
+        assert!(Value::parse_report("[[[1]]]", tolerant().max_depth(2)).is_err());
+    }
 }
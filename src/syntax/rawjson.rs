@@ -1,10 +1,12 @@
-use super::lexer::Lexer;
+use super::{error::Position, lexer::Lexer};
 
 /// [`RawJson`] represent raw json string sequence.
 /// each sequence is terminated in line feed `'\n'`.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct RawJson {
     json: Vec<Vec<char>>,
+    /// byte length of each row in source, including its line terminator such as `"\r\n"`.
+    bytes: Vec<usize>,
 }
 
 impl RawJson {
@@ -39,9 +41,19 @@ impl RawJson {
     }
 
     /// get lexer of raw json
-    pub fn lexer(&self) -> Lexer {
+    pub fn lexer(&self) -> Lexer<'_> {
         Lexer::new(self)
     }
+
+    /// convert position into byte offset in source. position beyond end of row or source is clamped.
+    /// this method's complexity is **O(`row` + `col`)**.
+    pub fn byte_offset(&self, (row, col): Position) -> usize {
+        let before: usize = self.bytes.iter().take(row).sum();
+        match self.json.get(row) {
+            Some(line) => before + line.iter().take(col).map(|c| c.len_utf8()).sum::<usize>().min(self.bytes[row]),
+            None => before,
+        }
+    }
 }
 
 impl std::fmt::Display for RawJson {
@@ -50,15 +62,23 @@ impl std::fmt::Display for RawJson {
     }
 }
 
+/// each item is treated as line, that is terminated by line feed.
 impl FromIterator<String> for RawJson {
     fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
-        Self {
-            json: iter
-                .into_iter()
-                .flat_map(|s| s.replace("\r\n", "\n").split('\n').map(|s| s.to_string()).collect::<Vec<_>>())
-                .map(|s| (s + "\n").chars().collect())
-                .collect(),
+        let (mut json, mut bytes) = (Vec::new(), Vec::new());
+        for s in iter {
+            let mut lines = s.split('\n').peekable();
+            while let Some(line) = lines.next() {
+                let content = if lines.peek().is_some() { line.strip_suffix('\r').unwrap_or(line) } else { line };
+                json.push(content.chars().chain(['\n']).collect());
+                bytes.push(line.len() + 1);
+            }
         }
+        if let Some(last) = bytes.last_mut() {
+            // last line is not terminated
+            *last -= 1;
+        }
+        Self { json, bytes }
     }
 }
 impl<'a> FromIterator<&'a str> for RawJson {
@@ -77,7 +97,7 @@ impl From<&str> for RawJson {
         if s.is_empty() {
             Vec::<&str>::new().into_iter().collect()
         } else {
-            s.replace("\r\n", "\n").split('\n').collect()
+            std::iter::once(s).collect()
         }
     }
 }
@@ -133,6 +153,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn test_json_iter() {
         let json: RawJson = "{\n\"b\": 2\r\n}".into();
         let expected = vec![vec!['{', '\n'], vec!['"', 'b', '"', ':', ' ', '2', '\n'], vec!['}', '\n']];
@@ -170,4 +191,15 @@ mod tests {
         let mut j_iter = json.into_iter();
         assert_eq!(j_iter.next(), None);
     }
+
+    #[test]
+    fn test_byte_offset() {
+        let source = "{\r\n\"あ\": 1,\n\"b\": 2}";
+        let json: RawJson = source.into();
+        assert_eq!(json.byte_offset((0, 0)), 0);
+        assert_eq!(json.byte_offset((1, 0)), 3);
+        assert_eq!(json.byte_offset((1, 2)), 7);
+        assert_eq!(&source[json.byte_offset((2, 5))..], "2}");
+        assert_eq!(json.byte_offset(json.eof()), source.len());
+    }
 }
@@ -0,0 +1,74 @@
+use super::error::{postr, Position};
+use std::ops::Range;
+
+/// [`Span`] is range of source text. `start` and `end` are (row, col) position, 0-indexed, and `byte_range` is
+/// byte offset of them in the source. it is used by [`crate::Error`] and [`crate::Document`].
+/// # examples
+/// ```
+/// use dyson::Span;
+/// let source = "{\n  \"key\": true\n}";
+/// let span = Span::new(source, 11..15);
+/// assert_eq!((span.start, span.end), ((1, 9), (1, 13)));
+/// assert_eq!(&source[span.byte_range.clone()], "true");
+/// assert_eq!(span.to_string(), "line 2 (col 10) - line 2 (col 14)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+    pub byte_range: Range<usize>,
+}
+impl Span {
+    /// get span of `byte_range` in `source`.
+    /// # panics
+    /// if `byte_range` is out of `source` or it is not on char boundary.
+    pub fn new(source: &str, byte_range: Range<usize>) -> Self {
+        let start = position(source, byte_range.start);
+        let end = position(&source[byte_range.start..], byte_range.len());
+        let end = if end.0 == 0 { (start.0, start.1 + end.1) } else { (start.0 + end.0, end.1) };
+        Self { start, end, byte_range }
+    }
+}
+
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} - {}", postr(&self.start), postr(&self.end))
+    }
+}
+
+/// convert byte offset of `source` into [`Position`].
+pub(crate) fn position(source: &str, offset: usize) -> Position {
+    let before = &source[..offset];
+    let row = before.matches('\n').count();
+    let col = before.rsplit('\n').next().map_or(0, |line| line.chars().count());
+    (row, col)
+}
+
+/// convert [`Position`] into byte offset of `source`, inverse of [`position`]. position beyond source is clamped.
+pub(crate) fn offset(source: &str, (row, col): Position) -> usize {
+    let start = match row {
+        0 => 0,
+        _ => source.match_indices('\n').nth(row - 1).map_or(source.len(), |(i, _)| i + 1),
+    };
+    source[start..].char_indices().nth(col).map_or(source.len(), |(i, _)| start + i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span() {
+        let source = "[\"あ\",\r\n  1]";
+        let span = Span::new(source, 1..6);
+        assert_eq!((span.start, span.end), ((0, 1), (0, 4)));
+        let span = Span::new(source, 6..12);
+        assert_eq!((span.start, span.end), ((0, 4), (1, 3)));
+        let span = Span::new(source, 13..13);
+        assert_eq!((span.start, span.end), ((1, 4), (1, 4)));
+        for i in [0, 1, 2, 5, 6, 7, 8, 9, 12, 13] {
+            assert_eq!(offset(source, position(source, i)), i);
+        }
+        assert_eq!(offset(source, (5, 0)), source.len());
+    }
+}
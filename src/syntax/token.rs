@@ -24,6 +24,7 @@ pub enum MainToken {
     Minus,
     Dot,
     Whitespace,
+    #[allow(dead_code)] // public variant, not produced by lexer now
     Eof,
     Undecided(char),
 }
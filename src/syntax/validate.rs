@@ -0,0 +1,482 @@
+use super::{
+    encoding,
+    error::{Error, Position, ValidateError},
+    token::{MainToken, SingleToken},
+};
+use crate::ast::{index::JsonIndexer, index_path::JsonPath, Value};
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead, BufReader, Bytes, Read},
+};
+
+/// check syntax of json read from `r` without building ast. memory usage is bounded by nesting depth, not by
+/// input size. it accept same json as [`crate::Value::parse`] with default options.
+pub(crate) fn validate<R: BufRead>(r: R) -> Result<(), Error> {
+    let mut validator = Validator::new(r);
+    validator.validate().map_err(|e| Error::located(e, |pos| validator.offset_of(pos)))
+}
+
+/// read json from `r` as stream, and get the value on `path`. only the source of the value is kept in memory, so
+/// memory usage is bounded by size of the value and nesting depth, not by input size. reading stop at the end of the
+/// value, so input after it is not checked. if there is no value on `path`, return `None`.
+/// # examples
+/// ```
+/// use dyson::{JsonPath, Value};
+/// let raw = r#"{"header": {"id": 1}, "records": [{"name": "a"}, {"name": "b", "tags": ["x", "y"]}]}"#;
+/// let path = JsonPath::from_dotted("records[1].tags").unwrap();
+/// assert_eq!(dyson::extract(raw.as_bytes(), &path).unwrap(), Some(Value::parse(r#"["x", "y"]"#).unwrap()));
+///
+/// let path = JsonPath::from_dotted("records[2]").unwrap();
+/// assert_eq!(dyson::extract(raw.as_bytes(), &path).unwrap(), None);
+/// ```
+pub fn extract<R: Read>(r: R, path: &JsonPath) -> Result<Option<Value>, Error> {
+    let mut validator = Validator::new(BufReader::new(r));
+    validator.extraction = Some(Extraction { target: path.clone(), levels: Vec::new(), recording: None, done: false });
+    validator.validate().map_err(|e| Error::located(e, |pos| validator.offset_of(pos)))?;
+    match validator.extraction {
+        Some(Extraction { recording: Some(source), done: true, .. }) => Ok(Some(Value::parse(source)?)),
+        _ => Ok(None),
+    }
+}
+
+/// number of recently read chars whose byte offset is kept for error span.
+const RECENT: usize = 8;
+
+enum Container {
+    Object,
+    Array,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Value,
+    ValueOrEnd,
+    Key,
+    KeyOrEnd,
+    Colon,
+    CommaOrEnd,
+    Done,
+}
+
+/// state of [`extract`]. it follow path of current value, and record source of the value on `target`.
+struct Extraction {
+    target: JsonPath,
+    /// for each open container, whether the container and its current child are on `target`, and next array index.
+    levels: Vec<Level>,
+    /// source of the value on `target`, or the key that is read now.
+    recording: Option<String>,
+    done: bool,
+}
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    on_target: bool,
+    child_on_target: bool,
+    next_index: usize,
+}
+impl Extraction {
+    /// check the value that start now is on `target`. it must be called once for each value.
+    fn enter_value(&mut self, in_array: bool) -> bool {
+        let depth = self.levels.len();
+        match self.levels.last_mut() {
+            None => true,
+            Some(level) if in_array => {
+                let index = level.next_index;
+                level.next_index += 1;
+                level.child_on_target =
+                    level.on_target && self.target.get(depth - 1) == Some(&JsonIndexer::ArrInd(index));
+                level.child_on_target
+            }
+            Some(level) => level.child_on_target,
+        }
+    }
+}
+
+struct Validator<R> {
+    extraction: Option<Extraction>,
+    bytes: Bytes<R>,
+    pos: Position,
+    offset: usize,
+    recent: VecDeque<(Position, usize)>,
+    number_start: (Position, usize),
+    peeked: Option<Option<(Position, char)>>,
+    stack: Vec<Container>,
+    state: State,
+}
+impl<R: BufRead> Validator<R> {
+    fn new(r: R) -> Self {
+        Self {
+            extraction: None,
+            bytes: r.bytes(),
+            pos: (0, 0),
+            offset: 0,
+            recent: VecDeque::with_capacity(RECENT),
+            number_start: ((0, 0), 0),
+            peeked: None,
+            stack: Vec::new(),
+            state: State::Value,
+        }
+    }
+
+    /// get byte offset of `pos`, that is current position or position of recently read char.
+    fn offset_of(&self, pos: Position) -> usize {
+        let found = self.recent.iter().chain([&self.number_start]).find(|&&(p, _)| p == pos);
+        found.map_or(self.offset, |&(_, offset)| offset)
+    }
+
+    /// read next utf-8 char from stream.
+    fn read_char(&mut self) -> anyhow::Result<Option<(Position, char)>> {
+        let (pos, offset) = (self.pos, self.offset);
+        let c = match encoding::read_utf8(&mut self.bytes) {
+            Ok(Some(c)) => c,
+            Ok(None) => return Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => Err(ValidateError::InvalidUtf8 { pos })?,
+            Err(e) => Err(e)?,
+        };
+        self.pos = if c == '\n' { (pos.0 + 1, 0) } else { (pos.0, pos.1 + 1) };
+        self.offset += c.len_utf8();
+        if self.recent.len() == RECENT {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((pos, offset));
+        Ok(Some((pos, c)))
+    }
+
+    fn peek(&mut self) -> anyhow::Result<Option<(Position, char)>> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.read_char()?);
+        }
+        Ok(self.peeked.flatten())
+    }
+
+    fn next(&mut self) -> anyhow::Result<Option<(Position, char)>> {
+        let next = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.read_char()?,
+        };
+        if let (Some(Extraction { recording: Some(recording), .. }), Some((_, c))) = (&mut self.extraction, next) {
+            recording.push(c);
+        }
+        Ok(next)
+    }
+
+    /// read next char, that must not be EOF.
+    fn expect_next(&mut self, expected: &'static str) -> anyhow::Result<(Position, char)> {
+        let pos = self.pos;
+        Ok(self.next()?.ok_or(ValidateError::UnexpectedEof { expected, pos })?)
+    }
+
+    fn validate(&mut self) -> anyhow::Result<()> {
+        loop {
+            if matches!(self.extraction, Some(Extraction { done: true, .. })) {
+                return Ok(());
+            }
+            while matches!(self.peek()?, Some((_, c)) if MainToken::tokenize(c) == MainToken::Whitespace) {
+                self.next()?;
+            }
+            let (pos, c) = match (self.state, self.peek()?) {
+                (State::Done, None) => return Ok(()),
+                (state, None) => Err(ValidateError::UnexpectedEof { expected: state.expected(), pos: self.pos })?,
+                (_, Some(next)) => next,
+            };
+            match (self.state, c) {
+                (State::ValueOrEnd, ']') | (State::KeyOrEnd, '}') => self.close()?,
+                (State::Value | State::ValueOrEnd, _) => self.value()?,
+                (State::Key | State::KeyOrEnd, '"') => {
+                    self.key()?;
+                    self.state = State::Colon;
+                }
+                (State::Colon, ':') => {
+                    self.next()?;
+                    self.state = State::Value;
+                }
+                (State::CommaOrEnd, ',') => {
+                    self.next()?;
+                    self.state = match self.stack.last() {
+                        Some(Container::Object) => State::Key,
+                        _ => State::Value,
+                    };
+                }
+                (State::CommaOrEnd, '}') if matches!(self.stack.last(), Some(Container::Object)) => self.close()?,
+                (State::CommaOrEnd, ']') if matches!(self.stack.last(), Some(Container::Array)) => self.close()?,
+                (state, found) => Err(ValidateError::UnexpectedChar { expected: state.expected(), found, pos })?,
+            }
+        }
+    }
+
+    /// close object or array, and go to state after value.
+    fn close(&mut self) -> anyhow::Result<()> {
+        self.next()?;
+        self.stack.pop();
+        if let Some(extraction) = &mut self.extraction {
+            extraction.levels.pop();
+        }
+        self.after_value();
+        Ok(())
+    }
+
+    fn after_value(&mut self) {
+        self.state = if self.stack.is_empty() { State::Done } else { State::CommaOrEnd };
+        if let Some(extraction) = &mut self.extraction {
+            // value on target end when its container is closed
+            extraction.done |= extraction.recording.is_some() && self.stack.len() == extraction.target.depth();
+        }
+    }
+
+    /// read key of object. if its object is on target of extraction, check the key is also on target.
+    fn key(&mut self) -> anyhow::Result<()> {
+        let depth = self.stack.len();
+        match &mut self.extraction {
+            Some(extraction) if extraction.levels.last().map_or(false, |level| level.on_target) => {
+                extraction.recording = Some(String::new());
+                self.next()?;
+                self.string()?;
+                let extraction = self.extraction.as_mut().expect("extraction is set above");
+                let key: String = Value::parse(extraction.recording.take().unwrap_or_default())?.try_into()?;
+                let on_target = extraction.target.get(depth - 1) == Some(&JsonIndexer::ObjInd(key));
+                if let Some(level) = extraction.levels.last_mut() {
+                    level.child_on_target = on_target;
+                }
+            }
+            _ => {
+                self.next()?;
+                self.string()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> anyhow::Result<()> {
+        let in_array = matches!(self.stack.last(), Some(Container::Array));
+        let depth = self.stack.len();
+        let mut container_on_target = false;
+        if let Some(extraction) = &mut self.extraction {
+            let on_target = extraction.enter_value(in_array);
+            if on_target && depth == extraction.target.depth() {
+                extraction.recording = Some(String::new());
+            }
+            container_on_target = on_target && depth < extraction.target.depth();
+        }
+        let (pos, c) = self.expect_next("value")?;
+        let level = Level { on_target: container_on_target, child_on_target: false, next_index: 0 };
+        match c {
+            '{' => {
+                self.stack.push(Container::Object);
+                self.extraction.iter_mut().for_each(|e| e.levels.push(level));
+                self.state = State::KeyOrEnd;
+                return Ok(());
+            }
+            '[' => {
+                self.stack.push(Container::Array);
+                self.extraction.iter_mut().for_each(|e| e.levels.push(level));
+                self.state = State::ValueOrEnd;
+                return Ok(());
+            }
+            '"' => self.string()?,
+            '-' | '0'..='9' => self.number(pos, c)?,
+            't' => self.literal("true")?,
+            'f' => self.literal("false")?,
+            'n' => self.literal("null")?,
+            found => Err(ValidateError::UnexpectedChar { expected: "value", found, pos })?,
+        }
+        self.after_value();
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &'static str) -> anyhow::Result<()> {
+        for expected in literal.chars().skip(1) {
+            let (pos, found) = self.expect_next(literal)?;
+            if found != expected {
+                Err(ValidateError::UnexpectedChar { expected: literal, found, pos })?
+            }
+        }
+        Ok(())
+    }
+
+    /// read rest of string after opening quotation.
+    fn string(&mut self) -> anyhow::Result<()> {
+        loop {
+            match self.expect_next("closing quotation")? {
+                (_, '"') => return Ok(()),
+                (pos, '\n') => Err(ValidateError::UnexpectedChar { expected: "closing quotation", found: '\n', pos })?,
+                (_, '\\') => match self.expect_next("escape sequence")? {
+                    (_, '"' | '\\' | '/' | 'n' | 'r' | 't') => (),
+                    (pos, 'u') => {
+                        let mut hex = 0;
+                        for _ in 0..4 {
+                            let (p, c) = self.expect_next("hex digit")?;
+                            let digit = c.to_digit(16).ok_or(ValidateError::UnexpectedChar {
+                                expected: "hex digit",
+                                found: c,
+                                pos: p,
+                            })?;
+                            hex = hex * 16 + digit;
+                        }
+                        char::from_u32(hex).ok_or(ValidateError::InvalidUnicode { pos })?;
+                    }
+                    (pos, found) => Err(ValidateError::UnexpectedChar { expected: "escape sequence", found, pos })?,
+                },
+                _ => (),
+            }
+        }
+    }
+
+    fn number(&mut self, start: Position, first: char) -> anyhow::Result<()> {
+        self.number_start = (start, self.offset_of(start));
+        let negative = first == '-';
+        let (pos, first) = if negative { self.expect_next("digit")? } else { (start, first) };
+        let accumulate = |integer: Option<i64>, digit: char| {
+            let d = digit.to_digit(10).map(i64::from)?;
+            integer?.checked_mul(10).and_then(|i| if negative { i.checked_sub(d) } else { i.checked_add(d) })
+        };
+        let mut integer = Some(0);
+        match first {
+            '0' => {
+                if matches!(self.peek()?, Some((_, '0'..='9'))) {
+                    Err(ValidateError::LeadingZeros { pos })?
+                }
+            }
+            '1'..='9' => {
+                integer = accumulate(integer, first);
+                while let Some((_, c @ '0'..='9')) = self.peek()? {
+                    self.next()?;
+                    integer = accumulate(integer, c);
+                }
+            }
+            found => Err(ValidateError::UnexpectedChar { expected: "digit", found, pos })?,
+        }
+        let mut float = false;
+        if matches!(self.peek()?, Some((_, '.'))) {
+            self.next()?;
+            self.digits()?;
+            float = true;
+        }
+        if matches!(self.peek()?, Some((_, 'e' | 'E'))) {
+            self.next()?;
+            if matches!(self.peek()?, Some((_, '+' | '-'))) {
+                self.next()?;
+            }
+            self.digits()?;
+            float = true;
+        }
+        match integer {
+            None if !float => Err(ValidateError::IntegerOverflow { pos: start })?,
+            _ => Ok(()),
+        }
+    }
+
+    /// read one or more digits.
+    fn digits(&mut self) -> anyhow::Result<()> {
+        let (pos, found) = self.expect_next("digit")?;
+        if !found.is_ascii_digit() {
+            Err(ValidateError::UnexpectedChar { expected: "digit", found, pos })?
+        }
+        while let Some((_, '0'..='9')) = self.peek()? {
+            self.next()?;
+        }
+        Ok(())
+    }
+}
+
+impl State {
+    fn expected(&self) -> &'static str {
+        match self {
+            State::Value => "value",
+            State::ValueOrEnd => "value or ']'",
+            State::Key => "string",
+            State::KeyOrEnd => "string or '}'",
+            State::Colon => "':'",
+            State::CommaOrEnd => "',' or closing bracket",
+            State::Done => "EOF",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Value;
+
+    #[test]
+    fn test_validate_agree_with_parser() {
+        let inputs = [
+            "{}",
+            "[]",
+            r#"{"key": [1, -2.5e+3, true, false, null, "str\"\\\/\nあ"], "nested": {"a": {}}}"#,
+            "  [ 1 ,\n 2 ]\n",
+            "0",
+            "-0.0E-1",
+            "9223372036854775807",
+            "-9223372036854775808",
+            "9223372036854775808",
+            "1e400",
+            "\"\u{3042}\"",
+            "",
+            "[1, 2",
+            r#"{"key" 1}"#,
+            r#"{"key": 1,}"#,
+            "[1,]",
+            "[,]",
+            "{,}",
+            "tru",
+            "nul",
+            "[1] 2",
+            "012",
+            "-",
+            "1.",
+            "1e",
+            ".5",
+            "+1",
+            r#""\x""#,
+            r#""\b""#,
+            r#""\u12""#,
+            r#""\ud800""#,
+            "\"line\nbreak\"",
+            r#"{"a": 1]"#,
+            r#"[1}"#,
+            r#"{1: 2}"#,
+        ];
+        for input in inputs {
+            let (validated, parsed) = (validate(input.as_bytes()), Value::parse(input));
+            assert_eq!(validated.is_ok(), parsed.is_ok(), "{input:?}: {validated:?}, {parsed:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_error() {
+        let err = validate(r#"{"key": [1, 2}"#.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 1 (col 14): expected ',' or closing bracket, but found '}'");
+        let err = validate("[\n1,\n".as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "line 3 (col 1): expected value, but found EOF");
+        let err = validate(&b"[\"\xff\"]"[..]).unwrap_err();
+        assert_eq!(err.to_string(), "line 1 (col 3): stream did not contain valid UTF-8");
+        assert_eq!(err.span().map(|s| s.byte_range.clone()), Some(2..2));
+
+        let source = "[\"\u{3042}\",\n  98765432109876543210]";
+        let err = validate(source.as_bytes()).unwrap_err();
+        assert_eq!(err.span().map(|s| &source[s.byte_range.start..]), Some("98765432109876543210]"));
+        let err = validate("{\"あ\": [1 2]}".as_bytes()).unwrap_err();
+        assert_eq!(err.span().map(|s| (s.start, s.byte_range.clone())), Some(((0, 9), 11..11)));
+    }
+
+    #[test]
+    fn test_extract() {
+        let raw = r#"{"a": {"b\"c": [1, {"d": [2, 3]}, "x"]}, "e": null, "a2": 0}"#;
+        let json = Value::parse(raw).unwrap();
+        for dotted in
+            ["", "a", r#"a["b\"c"]"#, r#"a["b\"c"][1].d"#, r#"a["b\"c"][1].d[1]"#, r#"a["b\"c"][2]"#, "e", "a2"]
+        {
+            let path = JsonPath::from_dotted(dotted).unwrap();
+            assert_eq!(extract(raw.as_bytes(), &path).unwrap().as_ref(), json.get(&path), "{dotted}");
+        }
+        for dotted in ["b", "a[0]", r#"a["b\"c"][3]"#, "e.f", "a2[0]"] {
+            let path = JsonPath::from_dotted(dotted).unwrap();
+            assert_eq!(extract(raw.as_bytes(), &path).unwrap(), None, "{dotted}");
+        }
+
+        // reading stop at the end of extracted value
+        let path = JsonPath::from_dotted("a").unwrap();
+        assert_eq!(extract(r#"{"a": 1, "b": invalid"#.as_bytes(), &path).unwrap(), Some(Value::Integer(1)));
+        let err = extract(r#"{"b": invalid, "a": 1}"#.as_bytes(), &path).unwrap_err();
+        assert!(err.to_string().contains("line 1 (col 7)"));
+    }
+}
@@ -0,0 +1,87 @@
+//! differential test between dyson and serde_json.
+//!
+//! each file in `tests/compat/corpus` is parsed by both parsers, and their acceptance and resulting structure are
+//! compared. known disagreements are recorded in `tests/compat/known_gaps.txt`, and this test fail if disagreements
+//! differ from the record. run with `DYSON_COMPAT_RECORD=1` to record current disagreements.
+
+use dyson::Value;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+const RECORD_ENV: &str = "DYSON_COMPAT_RECORD";
+
+fn compat_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/compat")
+}
+
+/// convert serde_json value into dyson value, to compare structure.
+fn from_serde(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Object(m) => m.into_iter().map(|(k, v)| (k, from_serde(v))).collect(),
+        serde_json::Value::Array(v) => Value::Array(v.into_iter().map(from_serde).collect()),
+        serde_json::Value::Bool(b) => Value::Bool(b),
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::String(s) => Value::String(s),
+        serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => Value::Integer(i),
+            (None, Some(f)) => Value::Float(f),
+            (None, None) => unreachable!("serde_json number without arbitrary precision is i64, u64 or f64"),
+        },
+    }
+}
+
+/// compare dyson and serde_json with `input`. if they disagree, return description of the disagreement.
+fn disagreement(input: &[u8]) -> Option<String> {
+    match (Value::read(input), serde_json::from_slice::<serde_json::Value>(input).map(from_serde)) {
+        (Ok(dyson), Ok(serde)) if dyson == serde => None,
+        (Ok(dyson), Ok(serde)) => Some(format!("structure differ: dyson {}, serde_json {}", dyson, serde)),
+        (Ok(dyson), Err(serde)) => Some(format!("only dyson accept: {}, serde_json error: {}", dyson, serde)),
+        (Err(dyson), Ok(serde)) => Some(format!("only serde_json accept: {}, dyson error: {}", serde, dyson)),
+        (Err(_), Err(_)) => None,
+    }
+}
+
+/// run all corpus, and get disagreements by file name.
+fn replay() -> BTreeMap<String, String> {
+    let mut corpus: Vec<_> = std::fs::read_dir(compat_dir().join("corpus"))
+        .expect("corpus directory exist")
+        .map(|entry| entry.expect("readable entry").path())
+        .collect();
+    corpus.sort();
+    corpus
+        .into_iter()
+        .filter_map(|path| {
+            let input = std::fs::read(&path).expect("readable corpus");
+            let name = path.file_name().expect("corpus is file").to_string_lossy().to_string();
+            disagreement(&input).map(|d| (name, d))
+        })
+        .collect()
+}
+
+#[test]
+#[cfg_attr(feature = "sorted-map", ignore = "known gaps are recorded with insertion order map")]
+fn test_compat_with_serde_json() {
+    let known_gaps_path = compat_dir().join("known_gaps.txt");
+    let disagreements = replay();
+    if std::env::var_os(RECORD_ENV).is_some() {
+        let record: String =
+            disagreements.iter().map(|(name, d)| format!("{name}\t{}\n", d.replace('\n', " "))).collect();
+        std::fs::write(&known_gaps_path, record).expect("writable record");
+        return;
+    }
+
+    let known_gaps: BTreeMap<_, _> = std::fs::read_to_string(&known_gaps_path)
+        .expect("readable record")
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, d)| (name.to_string(), d.to_string()))
+        .collect();
+    let new_gaps: Vec<_> = disagreements.iter().filter(|(name, _)| !known_gaps.contains_key(*name)).collect();
+    let fixed_gaps: Vec<_> = known_gaps.keys().filter(|name| !disagreements.contains_key(*name)).collect();
+    assert!(
+        new_gaps.is_empty() && fixed_gaps.is_empty(),
+        "disagreements with serde_json are changed (run with {RECORD_ENV}=1 to record them)\nnew: {new_gaps:#?}\nfixed: {fixed_gaps:#?}"
+    );
+}
@@ -0,0 +1,25 @@
+//! load json files encoded in UTF-8, UTF-16 and UTF-32, with and without BOM.
+
+use dyson::Value;
+use std::path::Path;
+
+#[test]
+fn test_load_encoded_files() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/encoding");
+    let expected = Value::load(dir.join("utf8.json")).unwrap();
+    assert_eq!(expected["emoji"], Value::from("あ😀"));
+    for encoding in ["utf8", "utf16le", "utf16be", "utf32le", "utf32be"] {
+        for name in [format!("{encoding}.json"), format!("{encoding}_bom.json")] {
+            let loaded = Value::load(dir.join(&name)).unwrap_or_else(|e| panic!("{name}: {e}"));
+            assert_eq!(loaded, expected, "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_invalid_encoded_file() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/encoding/utf16le_bom.json");
+    let bytes = std::fs::read(path).unwrap();
+    let err = Value::read(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert!(err.to_string().contains("UTF-16LE"));
+}